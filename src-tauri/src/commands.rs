@@ -2,7 +2,25 @@
 // TAURI COMMANDS
 // ============================================================================
 
-use crate::config::{ConfigPaths, WaybarConfigFile};
+use crate::config::backups::{BackupInfo, RenamedBackup};
+use crate::config::catalog::ModuleSpec;
+use crate::config::css::{ColorDef, CssWarning, NormalizedColors};
+use crate::config::edit::{ConfigEdit, ScriptRelink};
+use crate::config::encoding::EncodingReport;
+use crate::config::git::GitCommit;
+use crate::config::include::{DuplicateInclude, FlattenedConfig, IncludeResolution, PlannedFile};
+use crate::config::legacy::LegacyConfigAction;
+use crate::config::migrate::{Migration, ModuleRename};
+use crate::config::lint::{
+    BarFieldDifference, DuplicateClickAction, LintWarning, ModuleIssue, MprisPlayerReport,
+};
+use crate::config::parser::ParseBenchmark;
+use crate::config::quotes::SingleQuotedString;
+use crate::config::schema::SchemaViolation;
+use crate::config::snapshots::SnapshotInfo;
+use crate::config::watch::ConfigWatcher;
+use crate::config::writer::ConfigDiff;
+use crate::config::{ArrayStrategy, ConfigDocument, ConfigPaths, WaybarConfigFile};
 use crate::error::{AppError, Result};
 use std::fs;
 
@@ -10,7 +28,17 @@ use std::fs;
 /// Checks for config directory and files at standard locations
 #[tauri::command]
 pub async fn detect_config_paths() -> Result<ConfigPaths> {
-    let paths = ConfigPaths::default()?;
+    detect_config_paths_in(None).await
+}
+
+/// Detect Waybar configuration paths in a given directory
+/// Falls back to the default directory (see `detect_config_paths`) when `dir` is `None`
+#[tauri::command]
+pub async fn detect_config_paths_in(dir: Option<String>) -> Result<ConfigPaths> {
+    let paths = match dir {
+        Some(dir) => ConfigPaths::in_dir(&dir),
+        None => ConfigPaths::default()?,
+    };
 
     // Check if config directory exists
     if !paths.config_exists() {
@@ -33,6 +61,13 @@ pub async fn detect_config_paths() -> Result<ConfigPaths> {
     }
 }
 
+/// Create the Waybar config directory with a starter config and stylesheet
+/// Fails with `AlreadyExists` rather than overwrite an existing configuration
+#[tauri::command]
+pub async fn create_default_config() -> Result<ConfigPaths> {
+    crate::config::scaffold::create_default_config(&ConfigPaths::default()?)
+}
+
 /// Load Waybar configuration file
 /// Handles JSONC format (strips comments before returning)
 #[tauri::command]
@@ -46,34 +81,127 @@ pub async fn load_config(path: String) -> Result<WaybarConfigFile> {
         }
     })?;
 
-    // Strip JSONC comments and validate JSON
-    let stripped = crate::config::parser::strip_jsonc_comments(&content);
+    // Parse errors point at the line and column in the file, comments included
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    let has_includes = crate::config::include::has_includes(&config);
 
-    // Validate it's valid JSON
-    crate::config::parser::validate_json(&stripped)?;
+    // serde_json keeps the last of duplicate keys silently
+    let warnings = crate::config::parser::find_duplicate_keys(&content)
+        .into_iter()
+        .map(|(key, line)| {
+            format!("Duplicate key \"{}\" on line {}; only the last definition is used", key, line)
+        })
+        .collect();
 
     Ok(WaybarConfigFile {
         content,
         path: path.clone(),
+        warnings,
+        has_includes,
     })
 }
 
+/// Number of backups a save keeps, defaulting to 10
+/// Zero is refused, as pruning would delete the backup the save just made
+fn backup_limit(max_backups: Option<usize>) -> Result<usize> {
+    match max_backups.unwrap_or(crate::config::writer::DEFAULT_MAX_BACKUPS) {
+        0 => Err(AppError::Validation("At least one backup must be kept".to_string())),
+        max_keep => Ok(max_keep),
+    }
+}
+
 /// Save Waybar configuration file
 /// Creates automatic backup before writing
+/// Keeps the newest `max_backups` backups of the file (default 10)
 #[tauri::command]
-pub async fn save_config(path: String, content: String) -> Result<()> {
+pub async fn save_config(path: String, content: String, max_backups: Option<usize>) -> Result<()> {
+    let max_keep = backup_limit(max_backups)?;
+
     // Validate it's valid JSON before saving
     crate::config::parser::validate_json(&content)?;
 
-    // Add comments header
-    let with_comments = crate::config::writer::add_config_comments(&content);
+    // Add comments header, keeping the comments of unchanged keys
+    let previous = fs::read_to_string(&path).ok();
+    let output = crate::config::writer::config_output(&content, previous.as_deref());
 
     // Write with backup
-    crate::config::writer::write_config_file(&path, &with_comments)?;
+    crate::config::writer::write_config_file(&path, &output)?;
+
+    crate::config::writer::rotate_backups(&path, max_keep);
 
     Ok(())
 }
 
+/// Show what `save_config` would change on disk, without writing anything
+/// A missing file diffs as empty, so every line shows as added
+/// Comments of the current file that the save can't keep are listed in
+/// `dropped_comments`
+#[tauri::command]
+pub async fn preview_save_config(path: String, content: String) -> Result<ConfigDiff> {
+    crate::config::parser::validate_json(&content)?;
+
+    let previous = match fs::read_to_string(&path) {
+        Ok(previous) => Some(previous),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let output = crate::config::writer::config_output(&content, previous.as_deref());
+
+    Ok(crate::config::writer::diff_config(&path, previous.as_deref().unwrap_or(""), &output))
+}
+
+/// Check the types of known bar and module options
+/// Each violation carries a JSON pointer to the offending value
+#[tauri::command]
+pub async fn validate_config_schema(content: String) -> Result<Vec<SchemaViolation>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::schema::validate_config_schema(&config))
+}
+
+/// Validate config content before a reload
+/// Fails on invalid JSONC; otherwise returns problems with the placed modules
+/// (unknown module types, undefined `custom/*` or `group/*` modules)
+#[tauri::command]
+pub async fn validate_config(content: String) -> Result<Vec<ModuleIssue>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_module_names(&config))
+}
+
+/// Count the bars defined in config content (1 for a single bar object)
+#[tauri::command]
+pub async fn count_bars(content: String) -> Result<usize> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(ConfigDocument::from_value(config)?.bar_count())
+}
+
+/// Load JSONC config fragments and deep-merge them in order, later files winning
+/// Arrays are replaced unless `strategy` is `concat`
+#[tauri::command]
+pub async fn merge_config_files(
+    paths: Vec<String>,
+    strategy: Option<ArrayStrategy>,
+) -> Result<serde_json::Value> {
+    let mut fragments = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let content = fs::read_to_string(path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                AppError::NotFound(format!("Config file not found: {}", path))
+            } else {
+                AppError::from(e)
+            }
+        })?;
+        let fragment = crate::config::parser::parse_jsonc(&content)
+            .map_err(|e| AppError::Parse(format!("{}: {}", path, e.message())))?;
+        fragments.push(fragment);
+    }
+
+    let strategy = strategy.unwrap_or_default();
+    fragments
+        .into_iter()
+        .reduce(|base, overlay| crate::config::merge_configs_with(base, overlay, strategy))
+        .ok_or_else(|| AppError::Validation("No config files to merge".to_string()))
+}
+
 /// Load CSS style file
 #[tauri::command]
 pub async fn load_css(path: String) -> Result<String> {
@@ -88,47 +216,138 @@ pub async fn load_css(path: String) -> Result<String> {
 
 /// Save CSS style file
 /// Creates automatic backup before writing
+/// Keeps the newest `max_backups` backups of the file (default 10)
 #[tauri::command]
-pub async fn save_css(path: String, content: String) -> Result<()> {
+pub async fn save_css(path: String, content: String, max_backups: Option<usize>) -> Result<()> {
+    let max_keep = backup_limit(max_backups)?;
+
     // Basic CSS validation (check it's not empty)
     if content.trim().is_empty() {
         return Err(AppError::Validation("CSS content cannot be empty".to_string()));
     }
+    crate::config::css::validate_css(&content)?;
+
+    // Keep the line endings the file already has
+    let previous = fs::read_to_string(&path).ok();
+    let content = crate::config::writer::match_line_ending(&content, previous.as_deref());
 
     // Write with backup
     crate::config::writer::write_config_file(&path, &content)?;
+    crate::config::writer::rotate_backups(&path, max_keep);
 
     Ok(())
 }
 
-/// List all backup files in config directory
+/// Watch the config and style files, emitting `config-file-changed` when either changes on disk
+/// Replaces any watch already running. Writes made by the app itself are skipped.
 #[tauri::command]
-pub async fn list_backups(config_dir: String) -> Result<Vec<String>> {
-    let entries = fs::read_dir(&config_dir)?;
+pub async fn watch_config(app: tauri::AppHandle, paths: ConfigPaths) -> Result<()> {
+    use tauri::Emitter;
 
-    let mut backups = Vec::new();
-    for entry in entries {
-        let entry = entry?;
-        let file_name = entry
-            .file_name()
-            .to_str()
-            .ok_or_else(|| AppError::Internal("Invalid UTF-8 in filename".to_string()))?
-            .to_string();
-
-        if file_name.contains(".backup.") {
-            backups.push(file_name);
+    let watcher = ConfigWatcher::start(&paths, crate::config::watch::DEFAULT_DEBOUNCE, move |change| {
+        if let Err(e) = app.emit(crate::config::watch::CONFIG_CHANGED_EVENT, change) {
+            log::warn!("Failed to emit config change event: {}", e);
         }
-    }
+    })?;
+    crate::config::watch::set_active_watcher(Some(watcher))?;
+    Ok(())
+}
+
+/// Stop watching the config and style files
+#[tauri::command]
+pub async fn stop_watch_config() -> Result<()> {
+    crate::config::watch::set_active_watcher(None)?;
+    Ok(())
+}
+
+/// Load a config with its `include` files (globs expanded) merged in
+/// The result lists the included files in merge order
+#[tauri::command]
+pub async fn resolve_includes(path: String) -> Result<IncludeResolution> {
+    let content = fs::read_to_string(&path)?;
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    crate::config::include::resolve_includes(config, std::path::Path::new(&path))
+}
+
+/// List files a config includes more than once (directly or via overlapping globs)
+#[tauri::command]
+pub async fn find_duplicate_includes(path: String) -> Result<Vec<DuplicateInclude>> {
+    let content = fs::read_to_string(&path)?;
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    let resolution = crate::config::include::resolve_includes(config, std::path::Path::new(&path))?;
+    Ok(resolution.duplicates)
+}
+
+/// Plan splitting a single-bar config into one include file per module
+/// Returns the files to write, relative to the config directory; writes nothing
+#[tauri::command]
+pub async fn split_config(path: String) -> Result<Vec<PlannedFile>> {
+    let content = fs::read_to_string(&path)?;
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    let main_file = std::path::Path::new(&path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "config.jsonc".to_string());
+
+    crate::config::include::split_config(&config, &main_file)
+}
+
+/// Inline all include files into one self-contained config (not written)
+/// Key conflicts between files are reported with the value that won
+#[tauri::command]
+pub async fn flatten_config(path: String) -> Result<FlattenedConfig> {
+    let content = fs::read_to_string(&path)?;
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    crate::config::include::flatten_config(config, std::path::Path::new(&path))
+}
+
+/// Check a file for a byte order mark and invalid UTF-8
+#[tauri::command]
+pub async fn check_encoding(path: String) -> Result<EncodingReport> {
+    crate::config::encoding::check_encoding(std::path::Path::new(&path))
+}
+
+/// Get the free disk space, in bytes, of the filesystem holding `path`
+#[tauri::command]
+pub async fn get_free_disk_space(path: String) -> Result<u64> {
+    crate::config::writer::free_disk_space(std::path::Path::new(&path))
+}
 
-    // Sort by timestamp (newest first)
-    backups.sort_by(|a, b| b.cmp(a));
+/// Enable or disable the free disk space check done before each write
+#[tauri::command]
+pub async fn set_disk_space_check(enabled: bool) -> Result<()> {
+    crate::config::writer::set_disk_space_check(enabled);
+    Ok(())
+}
 
-    Ok(backups)
+/// List the backups in config directory with their original file and date
+/// Newest first; `file_filter` limits the list to backups of one file (e.g. `style.css`)
+#[tauri::command]
+pub async fn list_backups(config_dir: String, file_filter: Option<String>) -> Result<Vec<BackupInfo>> {
+    crate::config::backups::list_backups(&config_dir, file_filter.as_deref())
+}
+
+/// Export the config directory as a `.tar.gz`, without backups
+/// Returns the number of files archived
+#[tauri::command]
+pub async fn export_config(config_dir: String, out_path: String) -> Result<usize> {
+    crate::config::archive::export_config(&config_dir, &out_path)
+}
+
+/// Import a `.tar.gz` made by `export_config` into the config directory
+/// Unsafe archives are refused; the current config and stylesheet are backed up
+#[tauri::command]
+pub async fn import_config(archive_path: String, config_dir: String) -> Result<usize> {
+    crate::config::archive::import_config(&archive_path, &config_dir)
 }
 
 /// Restore a backup file
+/// Corrupt config backups and empty stylesheet backups are refused
 #[tauri::command]
 pub async fn restore_backup(backup_path: String, target_path: String) -> Result<()> {
+    // Refuse a corrupt backup before touching the live file
+    crate::config::backups::validate_backup(&backup_path, &target_path)?;
+
     // Create backup of current file before restoring
     if std::path::Path::new(&target_path).exists() {
         crate::config::writer::create_backup(&target_path)?;
@@ -140,6 +359,490 @@ pub async fn restore_backup(backup_path: String, target_path: String) -> Result<
     Ok(())
 }
 
+/// Rename backups from older naming schemes to `<name>.backup.<timestamp>`
+#[tauri::command]
+pub async fn migrate_backups(config_dir: String) -> Result<Vec<RenamedBackup>> {
+    crate::config::backups::migrate_backups(&config_dir)
+}
+
+/// Save the current config and style as a named snapshot
+#[tauri::command]
+pub async fn create_snapshot(config_dir: String, name: String) -> Result<SnapshotInfo> {
+    crate::config::snapshots::create_snapshot(&config_dir, &name)
+}
+
+/// List named snapshots
+#[tauri::command]
+pub async fn list_snapshots(config_dir: String) -> Result<Vec<SnapshotInfo>> {
+    crate::config::snapshots::list_snapshots(&config_dir)
+}
+
+/// Restore a named snapshot, backing up the current files first
+#[tauri::command]
+pub async fn restore_snapshot(config_dir: String, name: String) -> Result<Vec<String>> {
+    crate::config::snapshots::restore_snapshot(&config_dir, &name)
+}
+
+/// Delete a named snapshot
+#[tauri::command]
+pub async fn delete_snapshot(config_dir: String, name: String) -> Result<()> {
+    crate::config::snapshots::delete_snapshot(&config_dir, &name)
+}
+
+/// Save the current config and style as a named theme, replacing an older one
+#[tauri::command]
+pub async fn save_theme(config_dir: String, name: String) -> Result<Vec<String>> {
+    crate::config::themes::save_theme(&config_dir, &name)
+}
+
+/// Apply a named theme, backing up the current files, then reload Waybar
+#[tauri::command]
+pub async fn apply_theme(config_dir: String, name: String) -> Result<Vec<String>> {
+    let applied = crate::config::themes::apply_theme(&config_dir, &name)?;
+    crate::waybar::reload_waybar().await?;
+    Ok(applied)
+}
+
+/// List named themes
+#[tauri::command]
+pub async fn list_themes(config_dir: String) -> Result<Vec<String>> {
+    crate::config::themes::list_themes(&config_dir)
+}
+
+/// List editor swap/temp files cluttering the config directory
+#[tauri::command]
+pub async fn list_junk_files(config_dir: String) -> Result<Vec<String>> {
+    crate::config::cleanup::list_junk_files(&config_dir)
+}
+
+/// Remove editor swap/temp files from the config directory
+/// Requires `confirm` to be true; never touches config, style or backup files
+#[tauri::command]
+pub async fn clean_junk_files(config_dir: String, confirm: bool) -> Result<Vec<String>> {
+    if !confirm {
+        return Err(AppError::Validation(
+            "Cleaning junk files requires confirmation".to_string(),
+        ));
+    }
+
+    crate::config::cleanup::clean_junk_files(&config_dir)
+}
+
+/// Copy a legacy extension-less `config` to `config.jsonc`
+/// Refuses to overwrite an existing `config.jsonc` unless `force` is set
+#[tauri::command]
+pub async fn migrate_to_jsonc(
+    config_dir: String,
+    old_file: LegacyConfigAction,
+    force: bool,
+) -> Result<String> {
+    crate::config::legacy::migrate_to_jsonc(&config_dir, old_file, force)
+}
+
+/// Check `group/*` module orientation against each bar's orientation
+#[tauri::command]
+pub async fn check_group_orientation(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_group_orientation(&config))
+}
+
+/// Check bar layer settings for risky `layer`/`exclusive`/`passthrough` combinations
+#[tauri::command]
+pub async fn check_layer_settings(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_layer_settings(&config))
+}
+
+/// Commit config.jsonc/style.css when the config directory is a git repository
+/// Returns the new commit hash
+#[tauri::command]
+pub async fn commit_config(config_dir: String, message: Option<String>) -> Result<String> {
+    crate::config::git::commit_config(&config_dir, message.as_deref())
+}
+
+/// List the last `limit` git commits touching config.jsonc/style.css
+#[tauri::command]
+pub async fn config_git_log(config_dir: String, limit: usize) -> Result<Vec<GitCommit>> {
+    crate::config::git::config_git_log(&config_dir, limit)
+}
+
+/// Restore config.jsonc/style.css from a git commit, backing up the current file
+#[tauri::command]
+pub async fn restore_from_commit(config_dir: String, commit: String, file: String) -> Result<String> {
+    crate::config::git::restore_from_commit(&config_dir, &commit, &file)
+}
+
+/// Check module format strings for unbalanced `{}` placeholders
+#[tauri::command]
+pub async fn check_format_placeholders(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_format_placeholders(&config))
+}
+
+/// Compare `height` / `spacing` / `margin` across the bars of a multi-bar config
+#[tauri::command]
+pub async fn compare_bar_dimensions(content: String) -> Result<Vec<BarFieldDifference>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::compare_bar_dimensions(&config))
+}
+
+/// Report differing `layer` values across the bars of a multi-bar config
+#[tauri::command]
+pub async fn compare_bar_layers(content: String) -> Result<Option<BarFieldDifference>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::compare_bar_layers(&config))
+}
+
+/// Check `max-length` / `min-length` values of every module
+#[tauri::command]
+pub async fn check_length_limits(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_length_limits(&config))
+}
+
+/// Check per-core `cpu` format-icons arrays against this machine's core count
+/// Uses the loaded system profile instead, if there is one (no warnings when
+/// it has no CPU section)
+#[tauri::command]
+pub async fn check_cpu_format_icons(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    let cpu = match crate::system::active_profile() {
+        Some(profile) => match profile.cpu {
+            Some(cpu) => cpu,
+            None => return Ok(Vec::new()),
+        },
+        None => crate::system::get_cpu_info().await?,
+    };
+    Ok(crate::config::lint::check_cpu_format_icons(&config, cpu.logical_cores))
+}
+
+/// Check workspace `format-icons` maps against the compositor's live workspaces
+/// Returns no warnings when the compositor can't be queried
+#[tauri::command]
+pub async fn check_workspace_icons(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+
+    let (module_type, workspaces): (&str, Vec<String>) = match crate::system::detect_compositor().await?.as_str() {
+        "hyprland" => match crate::system::get_hyprland_workspaces().await {
+            Ok(workspaces) => ("hyprland/workspaces", workspaces.into_iter().map(|w| w.name).collect()),
+            Err(_) => return Ok(Vec::new()),
+        },
+        "sway" => match crate::system::get_sway_workspaces().await {
+            Ok(workspaces) => ("sway/workspaces", workspaces.into_iter().map(|w| w.name).collect()),
+            Err(_) => return Ok(Vec::new()),
+        },
+        _ => return Ok(Vec::new()),
+    };
+
+    Ok(crate::config::lint::check_workspace_icons(&config, module_type, &workspaces))
+}
+
+/// Group modules sharing the same non-trivial `on-click` command
+#[tauri::command]
+pub async fn find_duplicate_click_actions(content: String) -> Result<Vec<DuplicateClickAction>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::find_duplicate_click_actions(&config))
+}
+
+/// Check `drawer` settings sit on groups and groups list their children
+#[tauri::command]
+pub async fn check_group_drawers(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_group_drawers(&config))
+}
+
+/// Check Waybar's self-reload options against the app's SIGUSR2 reload
+#[tauri::command]
+pub async fn check_reload_options(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_reload_options(&config))
+}
+
+/// Check `network` modules' interfaces (globs allowed) against this machine's interfaces
+/// Uses the loaded system profile instead, if there is one
+#[tauri::command]
+pub async fn check_network_interfaces(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    let interfaces = match crate::system::active_profile() {
+        Some(profile) => profile.interfaces,
+        None => crate::system::list_network_interfaces().await?,
+    };
+    if interfaces.is_empty() {
+        // Interfaces couldn't be listed; nothing to compare against
+        return Ok(Vec::new());
+    }
+    Ok(crate::config::lint::check_network_interfaces(&config, &interfaces))
+}
+
+/// Compare the players named by `mpris` modules with the players active on D-Bus
+#[tauri::command]
+pub async fn check_mpris_players(content: String) -> Result<MprisPlayerReport> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    let players = crate::system::list_mpris_players().await?;
+    Ok(crate::config::lint::check_mpris_players(&config, &players))
+}
+
+/// Flag modules with a scroll action in only one direction
+#[tauri::command]
+pub async fn check_scroll_actions(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_scroll_actions(&config))
+}
+
+/// Check `tooltip-format` placeholders against the fields each module provides
+#[tauri::command]
+pub async fn check_tooltip_fields(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_tooltip_fields(&config))
+}
+
+/// Flag `clock` modules updating every second without displaying seconds
+#[tauri::command]
+pub async fn check_clock_intervals(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_clock_intervals(&config))
+}
+
+/// Flag bar options the running compositor doesn't support (e.g. sway-only bar IPC)
+/// Uses the loaded system profile instead, if there is one (no warnings when
+/// it has no compositor section)
+#[tauri::command]
+pub async fn check_compositor_features(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    let compositor = match crate::system::active_profile() {
+        Some(profile) => match profile.compositor {
+            Some(compositor) => compositor.name,
+            None => return Ok(Vec::new()),
+        },
+        None => crate::system::detect_compositor().await?,
+    };
+    Ok(crate::config::lint::check_compositor_features(&config, &compositor))
+}
+
+/// Check every `format-icons` is a string, a non-empty string array or a map of them
+#[tauri::command]
+pub async fn check_format_icon_types(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_format_icon_types(&config))
+}
+
+/// Flag modules whose `format-alt` is identical to `format`
+#[tauri::command]
+pub async fn check_format_alt(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_format_alt(&config))
+}
+
+/// Check module options against the known keys of each module type
+#[tauri::command]
+pub async fn check_unknown_keys(content: String) -> Result<Vec<LintWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::lint::check_unknown_keys(&config))
+}
+
+/// List the modules placed on any bar of the config
+#[tauri::command]
+pub async fn list_config_modules(content: String) -> Result<Vec<String>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::list_config_modules(&config))
+}
+
+/// List the catalog modules available in a Waybar version
+/// Uses the installed Waybar's version when none is given; lists every module
+/// when it can't be detected
+#[tauri::command]
+pub async fn modules_available_in_version(version: Option<String>) -> Result<Vec<ModuleSpec>> {
+    let version = match version {
+        Some(version) => Some(version),
+        None => crate::waybar::get_waybar_version().await.ok(),
+    };
+
+    Ok(match version {
+        Some(version) => crate::config::catalog::modules_available_in_version(&version)
+            .into_iter()
+            .cloned()
+            .collect(),
+        None => crate::config::catalog::MODULE_CATALOG.to_vec(),
+    })
+}
+
+/// Inline every local `@import` of a stylesheet into one CSS document
+#[tauri::command]
+pub async fn flatten_css(path: String) -> Result<String> {
+    crate::config::css::flatten_css(std::path::Path::new(&path))
+}
+
+/// Find CSS rules targeting modules that aren't in the config
+#[tauri::command]
+pub async fn find_unused_css_rules(css: String, content: String) -> Result<Vec<CssWarning>> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::css::find_unused_css_rules(&css, &config))
+}
+
+/// Find `@color` references in a stylesheet that don't resolve to a definition
+#[tauri::command]
+pub async fn find_undefined_colors(css: String) -> Result<Vec<CssWarning>> {
+    Ok(crate::config::css::find_undefined_colors(&css))
+}
+
+/// List the colors defined with `@define-color` in a stylesheet
+#[tauri::command]
+pub async fn extract_color_definitions(css: String) -> Result<Vec<ColorDef>> {
+    Ok(crate::config::css::extract_color_definitions(&css))
+}
+
+/// Change the value of one `@define-color` definition, returning the new stylesheet
+#[tauri::command]
+pub async fn set_color_definition(css: String, name: String, value: String) -> Result<String> {
+    crate::config::css::set_color_definition(&css, &name, &value)
+}
+
+/// List module CSS rules overriding the bar-wide font
+#[tauri::command]
+pub async fn find_font_overrides(css: String) -> Result<Vec<CssWarning>> {
+    Ok(crate::config::css::find_font_overrides(&css))
+}
+
+/// Validate hex color lengths in a stylesheet, optionally expanding shorthands
+#[tauri::command]
+pub async fn normalize_hex_colors(css: String, expand: bool) -> Result<NormalizedColors> {
+    Ok(crate::config::css::normalize_hex_colors(&css, expand))
+}
+
+/// Enable or disable tooltips on every module that supports them
+#[tauri::command]
+pub async fn set_all_tooltips(content: String, enabled: bool) -> Result<ConfigEdit> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::edit::set_all_tooltips(config, enabled))
+}
+
+/// Replace the user's home directory with `~` in every string value for sharing
+#[tauri::command]
+pub async fn export_portable(content: String) -> Result<ConfigEdit> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::edit::export_portable(config, &home_dir()?))
+}
+
+/// Expand `~` paths in every string value to the current user's home directory
+#[tauri::command]
+pub async fn import_portable(content: String) -> Result<ConfigEdit> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::edit::import_portable(config, &home_dir()?))
+}
+
+/// Move a custom module script and point every command referencing it at the new path
+///
+/// Returns the updated config without saving it. Refuses to move the script
+/// onto an existing file; a missing script only rewrites the references.
+#[tauri::command]
+pub async fn relink_script(
+    content: String,
+    old_path: String,
+    new_path: String,
+) -> Result<ScriptRelink> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    let mut relink = crate::config::edit::relink_script(config, &old_path, &new_path);
+    relink.moved = crate::config::edit::move_script(&old_path, &new_path, &home_dir()?)?;
+    Ok(relink)
+}
+
+/// Get the current user's home directory from `$HOME`
+fn home_dir() -> Result<String> {
+    std::env::var("HOME")
+        .map_err(|_| AppError::Config("HOME environment variable not set".to_string()))
+}
+
+/// Check if an edited config differs functionally from the last-applied one
+/// Comment-only and formatting-only edits don't need a Waybar reload
+#[tauri::command]
+pub async fn reload_needed(content: String, last_applied: String) -> Result<bool> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+
+    // An unparseable previous config can't have been applied as-is
+    match crate::config::parser::parse_jsonc(&last_applied) {
+        Ok(applied) => Ok(config != applied),
+        Err(_) => Ok(true),
+    }
+}
+
+/// Hash a config's content ignoring comments, formatting and key order
+#[tauri::command]
+pub async fn config_fingerprint(content: String) -> Result<String> {
+    crate::config::parser::config_fingerprint(&content)
+}
+
+/// Time parsing a config (with include resolution) over several iterations
+#[tauri::command]
+pub async fn benchmark_parse(path: String, iterations: u32) -> Result<ParseBenchmark> {
+    crate::config::parser::benchmark_parse(std::path::Path::new(&path), iterations)
+}
+
+/// Fix the indentation of JSONC content, keeping comments and key order
+#[tauri::command]
+pub async fn reindent(content: String, indent_width: Option<usize>) -> Result<String> {
+    crate::config::format::reindent(
+        &content,
+        indent_width.unwrap_or(crate::config::format::DEFAULT_INDENT_WIDTH),
+    )
+}
+
+/// List the lines of JSONC content that end in spaces or tabs
+#[tauri::command]
+pub async fn find_trailing_whitespace(content: String) -> Result<Vec<usize>> {
+    Ok(crate::config::format::find_trailing_whitespace(&content))
+}
+
+/// Remove trailing spaces and tabs from each line, leaving string values intact
+#[tauri::command]
+pub async fn trim_trailing_whitespace(content: String) -> Result<String> {
+    Ok(crate::config::format::trim_trailing_whitespace(&content))
+}
+
+/// Rewrite compositor-specific modules (e.g. `sway/*` → `hyprland/*`) in a config file
+/// Creates automatic backup before writing, keeping comments and line endings
+/// the way `save_config` does
+#[tauri::command]
+pub async fn migrate_modules(path: String, from: String, to: String) -> Result<Vec<ModuleRename>> {
+    let content = fs::read_to_string(&path)?;
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    let migration = crate::config::migrate::migrate_modules(&config, &from, &to)?;
+
+    if !migration.changes.is_empty() {
+        let formatted = crate::config::writer::format_json(&migration.config)?;
+        let output = crate::config::writer::config_output(&formatted, Some(&content));
+        crate::config::writer::write_config_file(&path, &output)?;
+        crate::config::writer::rotate_backups(&path, crate::config::writer::DEFAULT_MAX_BACKUPS);
+    }
+
+    Ok(migration.changes)
+}
+
+/// Preview a compositor module migration without writing anything
+#[tauri::command]
+pub async fn preview_migration(content: String, from: String, to: String) -> Result<Migration> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    crate::config::migrate::migrate_modules(&config, &from, &to)
+}
+
+/// Find single-quoted string literals, which JSON rejects
+#[tauri::command]
+pub async fn find_single_quoted_strings(content: String) -> Result<Vec<SingleQuotedString>> {
+    Ok(crate::config::quotes::find_single_quoted_strings(&content))
+}
+
+/// Convert unambiguous single-quoted string literals to double-quoted ones
+#[tauri::command]
+pub async fn fix_quotes(content: String) -> Result<String> {
+    Ok(crate::config::quotes::fix_quotes(&content))
+}
+
+/// Render a textual approximation of each bar's module layout
+#[tauri::command]
+pub async fn render_ascii_preview(content: String) -> Result<String> {
+    let config = crate::config::parser::parse_jsonc(&content)?;
+    Ok(crate::config::preview::render_ascii_preview(&config))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,6 +856,41 @@ mod tests {
         assert!(result.is_ok() || result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_merge_config_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = temp_dir.path().join("base.jsonc");
+        let clock = temp_dir.path().join("clock.jsonc");
+        fs::write(&base, "// bar\n{ \"modules-right\": [\"clock\"], \"height\": 30 }").unwrap();
+        fs::write(&clock, "{ \"modules-right\": [\"battery\"], \"clock\": {} }").unwrap();
+        let paths = vec![base.to_str().unwrap().to_string(), clock.to_str().unwrap().to_string()];
+
+        let merged = merge_config_files(paths.clone(), None).await.unwrap();
+        assert_eq!(
+            merged,
+            serde_json::json!({ "modules-right": ["battery"], "height": 30, "clock": {} })
+        );
+        let merged = merge_config_files(paths, Some(ArrayStrategy::Concat)).await.unwrap();
+        assert_eq!(merged["modules-right"], serde_json::json!(["clock", "battery"]));
+
+        assert!(matches!(merge_config_files(vec![], None).await, Err(AppError::Validation(_))));
+    }
+
+    #[tokio::test]
+    async fn test_detect_config_paths_in_custom_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_str().unwrap().to_string();
+        fs::write(temp_dir.path().join("config"), "{}").unwrap();
+
+        let paths = detect_config_paths_in(Some(dir.clone())).await.unwrap();
+        assert_eq!(paths.config_dir, dir);
+        assert_eq!(paths.config_file, format!("{}/config", dir));
+        assert_eq!(paths.style_file, format!("{}/style.css", dir));
+
+        let missing = temp_dir.path().join("profiles/work").to_str().unwrap().to_string();
+        assert!(matches!(detect_config_paths_in(Some(missing)).await, Err(AppError::NotFound(_))));
+    }
+
     #[tokio::test]
     async fn test_load_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -171,13 +909,42 @@ mod tests {
         assert!(config_file.content.contains("// Comment"));
     }
 
+    #[tokio::test]
+    async fn test_load_config_error_points_at_file_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.jsonc");
+        fs::write(&config_path, "/* one\n   two */ {\n  \"height\": 30,\n  \"layer\": top\n}").unwrap();
+
+        match load_config(config_path.to_str().unwrap().to_string()).await {
+            Err(AppError::Parse(message)) => assert!(message.contains("line 4"), "{}", message),
+            other => panic!("Expected a parse error, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_modules_keeps_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.jsonc");
+        let content = "{\r\n    // Bar height\r\n    \"height\": 30,\r\n    \"modules-left\": [\"sway/workspaces\"]\r\n}\r\n";
+        fs::write(&config_path, content).unwrap();
+
+        let path = config_path.to_str().unwrap().to_string();
+        let changes = migrate_modules(path, "sway".to_string(), "hyprland".to_string()).await.unwrap();
+        assert!(!changes.is_empty());
+
+        let migrated = fs::read_to_string(&config_path).unwrap();
+        assert!(migrated.contains("// Bar height\r\n"));
+        assert!(migrated.contains("hyprland/workspaces"));
+        assert!(!migrated.replace("\r\n", "").contains('\n'));
+    }
+
     #[tokio::test]
     async fn test_save_config() {
         let temp_dir = TempDir::new().unwrap();
         let config_path = temp_dir.path().join("config.json");
 
         let content = r#"{"modules-left": ["cpu"]}"#;
-        let result = save_config(config_path.to_str().unwrap().to_string(), content.to_string()).await;
+        let result = save_config(config_path.to_str().unwrap().to_string(), content.to_string(), None).await;
         assert!(result.is_ok());
 
         let saved_content = fs::read_to_string(&config_path).unwrap();
@@ -191,10 +958,64 @@ mod tests {
         let css_path = temp_dir.path().join("style.css");
 
         let content = "* { margin: 0; }";
-        let result = save_css(css_path.to_str().unwrap().to_string(), content.to_string()).await;
+        let result = save_css(css_path.to_str().unwrap().to_string(), content.to_string(), None).await;
         assert!(result.is_ok());
 
         let saved_content = fs::read_to_string(&css_path).unwrap();
         assert_eq!(saved_content, content);
     }
+
+    #[tokio::test]
+    async fn test_save_rejects_zero_max_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.jsonc");
+        let css_path = temp_dir.path().join("style.css");
+        fs::write(&config_path, "{}").unwrap();
+        fs::write(&css_path, "* {}").unwrap();
+
+        let config = config_path.to_str().unwrap().to_string();
+        let result = save_config(config, r#"{"height": 30}"#.to_string(), Some(0)).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        let css = css_path.to_str().unwrap().to_string();
+        let result = save_css(css, "* { margin: 0; }".to_string(), Some(0)).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+
+        // Nothing was written or backed up
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), "{}");
+        assert_eq!(fs::read_to_string(&css_path).unwrap(), "* {}");
+        assert_eq!(fs::read_dir(temp_dir.path()).unwrap().count(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_clean_junk_files_requires_confirmation() {
+        let temp_dir = TempDir::new().unwrap();
+        let swap_path = temp_dir.path().join(".config.jsonc.swp");
+        fs::write(&swap_path, "swap").unwrap();
+        let dir = temp_dir.path().to_str().unwrap().to_string();
+
+        let result = clean_junk_files(dir.clone(), false).await;
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert!(swap_path.exists());
+
+        let removed = clean_junk_files(dir, true).await.unwrap();
+        assert_eq!(removed, vec![".config.jsonc.swp"]);
+        assert!(!swap_path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_reload_needed_ignores_comments_and_formatting() {
+        let applied = "{\n  // Clock\n  \"modules-left\": [\"clock\"]\n}".to_string();
+        let edited = "{ /* The clock */ \"modules-left\": [ \"clock\" ] }".to_string();
+
+        assert!(!reload_needed(edited, applied).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_reload_needed_on_value_change() {
+        let applied = r#"{ "height": 30 }"#.to_string();
+        let edited = r#"{ "height": 32 }"#.to_string();
+
+        assert!(reload_needed(edited.clone(), applied).await.unwrap());
+        assert!(reload_needed(edited, "{ broken".to_string()).await.unwrap());
+    }
 }
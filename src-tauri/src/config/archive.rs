@@ -0,0 +1,369 @@
+// ============================================================================
+// CONFIG ARCHIVES
+// ============================================================================
+//
+// Bundles the whole config directory (config, stylesheet, included files,
+// scripts) into a `.tar.gz` that can be shared or committed elsewhere.
+// Entry names are relative to the config directory, so an exported archive
+// imports straight back into any config directory.
+
+use crate::error::{AppError, Result};
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::{Component, Path, PathBuf};
+
+/// Marker in the name of files created by `writer::create_backup`
+const BACKUP_MARKER: &str = ".backup.";
+
+/// Files backed up before an import overwrites them
+const IMPORT_BACKED_UP_FILES: [&str; 2] = ["config.jsonc", "style.css"];
+
+// ============================================================================
+// EXPORT
+// ============================================================================
+
+/// Collect the files under `dir` worth archiving, sorted for a stable archive
+///
+/// Backups and the archive being written (`skip`) are left out. Symlinks are
+/// skipped rather than followed: a link may point outside the config
+/// directory or back up the tree, and import only accepts plain files.
+fn collect_files(dir: &Path, skip: &Path, files: &mut Vec<PathBuf>) -> Result<()> {
+    let mut entries = fs::read_dir(dir)?.collect::<std::io::Result<Vec<_>>>()?;
+    entries.sort_by_key(|entry| entry.file_name());
+
+    for entry in entries {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().contains(BACKUP_MARKER) || path == skip {
+            continue;
+        }
+
+        let file_type = entry.file_type()?;
+        if file_type.is_symlink() {
+            log::warn!("Not archiving symlink {}", path.display());
+        } else if file_type.is_dir() {
+            collect_files(&path, skip, files)?;
+        } else if file_type.is_file() {
+            files.push(path);
+        }
+    }
+
+    Ok(())
+}
+
+/// Write `files` (under `dir`) as a `.tar.gz` to `output`
+fn build_archive(output: File, dir: &Path, files: &[PathBuf]) -> Result<()> {
+    let encoder = GzEncoder::new(output, Compression::default());
+    let mut archive = tar::Builder::new(encoder);
+    for file in files {
+        let name = file.strip_prefix(dir).map_err(|e| AppError::Internal(e.to_string()))?;
+        archive.append_path_with_name(file, name)?;
+    }
+    archive.into_inner()?.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// Write the config directory to a `.tar.gz` at `out_path`
+///
+/// Subdirectories are included, backups and symlinks are not. The archive is
+/// written to a temporary file renamed over `out_path` once complete, so a
+/// failed export never leaves a truncated archive. Returns the number of
+/// files archived.
+pub fn export_config(config_dir: &str, out_path: &str) -> Result<usize> {
+    let dir = Path::new(config_dir);
+    if !dir.is_dir() {
+        return Err(AppError::NotFound(format!("Config directory not found: {}", config_dir)));
+    }
+
+    let out = Path::new(out_path);
+    let out_name = out
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| AppError::Validation(format!("Invalid archive path: {}", out_path)))?;
+    let out_dir = match out.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => fs::canonicalize(parent)?,
+        _ => std::env::current_dir()?,
+    };
+    // An archive written inside the config directory must not include itself
+    let skip = out_dir.join(out_name);
+
+    let dir = fs::canonicalize(dir)?;
+    let mut files = Vec::new();
+    collect_files(&dir, &skip, &mut files)?;
+
+    let temp_path = out_dir.join(format!(".{}.{}.tmp", out_name, std::process::id()));
+    let written = File::create(&temp_path)
+        .map_err(AppError::from)
+        .and_then(|output| build_archive(output, &dir, &files))
+        .and_then(|()| Ok(fs::rename(&temp_path, &skip)?));
+    if let Err(e) = written {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e);
+    }
+
+    log::info!("Exported {} files from {} to {}", files.len(), config_dir, out_path);
+    Ok(files.len())
+}
+
+// ============================================================================
+// IMPORT
+// ============================================================================
+
+/// Open a `.tar.gz` for reading
+fn open_archive(archive_path: &str) -> Result<tar::Archive<GzDecoder<File>>> {
+    let file = File::open(archive_path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => {
+            AppError::NotFound(format!("Archive not found: {}", archive_path))
+        }
+        _ => AppError::from(e),
+    })?;
+    Ok(tar::Archive::new(GzDecoder::new(file)))
+}
+
+/// Whether an entry path stays inside the directory it is extracted into
+fn is_safe_entry_path(path: &Path) -> bool {
+    path.components().all(|c| matches!(c, Component::Normal(_) | Component::CurDir))
+}
+
+/// Check every entry before anything is written
+///
+/// Only plain files and directories with relative paths that stay inside the
+/// config directory are accepted. An archived `config.jsonc` must parse.
+/// Returns the number of files in the archive.
+fn validate_archive(archive_path: &str) -> Result<usize> {
+    let mut archive = open_archive(archive_path)?;
+    let mut files = 0;
+
+    for entry in archive.entries()? {
+        let mut entry = entry?;
+        let path = entry.path()?.into_owned();
+
+        if !is_safe_entry_path(&path) {
+            return Err(AppError::Validation(format!(
+                "Archive entry escapes the config directory: {}",
+                path.display()
+            )));
+        }
+
+        match entry.header().entry_type() {
+            tar::EntryType::Directory => {}
+            tar::EntryType::Regular => {
+                files += 1;
+                if path == Path::new("config.jsonc") {
+                    let mut content = String::new();
+                    entry.read_to_string(&mut content)?;
+                    crate::config::parser::parse_jsonc(&content).map_err(|e| {
+                        AppError::Validation(format!("Archived config.jsonc is invalid: {}", e))
+                    })?;
+                }
+            }
+            other => {
+                return Err(AppError::Validation(format!(
+                    "Archive entry is not a regular file: {} ({:?})",
+                    path.display(),
+                    other
+                )));
+            }
+        }
+    }
+
+    Ok(files)
+}
+
+/// Extract a `.tar.gz` made by [`export_config`] into `config_dir`
+///
+/// The whole archive is checked first; an unsafe entry or an invalid
+/// `config.jsonc` fails with `Validation` without touching the directory.
+/// The current `config.jsonc` and `style.css` are backed up before they are
+/// replaced. Returns the number of files extracted.
+pub fn import_config(archive_path: &str, config_dir: &str) -> Result<usize> {
+    let files = validate_archive(archive_path)?;
+
+    let dir = Path::new(config_dir);
+    fs::create_dir_all(dir)?;
+    for name in IMPORT_BACKED_UP_FILES {
+        let existing = dir.join(name);
+        if existing.is_file() {
+            crate::config::writer::create_backup(&existing.to_string_lossy())?;
+        }
+    }
+
+    let mut archive = open_archive(archive_path)?;
+    for entry in archive.entries()? {
+        entry?.unpack_in(dir)?;
+    }
+
+    log::info!("Imported {} files from {} into {}", files, archive_path, config_dir);
+    Ok(files)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn entry_names(archive: &Path) -> Vec<String> {
+        let mut archive = tar::Archive::new(GzDecoder::new(File::open(archive).unwrap()));
+        archive
+            .entries()
+            .unwrap()
+            .map(|entry| entry.unwrap().path().unwrap().to_string_lossy().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_export_config_skips_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("waybar");
+        fs::create_dir_all(config_dir.join("modules")).unwrap();
+        fs::write(config_dir.join("config.jsonc"), "{}").unwrap();
+        fs::write(config_dir.join("style.css"), "* {}").unwrap();
+        fs::write(config_dir.join("config.jsonc.backup.1700000000"), "{}").unwrap();
+        fs::write(config_dir.join("modules/clock.jsonc"), "{}").unwrap();
+
+        let out = temp_dir.path().join("waybar.tar.gz");
+        let count = export_config(config_dir.to_str().unwrap(), out.to_str().unwrap()).unwrap();
+
+        assert_eq!(count, 3);
+        assert_eq!(entry_names(&out), vec!["config.jsonc", "modules/clock.jsonc", "style.css"]);
+    }
+
+    #[test]
+    fn test_export_config_into_config_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("config.jsonc"), "{}").unwrap();
+
+        let out = temp_dir.path().join("export.tar.gz");
+        let count = export_config(temp_dir.path().to_str().unwrap(), out.to_str().unwrap()).unwrap();
+
+        assert_eq!(count, 1);
+        assert_eq!(entry_names(&out), vec!["config.jsonc"]);
+    }
+
+    #[test]
+    fn test_export_skips_symlinks() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_dir = temp_dir.path().join("waybar");
+        fs::create_dir_all(config_dir.join("scripts")).unwrap();
+        fs::write(config_dir.join("config.jsonc"), "{}").unwrap();
+        fs::write(temp_dir.path().join("secret"), "outside").unwrap();
+        std::os::unix::fs::symlink(temp_dir.path().join("secret"), config_dir.join("secret")).unwrap();
+        std::os::unix::fs::symlink(&config_dir, config_dir.join("scripts/loop")).unwrap();
+
+        let out = temp_dir.path().join("waybar.tar.gz");
+        let count = export_config(config_dir.to_str().unwrap(), out.to_str().unwrap()).unwrap();
+
+        assert_eq!(count, 1);
+        assert_eq!(entry_names(&out), vec!["config.jsonc"]);
+        // Only the finished archive is left behind
+        let left: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.ends_with(".tmp"))
+            .collect();
+        assert!(left.is_empty());
+    }
+
+    #[test]
+    fn test_export_missing_config_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let missing = temp_dir.path().join("missing");
+        let out = temp_dir.path().join("out.tar.gz");
+
+        let result = export_config(missing.to_str().unwrap(), out.to_str().unwrap());
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+        assert!(!out.exists());
+    }
+
+    /// Build a `.tar.gz` with raw entry names, bypassing the builder's own checks
+    fn write_archive(path: &Path, entries: &[(&str, &str)]) {
+        let encoder = GzEncoder::new(File::create(path).unwrap(), Compression::default());
+        let mut builder = tar::Builder::new(encoder);
+        for (name, content) in entries {
+            let mut header = tar::Header::new_gnu();
+            let raw = &mut header.as_old_mut().name;
+            raw[..name.len()].copy_from_slice(name.as_bytes());
+            header.set_size(content.len() as u64);
+            header.set_mode(0o644);
+            header.set_entry_type(tar::EntryType::Regular);
+            header.set_cksum();
+            builder.append(&header, content.as_bytes()).unwrap();
+        }
+        builder.into_inner().unwrap().finish().unwrap();
+    }
+
+    #[test]
+    fn test_export_import_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("source");
+        fs::create_dir_all(source.join("modules")).unwrap();
+        fs::write(source.join("config.jsonc"), "// new\n{\"height\": 30}").unwrap();
+        fs::write(source.join("modules/clock.jsonc"), "{}").unwrap();
+        let out = temp_dir.path().join("waybar.tar.gz");
+        export_config(source.to_str().unwrap(), out.to_str().unwrap()).unwrap();
+
+        let target = temp_dir.path().join("target");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("config.jsonc"), "{\"height\": 20}").unwrap();
+
+        let count = import_config(out.to_str().unwrap(), target.to_str().unwrap()).unwrap();
+        assert_eq!(count, 2);
+        assert!(fs::read_to_string(target.join("config.jsonc")).unwrap().contains("30"));
+        assert!(target.join("modules/clock.jsonc").is_file());
+
+        let backups: Vec<_> = fs::read_dir(&target)
+            .unwrap()
+            .map(|e| e.unwrap().file_name().to_string_lossy().to_string())
+            .filter(|name| name.starts_with("config.jsonc.backup."))
+            .collect();
+        assert_eq!(backups.len(), 1);
+    }
+
+    #[test]
+    fn test_import_rejects_unsafe_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let target = temp_dir.path().join("waybar");
+        fs::create_dir_all(&target).unwrap();
+        fs::write(target.join("config.jsonc"), "{}").unwrap();
+
+        for name in ["../escape.sh", "/etc/waybar.css", "modules/../../escape.sh"] {
+            let archive = temp_dir.path().join("bad.tar.gz");
+            write_archive(&archive, &[("style.css", "* {}"), (name, "x")]);
+
+            let result = import_config(archive.to_str().unwrap(), target.to_str().unwrap());
+            assert!(matches!(result, Err(AppError::Validation(_))), "{}", name);
+        }
+
+        // Nothing was backed up or written
+        assert_eq!(fs::read_dir(&target).unwrap().count(), 1);
+        assert!(!temp_dir.path().join("escape.sh").exists());
+    }
+
+    #[test]
+    fn test_import_rejects_invalid_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let archive = temp_dir.path().join("broken.tar.gz");
+        write_archive(&archive, &[("config.jsonc", "{\"height\": }")]);
+
+        let target = temp_dir.path().join("waybar");
+        let result = import_config(archive.to_str().unwrap(), target.to_str().unwrap());
+        assert!(matches!(result, Err(AppError::Validation(_))));
+        assert!(!target.exists());
+    }
+
+    #[test]
+    fn test_import_missing_archive() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = import_config(
+            temp_dir.path().join("missing.tar.gz").to_str().unwrap(),
+            temp_dir.path().to_str().unwrap(),
+        );
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
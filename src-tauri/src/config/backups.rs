@@ -0,0 +1,373 @@
+// ============================================================================
+// BACKUP MAINTENANCE
+// ============================================================================
+//
+// Housekeeping for the `<name>.backup.<timestamp>` files created by
+// `writer::create_backup`.
+
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Files the app backs up, longest first so `config.jsonc` wins over `config`
+const BACKED_UP_FILES: [&str; 3] = ["config.jsonc", "style.css", "config"];
+
+/// Suffix words older versions used to mark backups
+const LEGACY_MARKERS: [&str; 4] = ["bak", "backup", "old", "orig"];
+
+/// Minimum digits for a number in a legacy name to count as a Unix timestamp
+const MIN_TIMESTAMP_DIGITS: usize = 9;
+
+/// A backup renamed into the current naming scheme
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RenamedBackup {
+    /// Old file name
+    pub from: String,
+    /// New file name
+    pub to: String,
+}
+
+/// A backup in the current `<name>.backup.<timestamp>` format
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BackupInfo {
+    /// Backup file name
+    pub filename: String,
+    /// Name of the file it backs up (e.g. `config.jsonc`)
+    pub original_file: String,
+    /// Unix timestamp from the name
+    pub timestamp: u64,
+    /// The timestamp as an RFC 3339 UTC date (`2024-04-05T19:34:38Z`)
+    pub date: String,
+    /// File size in bytes
+    pub size_bytes: u64,
+}
+
+/// Split a current-format backup name into the original name and timestamp
+fn parse_backup_name(file_name: &str) -> Option<(&str, u64)> {
+    let (original, timestamp) = file_name.rsplit_once(".backup.")?;
+    if original.is_empty() || !timestamp.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    Some((original, timestamp.parse().ok()?))
+}
+
+/// Format a Unix timestamp as an RFC 3339 UTC date
+fn rfc3339(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let secs = timestamp % 86_400;
+
+    // Civil date from days since the epoch (Howard Hinnant's algorithm)
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        secs / 3_600,
+        secs % 3_600 / 60,
+        secs % 60
+    )
+}
+
+/// List the backups in `config_dir`, newest first
+///
+/// Ordered by the parsed timestamp, so a 9-digit timestamp sorts before a
+/// 10-digit one. Legacy-named backups are not listed; see [`migrate_backups`].
+/// With a `file_filter`, only backups of that file (e.g. `style.css`) are
+/// listed, matched exactly against the name before `.backup.`.
+pub fn list_backups(config_dir: &str, file_filter: Option<&str>) -> Result<Vec<BackupInfo>> {
+    let mut backups = Vec::new();
+
+    for entry in fs::read_dir(config_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let Some(file_name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let Some((original, timestamp)) = parse_backup_name(&file_name) else {
+            continue;
+        };
+        if file_filter.is_some_and(|filter| filter != original) {
+            continue;
+        }
+
+        backups.push(BackupInfo {
+            original_file: original.to_string(),
+            timestamp,
+            date: rfc3339(timestamp),
+            size_bytes: entry.metadata()?.len(),
+            filename: file_name,
+        });
+    }
+
+    backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp).then_with(|| a.filename.cmp(&b.filename)));
+    Ok(backups)
+}
+
+/// Check that a backup is safe to restore over `target_path`
+///
+/// Backups restored to a config (`*.jsonc`, `*.json` or the extensionless
+/// `config`) must parse as JSONC; stylesheet backups must not be empty.
+/// Nothing is written.
+pub fn validate_backup(backup_path: &str, target_path: &str) -> Result<()> {
+    let content = fs::read_to_string(backup_path)?;
+    let target = Path::new(target_path);
+    let extension = target.extension().and_then(|e| e.to_str());
+    let is_config = matches!(extension, Some("jsonc" | "json"))
+        || (extension.is_none() && target.file_name().is_some_and(|name| name == "config"));
+
+    if is_config {
+        crate::config::parser::parse_jsonc(&content).map_err(|e| {
+            AppError::Validation(format!("Backup {} is not valid JSONC: {}", backup_path, e))
+        })?;
+    } else if content.trim().is_empty() {
+        return Err(AppError::Validation(format!("Backup {} is empty", backup_path)));
+    }
+
+    Ok(())
+}
+
+/// Check if a suffix is the current `.backup.<timestamp>` form
+fn is_current_suffix(suffix: &str) -> bool {
+    suffix
+        .strip_prefix(".backup.")
+        .is_some_and(|ts| !ts.is_empty() && ts.bytes().all(|b| b.is_ascii_digit()))
+}
+
+/// Recognize a legacy backup name (`config.jsonc.bak`, `style.css.old.1700000000`,
+/// `config-backup-2`, ...)
+///
+/// Returns the original file name and the timestamp embedded in the name, if any.
+fn parse_legacy_backup(file_name: &str) -> Option<(&'static str, Option<u64>)> {
+    let (base, suffix) = BACKED_UP_FILES.iter().find_map(|base| {
+        file_name
+            .strip_prefix(base)
+            .filter(|suffix| suffix.starts_with(['.', '-', '_']))
+            .map(|suffix| (*base, suffix))
+    })?;
+
+    if is_current_suffix(suffix) {
+        return None;
+    }
+
+    let tokens: Vec<&str> = suffix.split(['.', '-', '_']).filter(|t| !t.is_empty()).collect();
+    let has_marker = tokens
+        .iter()
+        .any(|t| LEGACY_MARKERS.contains(&t.to_ascii_lowercase().as_str()));
+    let rest_numeric = tokens.iter().all(|t| {
+        LEGACY_MARKERS.contains(&t.to_ascii_lowercase().as_str()) || t.bytes().all(|b| b.is_ascii_digit())
+    });
+
+    if !has_marker || !rest_numeric {
+        return None;
+    }
+
+    let timestamp = tokens
+        .iter()
+        .find(|t| t.len() >= MIN_TIMESTAMP_DIGITS && t.bytes().all(|b| b.is_ascii_digit()))
+        .and_then(|t| t.parse().ok());
+
+    Some((base, timestamp))
+}
+
+/// Get a file's modification time as a Unix timestamp
+fn mtime_secs(path: &Path) -> Result<u64> {
+    let modified = fs::metadata(path)?.modified()?;
+    modified
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .map_err(|e| AppError::Internal(format!("Invalid modification time: {}", e)))
+}
+
+/// Rename backups from older naming schemes to `<name>.backup.<timestamp>`
+///
+/// The timestamp comes from the old name when it has one, otherwise from the
+/// file's modification time. Files already in the current format are skipped
+/// and existing backups are never overwritten. Returns the renames, sorted by
+/// old name.
+pub fn migrate_backups(config_dir: &str) -> Result<Vec<RenamedBackup>> {
+    let dir = Path::new(config_dir);
+    let mut file_names = Vec::new();
+
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+
+        let file_name = entry
+            .file_name()
+            .to_str()
+            .ok_or_else(|| AppError::Internal("Invalid UTF-8 in filename".to_string()))?
+            .to_string();
+        file_names.push(file_name);
+    }
+    file_names.sort();
+
+    let mut renamed = Vec::new();
+    for file_name in file_names {
+        let Some((base, timestamp)) = parse_legacy_backup(&file_name) else {
+            continue;
+        };
+
+        let path = dir.join(&file_name);
+        let mut timestamp = match timestamp {
+            Some(timestamp) => timestamp,
+            None => mtime_secs(&path)?,
+        };
+
+        let mut new_name = format!("{}.backup.{}", base, timestamp);
+        while dir.join(&new_name).exists() {
+            timestamp += 1;
+            new_name = format!("{}.backup.{}", base, timestamp);
+        }
+
+        fs::rename(&path, dir.join(&new_name))?;
+        log::info!("Renamed backup {} to {}", file_name, new_name);
+        renamed.push(RenamedBackup { from: file_name, to: new_name });
+    }
+
+    Ok(renamed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_rfc3339() {
+        assert_eq!(rfc3339(0), "1970-01-01T00:00:00Z");
+        assert_eq!(rfc3339(1_712_345_678), "2024-04-05T19:34:38Z");
+        assert_eq!(rfc3339(951_782_400), "2000-02-29T00:00:00Z");
+    }
+
+    #[test]
+    fn test_list_backups_sorted_by_timestamp() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("config.jsonc"), "{}").unwrap();
+        fs::write(dir.join("config.jsonc.backup.999999999"), "old").unwrap();
+        fs::write(dir.join("config.jsonc.backup.1712345678"), "{}").unwrap();
+        fs::write(dir.join("style.css.backup.1700000000"), "* {}").unwrap();
+        fs::write(dir.join("config.jsonc.bak"), "legacy").unwrap();
+
+        let backups = list_backups(dir.to_str().unwrap(), None).unwrap();
+        let names: Vec<&str> = backups.iter().map(|b| b.filename.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "config.jsonc.backup.1712345678",
+                "style.css.backup.1700000000",
+                "config.jsonc.backup.999999999",
+            ]
+        );
+        assert_eq!(backups[0].original_file, "config.jsonc");
+        assert_eq!(backups[0].date, "2024-04-05T19:34:38Z");
+        assert_eq!(backups[1].original_file, "style.css");
+        assert_eq!(backups[1].size_bytes, 4);
+
+        let styles = list_backups(dir.to_str().unwrap(), Some("style.css")).unwrap();
+        assert_eq!(styles.len(), 1);
+        assert_eq!(styles[0].filename, "style.css.backup.1700000000");
+        assert!(list_backups(dir.to_str().unwrap(), Some("config")).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_validate_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let path = |name: &str| dir.join(name).to_str().unwrap().to_string();
+        fs::write(dir.join("config.jsonc.backup.1"), "// ok\n{ \"height\": 30 }").unwrap();
+        fs::write(dir.join("config.jsonc.backup.2"), "{ \"height\": ").unwrap();
+        fs::write(dir.join("style.css.backup.1"), "  \n").unwrap();
+
+        assert!(validate_backup(&path("config.jsonc.backup.1"), &path("config.jsonc")).is_ok());
+        assert!(matches!(
+            validate_backup(&path("config.jsonc.backup.2"), &path("config.jsonc")),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            validate_backup(&path("config.jsonc.backup.2"), &path("config")),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            validate_backup(&path("style.css.backup.1"), &path("style.css")),
+            Err(AppError::Validation(_))
+        ));
+        // A CSS target doesn't need to be JSON
+        assert!(validate_backup(&path("config.jsonc.backup.2"), &path("style.css")).is_ok());
+    }
+
+    #[test]
+    fn test_parse_legacy_backup() {
+        assert_eq!(parse_legacy_backup("config.jsonc.bak"), Some(("config.jsonc", None)));
+        assert_eq!(
+            parse_legacy_backup("style.css.1700000000.old"),
+            Some(("style.css", Some(1700000000)))
+        );
+        assert_eq!(parse_legacy_backup("config-backup-2"), Some(("config", None)));
+        assert_eq!(parse_legacy_backup("config.jsonc.backup.1700000000"), None);
+        assert_eq!(parse_legacy_backup("config.jsonc"), None);
+        assert_eq!(parse_legacy_backup("config.pre-jsonc"), None);
+        assert_eq!(parse_legacy_backup("colors.css.bak"), None);
+    }
+
+    #[test]
+    fn test_migrate_mixed_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("config.jsonc"), "{}").unwrap();
+        fs::write(dir.join("config.jsonc.backup.1600000000"), "current").unwrap();
+        fs::write(dir.join("config.jsonc.bak.1700000000"), "old with timestamp").unwrap();
+        fs::write(dir.join("style.css.orig"), "old without timestamp").unwrap();
+
+        let renamed = migrate_backups(dir.to_str().unwrap()).unwrap();
+
+        assert_eq!(renamed.len(), 2);
+        assert_eq!(renamed[0].from, "config.jsonc.bak.1700000000");
+        assert_eq!(renamed[0].to, "config.jsonc.backup.1700000000");
+        assert_eq!(renamed[1].from, "style.css.orig");
+        assert!(renamed[1].to.starts_with("style.css.backup."));
+
+        assert_eq!(
+            fs::read_to_string(dir.join("config.jsonc.backup.1700000000")).unwrap(),
+            "old with timestamp"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join("config.jsonc.backup.1600000000")).unwrap(),
+            "current"
+        );
+        assert!(dir.join("config.jsonc").exists());
+        assert!(!dir.join("style.css.orig").exists());
+    }
+
+    #[test]
+    fn test_migrate_does_not_overwrite_existing_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("config.backup.1700000000"), "existing").unwrap();
+        fs::write(dir.join("config.old.1700000000"), "legacy").unwrap();
+
+        let renamed = migrate_backups(dir.to_str().unwrap()).unwrap();
+
+        assert_eq!(renamed[0].to, "config.backup.1700000001");
+        assert_eq!(
+            fs::read_to_string(dir.join("config.backup.1700000000")).unwrap(),
+            "existing"
+        );
+    }
+}
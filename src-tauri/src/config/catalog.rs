@@ -0,0 +1,447 @@
+// ============================================================================
+// MODULE CATALOG
+// ============================================================================
+//
+// Static knowledge about the Waybar module types, shared by the lints and
+// bulk edit operations. Mirrors the frontend's MODULE_METADATA list.
+
+use serde::Serialize;
+
+/// Static description of a Waybar module type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ModuleSpec {
+    /// Module type as written in the config (e.g. `clock`, `hyprland/workspaces`)
+    pub name: &'static str,
+    /// Whether the module shows a tooltip and honors the `tooltip` option
+    pub tooltip: bool,
+    /// Module-specific options, on top of [`COMMON_OPTIONS`]
+    pub options: &'static [&'static str],
+    /// Waybar release that introduced the module, if known
+    pub since: Option<&'static str>,
+    /// Placeholders the module provides to its format strings; empty when
+    /// not documented here
+    pub fields: &'static [&'static str],
+}
+
+const fn spec(name: &'static str, tooltip: bool, options: &'static [&'static str]) -> ModuleSpec {
+    ModuleSpec { name, tooltip, options, since: None, fields: &[] }
+}
+
+impl ModuleSpec {
+    /// Record the Waybar release that introduced the module
+    const fn since(self, version: &'static str) -> Self {
+        ModuleSpec { since: Some(version), ..self }
+    }
+
+    /// Record the placeholders the module provides
+    const fn fields(self, fields: &'static [&'static str]) -> Self {
+        ModuleSpec { fields, ..self }
+    }
+}
+
+/// Options every module accepts (labels, click/scroll actions, menus)
+///
+/// `format-*` and `tooltip-format-*` state variants are built dynamically by
+/// Waybar and are accepted for every module, see [`is_known_option`].
+pub const COMMON_OPTIONS: &[&str] = &[
+    "format",
+    "tooltip",
+    "tooltip-format",
+    "interval",
+    "states",
+    "rotate",
+    "max-length",
+    "min-length",
+    "align",
+    "justify",
+    "expand",
+    "markup",
+    "smooth-scrolling-threshold",
+    "on-click",
+    "on-click-release",
+    "on-double-click",
+    "on-triple-click",
+    "on-click-middle",
+    "on-click-middle-release",
+    "on-double-click-middle",
+    "on-triple-click-middle",
+    "on-click-right",
+    "on-click-right-release",
+    "on-double-click-right",
+    "on-triple-click-right",
+    "on-click-backward",
+    "on-click-backward-release",
+    "on-double-click-backward",
+    "on-triple-click-backward",
+    "on-click-forward",
+    "on-click-forward-release",
+    "on-double-click-forward",
+    "on-triple-click-forward",
+    "on-scroll-up",
+    "on-scroll-down",
+    "on-scroll-left",
+    "on-scroll-right",
+    "on-update",
+    "menu",
+    "menu-file",
+    "menu-actions",
+];
+
+/// All module types known to the app
+pub const MODULE_CATALOG: &[ModuleSpec] = &[
+    // System
+    spec(
+        "battery",
+        true,
+        &[
+            "bat", "adapter", "full-at", "design-capacity", "weighted-average", "bat-compatibility",
+        ],
+    )
+    .fields(&["capacity", "power", "icon", "time", "timeTo", "cycles", "health"]),
+    spec("cpu", true, &[])
+        .fields(&["load", "usage", "icon", "avg_frequency", "max_frequency", "min_frequency"]),
+    spec("memory", true, &[])
+        .fields(&[
+            "percentage", "total", "used", "avail", "swapPercentage", "swapTotal", "swapUsed",
+            "swapAvail", "icon",
+        ]),
+    spec("disk", true, &["path", "unit"])
+        .fields(&["percentage_used", "percentage_free", "total", "used", "free", "path"]),
+    spec(
+        "temperature",
+        true,
+        &[
+            "thermal-zone", "hwmon-path", "hwmon-path-abs", "input-filename", "critical-threshold",
+            "warning-threshold",
+        ],
+    )
+    .fields(&["temperatureC", "temperatureF", "temperatureK", "icon"]),
+    spec("network", true, &["interface", "interface-type", "family"])
+        .fields(&[
+            "ifname", "ipaddr", "gwaddr", "netmask", "netmask6", "cidr", "cidr6", "essid", "bssid",
+            "signalStrength", "signaldBm", "frequency", "icon", "bandwidthUpBits",
+            "bandwidthDownBits", "bandwidthTotalBits", "bandwidthUpOctets", "bandwidthDownOctets",
+            "bandwidthTotalOctets", "bandwidthUpBytes", "bandwidthDownBytes", "bandwidthTotalBytes",
+        ]),
+    spec("load", true, &[]),
+    spec(
+        "upower",
+        true,
+        &[
+            "native-path", "model", "icon-size", "hide-if-empty", "tooltip-spacing",
+            "tooltip-padding", "show-icon",
+        ],
+    ),
+    spec(
+        "backlight",
+        true,
+        &[
+            "device", "scroll-step", "reverse-scrolling", "reverse-mouse-scrolling",
+        ],
+    )
+    .fields(&["percent", "icon"]),
+    spec(
+        "pulseaudio",
+        true,
+        &[
+            "scroll-step", "reverse-scrolling", "reverse-mouse-scrolling", "max-volume",
+            "ignored-sinks",
+        ],
+    )
+    .fields(&["desc", "volume", "icon", "format_source", "source_volume", "source_desc"]),
+    spec(
+        "wireplumber",
+        true,
+        &[
+            "node-type", "scroll-step", "reverse-scrolling", "reverse-mouse-scrolling",
+            "max-volume",
+        ],
+    )
+    .since("0.9.13"),
+    spec("bluetooth", true, &["controller", "format-device-preference"]),
+    spec(
+        "keyboard-state",
+        false,
+        &[
+            "numlock", "capslock", "scrolllock", "device-path", "binding-keys",
+        ],
+    ),
+    // Generic window manager
+    spec(
+        "wlr/taskbar",
+        true,
+        &[
+            "all-outputs", "active-first", "sort-by-app-id", "icon-size", "icon-theme",
+            "ignore-list", "app_ids-mapping", "rewrite",
+        ],
+    ),
+    spec(
+        "ext/workspaces",
+        false,
+        &[
+            "all-outputs", "active-only", "ignore-hidden", "sort-by-name", "sort-by-coordinates",
+            "sort-by-id",
+        ],
+    )
+    .since("0.12.0"),
+    // Hyprland
+    spec(
+        "hyprland/workspaces",
+        false,
+        &[
+            "all-outputs", "active-only", "show-special", "special-visible-only", "persistent-only",
+            "persistent-workspaces", "persistent_workspaces", "move-to-monitor", "sort-by",
+            "sort-by-number", "sort-by-name", "window-rewrite", "window-rewrite-default",
+            "ignore-workspaces", "disable-scroll", "enable-bar-scroll",
+        ],
+    )
+    .since("0.9.16"),
+    spec("hyprland/window", true, &["separate-outputs", "icon", "icon-size", "rewrite"]),
+    spec("hyprland/language", true, &["keyboard-name"]),
+    spec("hyprland/submap", true, &["always-on", "default-submap"]).since("0.9.16"),
+    // Sway
+    spec(
+        "sway/workspaces",
+        false,
+        &[
+            "all-outputs", "disable-scroll", "disable-click", "disable-markup",
+            "disable-scroll-wraparound", "current-only", "persistent-workspaces",
+            "persistent_workspaces", "enable-bar-scroll", "numeric-first", "alphabetical_sort",
+            "window-rewrite", "window-rewrite-default", "window-format",
+        ],
+    ),
+    spec(
+        "sway/window",
+        true,
+        &[
+            "all-outputs", "rewrite", "icon", "icon-size", "offscreen-css", "offscreen-css-text",
+            "show-focused-workspace-name",
+        ],
+    ),
+    spec("sway/mode", true, &[]),
+    spec("sway/language", true, &["hide-single-layout"]),
+    spec("sway/scratchpad", true, &["show-empty"]).since("0.9.14"),
+    // River / DWL / Niri
+    spec(
+        "river/tags",
+        false,
+        &[
+            "num-tags", "tag-labels", "disable-click", "set-tags", "toggle-tags",
+        ],
+    ),
+    spec("river/mode", true, &[]),
+    spec("river/window", true, &[]),
+    spec("dwl/tags", false, &["num-tags", "tag-labels", "disable-click"]),
+    spec("dwl/window", true, &["rewrite"]).since("0.10.0"),
+    spec("niri/workspaces", false, &["all-outputs", "disable-click"]).since("0.11.0"),
+    spec("niri/window", true, &["separate-outputs", "icon", "icon-size", "rewrite"])
+        .since("0.11.0"),
+    spec("niri/language", true, &[]).since("0.11.0"),
+    // Media
+    spec(
+        "mpd",
+        true,
+        &[
+            "server", "port", "password", "timeout", "unknown-tag", "title-len", "artist-len",
+            "album-len", "state-icons", "consume-icons", "random-icons", "repeat-icons",
+            "single-icons",
+        ],
+    ),
+    spec(
+        "mpris",
+        true,
+        &[
+            "player", "ignored-players", "dynamic-order", "dynamic-importance-order", "dynamic-len",
+            "dynamic-separator", "title-len", "artist-len", "album-len", "ellipsis",
+            "enable-tooltip-len-limits", "player-icons", "status-icons",
+        ],
+    ),
+    spec(
+        "cava",
+        false,
+        &[
+            "cava_config", "framerate", "autosens", "sensitivity", "bars", "lower_cutoff_freq",
+            "higher_cutoff_freq", "method", "source", "sample_rate", "sample_bits", "stereo",
+            "reverse", "bar_delimiter", "monstercat", "waves", "noise_reduction", "input_delay",
+            "hide_on_silence", "sleep_timer",
+        ],
+    )
+    .since("0.9.18"),
+    // Utility
+    spec("clock", true, &["timezone", "timezones", "locale", "calendar", "actions"]),
+    spec("tray", false, &["icon-size", "spacing", "show-passive-items", "reverse-direction"]),
+    spec("idle_inhibitor", true, &["start-activated", "timeout"]),
+    spec("user", true, &["height", "width", "icon", "open-on-click"]),
+    spec(
+        "gamemode",
+        true,
+        &[
+            "hide-not-running", "use-icon", "glyph", "icon-name", "icon-spacing", "icon-size",
+        ],
+    )
+    .since("0.9.16"),
+    spec(
+        "privacy",
+        true,
+        &[
+            "icon-spacing", "icon-size", "transition-duration", "modules", "ignore-monitor",
+            "ignore",
+        ],
+    )
+    .since("0.10.0"),
+    spec("power-profiles-daemon", true, &[]).since("0.10.1"),
+    spec("systemd-failed-units", true, &["hide-on-ok", "system", "user"]).since("0.11.0"),
+    spec("image", true, &["path", "exec", "size", "signal"]),
+    // Containers and user-defined
+    spec("group", false, &["modules", "orientation", "drawer"]),
+    // Accepts arbitrary keys, see `check_unknown_keys`
+    spec("custom", true, &[]),
+];
+
+/// Get the module type of a config key
+///
+/// Strips the `#instance` suffix (`clock#utc` → `clock`) and collapses
+/// user-named modules to their kind (`custom/weather` → `custom`,
+/// `group/hardware` → `group`).
+pub fn module_type(name: &str) -> &str {
+    let base = name.split('#').next().unwrap_or(name);
+    match base.split_once('/') {
+        Some((kind @ ("custom" | "group" | "cffi"), _)) => kind,
+        _ => base,
+    }
+}
+
+/// Look up the catalog entry for a config key (see [`module_type`])
+pub fn find_module(name: &str) -> Option<&'static ModuleSpec> {
+    let kind = module_type(name);
+    MODULE_CATALOG.iter().find(|spec| spec.name == kind)
+}
+
+/// Get the CSS widget name Waybar gives a module
+///
+/// User-named modules keep their name with a dash (`custom/weather` →
+/// `custom-weather`, `group/hardware` → `group-hardware`); compositor modules
+/// drop the prefix (`hyprland/workspaces` → `workspaces`). The `#instance`
+/// suffix becomes a CSS class and is not part of the name.
+pub fn css_name(name: &str) -> String {
+    let base = name.split('#').next().unwrap_or(name);
+    match base.split_once('/') {
+        Some((kind @ ("custom" | "group" | "cffi"), rest)) => format!("{}-{}", kind, rest),
+        Some((_, rest)) => rest.to_string(),
+        None => base.to_string(),
+    }
+}
+
+/// Split a version like `v0.10.4` into numeric parts (`[0, 10, 4]`)
+fn version_parts(version: &str) -> Vec<u32> {
+    version
+        .trim()
+        .trim_start_matches('v')
+        .split('.')
+        .map(|part| {
+            let digits: String = part.chars().take_while(char::is_ascii_digit).collect();
+            digits.parse().unwrap_or(0)
+        })
+        .collect()
+}
+
+/// Check if version `a` is at or before version `b`, padding missing parts with 0
+fn version_at_or_before(a: &str, b: &str) -> bool {
+    let (mut a, mut b) = (version_parts(a), version_parts(b));
+    let len = a.len().max(b.len());
+    a.resize(len, 0);
+    b.resize(len, 0);
+    a <= b
+}
+
+/// List the catalog modules available in a Waybar version
+///
+/// Modules whose introducing release is unknown are always included.
+pub fn modules_available_in_version(version: &str) -> Vec<&'static ModuleSpec> {
+    MODULE_CATALOG
+        .iter()
+        .filter(|spec| spec.since.is_none_or(|since| version_at_or_before(since, version)))
+        .collect()
+}
+
+/// Check if `key` is a valid option for a module of the given type
+pub fn is_known_option(spec: &ModuleSpec, key: &str) -> bool {
+    key.starts_with("format-")
+        || key.starts_with("tooltip-format-")
+        || COMMON_OPTIONS.contains(&key)
+        || spec.options.contains(&key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_type() {
+        assert_eq!(module_type("clock"), "clock");
+        assert_eq!(module_type("clock#utc"), "clock");
+        assert_eq!(module_type("custom/weather"), "custom");
+        assert_eq!(module_type("custom/weather#2"), "custom");
+        assert_eq!(module_type("group/hardware"), "group");
+        assert_eq!(module_type("hyprland/workspaces"), "hyprland/workspaces");
+    }
+
+    #[test]
+    fn test_find_module() {
+        assert!(find_module("battery#bat2").unwrap().tooltip);
+        assert!(!find_module("tray").unwrap().tooltip);
+        assert_eq!(find_module("custom/spotify").unwrap().name, "custom");
+        assert!(find_module("batery").is_none());
+    }
+
+    #[test]
+    fn test_catalog_has_no_duplicates() {
+        let mut names: Vec<_> = MODULE_CATALOG.iter().map(|spec| spec.name).collect();
+        names.sort();
+        names.dedup();
+        assert_eq!(names.len(), MODULE_CATALOG.len());
+    }
+
+    #[test]
+    fn test_is_known_option() {
+        let battery = find_module("battery").unwrap();
+        assert!(is_known_option(battery, "full-at"));
+        assert!(is_known_option(battery, "on-click"));
+        assert!(is_known_option(battery, "format-charging"));
+        assert!(!is_known_option(battery, "tooltp"));
+        assert!(!is_known_option(battery, "spacing"));
+    }
+
+    #[test]
+    fn test_css_name() {
+        assert_eq!(css_name("clock#utc"), "clock");
+        assert_eq!(css_name("custom/weather"), "custom-weather");
+        assert_eq!(css_name("group/hardware"), "group-hardware");
+        assert_eq!(css_name("sway/workspaces"), "workspaces");
+        assert_eq!(css_name("wlr/taskbar"), "taskbar");
+    }
+
+    #[test]
+    fn test_modules_available_in_older_version() {
+        let names: Vec<_> = modules_available_in_version("v0.9.24")
+            .iter()
+            .map(|spec| spec.name)
+            .collect();
+
+        assert!(names.contains(&"clock"));
+        assert!(names.contains(&"hyprland/workspaces"));
+        assert!(names.contains(&"cava"));
+        assert!(!names.contains(&"privacy"));
+        assert!(!names.contains(&"niri/workspaces"));
+        assert!(!names.contains(&"ext/workspaces"));
+
+        assert_eq!(modules_available_in_version("0.12").len(), MODULE_CATALOG.len());
+    }
+
+    #[test]
+    fn test_version_at_or_before() {
+        assert!(version_at_or_before("0.9.16", "0.10.0"));
+        assert!(version_at_or_before("0.10.0", "v0.10"));
+        assert!(!version_at_or_before("0.11.0", "0.10.4"));
+    }
+}
@@ -0,0 +1,146 @@
+// ============================================================================
+// CONFIG DIRECTORY CLEANUP
+// ============================================================================
+
+use crate::error::{AppError, Result};
+use std::fs;
+use std::path::Path;
+
+/// Files that are part of the live Waybar setup and must never be removed
+const PROTECTED_FILES: [&str; 3] = ["config", "config.jsonc", "style.css"];
+
+/// Check if a file name looks like an editor swap/temp file
+///
+/// Recognizes:
+/// - Vim swap files (`.config.jsonc.swp`, `.swo`, `.swx`) and its `4913` write probe
+/// - Backup files with a trailing tilde (`style.css~`)
+/// - Generic temp files (`*.tmp`)
+/// - Emacs lock and autosave files (`.#config.jsonc`, `#config.jsonc#`)
+pub fn is_junk_file(file_name: &str) -> bool {
+    if PROTECTED_FILES.contains(&file_name) || file_name.contains(".backup.") {
+        return false;
+    }
+
+    file_name.ends_with(".swp")
+        || file_name.ends_with(".swo")
+        || file_name.ends_with(".swx")
+        || file_name.ends_with('~')
+        || file_name.ends_with(".tmp")
+        || file_name.starts_with(".#")
+        || (file_name.len() > 1 && file_name.starts_with('#') && file_name.ends_with('#'))
+        || file_name == "4913"
+}
+
+/// List editor swap/temp files in the config directory (non-recursive)
+/// Returns file names sorted alphabetically
+pub fn list_junk_files(config_dir: &str) -> Result<Vec<String>> {
+    let mut junk = Vec::new();
+
+    for entry in fs::read_dir(config_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+
+        let file_name = entry
+            .file_name()
+            .to_str()
+            .ok_or_else(|| AppError::Internal("Invalid UTF-8 in filename".to_string()))?
+            .to_string();
+
+        if is_junk_file(&file_name) {
+            junk.push(file_name);
+        }
+    }
+
+    junk.sort();
+    Ok(junk)
+}
+
+/// Remove editor swap/temp files from the config directory
+/// Returns the names of the removed files
+pub fn clean_junk_files(config_dir: &str) -> Result<Vec<String>> {
+    let junk = list_junk_files(config_dir)?;
+
+    for file_name in &junk {
+        fs::remove_file(Path::new(config_dir).join(file_name))?;
+        log::info!("Removed junk file: {}", file_name);
+    }
+
+    Ok(junk)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup_dir() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        for name in [
+            "config.jsonc",
+            "config",
+            "style.css",
+            "config.jsonc.backup.1712345678",
+            ".config.jsonc.swp",
+            "style.css~",
+            "scratch.tmp",
+            ".#style.css",
+        ] {
+            fs::write(temp_dir.path().join(name), "x").unwrap();
+        }
+        temp_dir
+    }
+
+    #[test]
+    fn test_is_junk_file() {
+        assert!(is_junk_file(".config.jsonc.swp"));
+        assert!(is_junk_file(".style.css.swo"));
+        assert!(is_junk_file("config.jsonc~"));
+        assert!(is_junk_file("write.tmp"));
+        assert!(is_junk_file("#style.css#"));
+        assert!(is_junk_file(".#style.css"));
+        assert!(is_junk_file("4913"));
+    }
+
+    #[test]
+    fn test_protected_files_are_not_junk() {
+        assert!(!is_junk_file("config"));
+        assert!(!is_junk_file("config.jsonc"));
+        assert!(!is_junk_file("style.css"));
+        assert!(!is_junk_file("config.jsonc.backup.1712345678"));
+        assert!(!is_junk_file("modules.json"));
+        assert!(!is_junk_file("#"));
+    }
+
+    #[test]
+    fn test_list_junk_files() {
+        let temp_dir = setup_dir();
+        let junk = list_junk_files(temp_dir.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(
+            junk,
+            vec![".#style.css", ".config.jsonc.swp", "scratch.tmp", "style.css~"]
+        );
+    }
+
+    #[test]
+    fn test_clean_junk_files_keeps_real_files() {
+        let temp_dir = setup_dir();
+        let removed = clean_junk_files(temp_dir.path().to_str().unwrap()).unwrap();
+
+        assert_eq!(removed.len(), 4);
+        assert!(!temp_dir.path().join(".config.jsonc.swp").exists());
+        assert!(!temp_dir.path().join("style.css~").exists());
+        assert!(temp_dir.path().join("config.jsonc").exists());
+        assert!(temp_dir.path().join("config").exists());
+        assert!(temp_dir.path().join("style.css").exists());
+        assert!(temp_dir.path().join("config.jsonc.backup.1712345678").exists());
+    }
+
+    #[test]
+    fn test_list_junk_files_missing_dir() {
+        let result = list_junk_files("/nonexistent/waybar");
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,1056 @@
+// ============================================================================
+// GTK CSS ANALYSIS
+// ============================================================================
+//
+// Lightweight, comment-aware scanning of Waybar's style.css. This is not a
+// full CSS parser: it works on a masked copy of the stylesheet where comments
+// and string literals are blanked out, so byte offsets (and therefore line
+// numbers) match the original file.
+
+use crate::config::catalog::{css_name, MODULE_CATALOG};
+use crate::config::list_config_modules;
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// A non-fatal finding about a stylesheet
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CssWarning {
+    /// 1-based line number in the stylesheet
+    pub line: usize,
+    /// The offending token (e.g. `@fg_colour`)
+    pub subject: String,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+/// A color declared with `@define-color`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ColorDef {
+    /// Color name, without the `@`
+    pub name: String,
+    /// Value as written (e.g. `#1e1e2e` or `rgba(30, 30, 46, 0.9)`)
+    pub value: String,
+    /// 1-based line number of the definition
+    pub line: usize,
+}
+
+/// A stylesheet with normalized hex colors
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NormalizedColors {
+    /// The rewritten stylesheet
+    pub css: String,
+    /// Expanded shorthands and invalid colors, in stylesheet order
+    pub fixes: Vec<CssWarning>,
+}
+
+// ============================================================================
+// SCANNING HELPERS
+// ============================================================================
+
+/// Blank out comments and string literals, keeping byte offsets and newlines
+///
+/// Every byte inside a `/* */` comment or a quoted string (quotes included)
+/// is replaced by a space, so the result has the same length and line layout
+/// as the input and is always valid UTF-8.
+pub fn mask_comments_and_strings(css: &str) -> String {
+    fn blank(masked: &mut [u8], from: usize, to: usize) {
+        for byte in &mut masked[from..to] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    }
+
+    let bytes = css.as_bytes();
+    let mut masked = bytes.to_vec();
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = css[i + 2..]
+                    .find("*/")
+                    .map(|pos| i + 2 + pos + 2)
+                    .unwrap_or(bytes.len());
+                blank(&mut masked, i, end);
+                i = end;
+            }
+            quote @ (b'"' | b'\'') => {
+                let mut end = i + 1;
+                while end < bytes.len() && bytes[end] != quote && bytes[end] != b'\n' {
+                    if bytes[end] == b'\\' {
+                        end += 1;
+                    }
+                    end += 1;
+                }
+                let end = (end + 1).min(bytes.len());
+                blank(&mut masked, i, end);
+                i = end;
+            }
+            _ => i += 1,
+        }
+    }
+
+    // Only ASCII bytes were written and multi-byte sequences were blanked whole
+    String::from_utf8(masked).unwrap_or_default()
+}
+
+/// Get the 1-based line number of a byte offset
+pub fn line_at(css: &str, offset: usize) -> usize {
+    css[..offset.min(css.len())].matches('\n').count() + 1
+}
+
+/// Check if a byte can be part of a CSS identifier
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'-'
+}
+
+/// Read the identifier starting at `start`, returning its end offset
+fn ident_end(masked: &str, start: usize) -> usize {
+    masked.as_bytes()[start..]
+        .iter()
+        .position(|b| !is_ident_byte(*b))
+        .map(|pos| start + pos)
+        .unwrap_or(masked.len())
+}
+
+// ============================================================================
+// COLOR REFERENCES
+// ============================================================================
+
+/// GTK CSS at-rule keywords, which are not color references
+const AT_RULES: [&str; 7] = [
+    "define-color",
+    "import",
+    "keyframes",
+    "media",
+    "binding-set",
+    "font-face",
+    "charset",
+];
+
+/// Named colors commonly provided by GTK themes (Adwaita and derivatives)
+/// and usable without an `@define-color` in the stylesheet
+pub const GTK_THEME_COLORS: [&str; 40] = [
+    "theme_fg_color",
+    "theme_bg_color",
+    "theme_base_color",
+    "theme_text_color",
+    "theme_selected_bg_color",
+    "theme_selected_fg_color",
+    "theme_unfocused_fg_color",
+    "theme_unfocused_bg_color",
+    "theme_unfocused_base_color",
+    "theme_unfocused_text_color",
+    "theme_unfocused_selected_bg_color",
+    "theme_unfocused_selected_fg_color",
+    "insensitive_fg_color",
+    "insensitive_bg_color",
+    "insensitive_base_color",
+    "borders",
+    "unfocused_borders",
+    "warning_color",
+    "error_color",
+    "success_color",
+    "content_view_bg",
+    "text_view_bg",
+    "wm_title",
+    "wm_unfocused_title",
+    "wm_highlight",
+    "wm_borders_edge",
+    "accent_color",
+    "accent_bg_color",
+    "accent_fg_color",
+    "destructive_color",
+    "destructive_bg_color",
+    "window_bg_color",
+    "window_fg_color",
+    "view_bg_color",
+    "view_fg_color",
+    "headerbar_bg_color",
+    "headerbar_fg_color",
+    "card_bg_color",
+    "popover_bg_color",
+    "dialog_bg_color",
+];
+
+/// Collect the names declared with `@define-color <name> <value>;`
+fn defined_color_names(masked: &str) -> HashSet<String> {
+    color_definitions(masked)
+        .into_iter()
+        .map(|(name, _, _)| name.to_string())
+        .collect()
+}
+
+/// Find `@color` references that don't resolve to a definition
+///
+/// A reference resolves when the stylesheet declares it with `@define-color`
+/// or it's one of the [`GTK_THEME_COLORS`]. References in comments and strings
+/// are ignored. Colors defined in `@import`ed files are not visible here.
+pub fn find_undefined_colors(css: &str) -> Vec<CssWarning> {
+    let masked = mask_comments_and_strings(css);
+    let defined = defined_color_names(&masked);
+    let bytes = masked.as_bytes();
+    let mut warnings = Vec::new();
+
+    for (at, _) in masked.match_indices('@') {
+        // Skip the `@` inside identifiers or other tokens
+        if at > 0 && is_ident_byte(bytes[at - 1]) {
+            continue;
+        }
+
+        let end = ident_end(&masked, at + 1);
+        let name = &masked[at + 1..end];
+        if name.is_empty() || AT_RULES.contains(&name) {
+            continue;
+        }
+
+        if !defined.contains(name) && !GTK_THEME_COLORS.contains(&name) {
+            warnings.push(CssWarning {
+                line: line_at(css, at),
+                subject: format!("@{}", name),
+                message: format!(
+                    "Color @{} is not defined with @define-color and is not a known GTK theme color",
+                    name
+                ),
+            });
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// UNUSED RULES
+// ============================================================================
+
+/// Prefixes of CSS names Waybar gives user-named modules
+const USER_MODULE_PREFIXES: [&str; 3] = ["custom-", "group-", "cffi-"];
+
+/// Get the top-level blocks of a stylesheet
+///
+/// Returns the byte offset of each block's prelude (the text before `{`),
+/// the prelude itself and the byte range of the block body. At-rule
+/// statements (`@define-color ...;`) are skipped.
+fn top_level_blocks(masked: &str) -> Vec<(usize, &str, Range<usize>)> {
+    let mut blocks = Vec::new();
+    let mut depth = 0usize;
+    let mut start = 0;
+    let mut body_start = 0;
+
+    for (pos, byte) in masked.bytes().enumerate() {
+        match byte {
+            b'{' => {
+                if depth == 0 {
+                    body_start = pos + 1;
+                }
+                depth += 1;
+            }
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                if depth == 0 {
+                    blocks.push((start, &masked[start..body_start - 1], body_start..pos));
+                    start = pos + 1;
+                }
+            }
+            b';' if depth == 0 => start = pos + 1,
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+/// Get the top-level selectors of a stylesheet with their byte offsets
+///
+/// Each comma-separated selector of a rule is returned separately. Text
+/// inside blocks and at-rule statements (`@define-color ...;`) is skipped.
+fn top_level_selectors(masked: &str) -> Vec<(usize, &str)> {
+    let mut selectors = Vec::new();
+
+    for (start, prelude, _) in top_level_blocks(masked) {
+        let mut offset = start;
+        for part in prelude.split(',') {
+            let trimmed = part.trim_start();
+            let part_start = offset + (part.len() - trimmed.len());
+            let trimmed = trimmed.trim_end();
+            if !trimmed.is_empty() && !trimmed.starts_with('@') {
+                selectors.push((part_start, trimmed));
+            }
+            offset += part.len() + 1;
+        }
+    }
+
+    selectors
+}
+
+/// Get the module names referenced by `#id` / `.class` tokens of a selector
+///
+/// Only names that look like module widgets count: catalog modules or
+/// user-named modules (`custom-*`, `group-*`). Structural names such as
+/// `window#waybar` or state classes like `.charging` are ignored.
+fn selector_module_names(selector: &str, module_names: &HashSet<String>) -> Vec<String> {
+    let bytes = selector.as_bytes();
+    let mut names = Vec::new();
+
+    for (pos, byte) in bytes.iter().enumerate() {
+        if *byte != b'#' && *byte != b'.' {
+            continue;
+        }
+
+        let end = ident_end(selector, pos + 1);
+        let name = &selector[pos + 1..end];
+        let module_like = module_names.contains(name)
+            || USER_MODULE_PREFIXES.iter().any(|p| name.len() > p.len() && name.starts_with(p));
+
+        if module_like {
+            names.push(name.to_string());
+        }
+    }
+
+    names
+}
+
+/// Find rules whose selectors target modules that aren't in the config
+///
+/// A selector is unused when one of its `#module` / `.module` tokens names a
+/// module not placed on any bar (see [`list_config_modules`]).
+pub fn find_unused_css_rules(css: &str, config: &Value) -> Vec<CssWarning> {
+    let masked = mask_comments_and_strings(css);
+    let catalog_names: HashSet<String> = MODULE_CATALOG.iter().map(|spec| css_name(spec.name)).collect();
+    let used: HashSet<String> = list_config_modules(config).iter().map(|name| css_name(name)).collect();
+    let mut warnings = Vec::new();
+
+    for (offset, selector) in top_level_selectors(&masked) {
+        let unused = selector_module_names(selector, &catalog_names)
+            .into_iter()
+            .find(|name| !used.contains(name));
+
+        if let Some(name) = unused {
+            warnings.push(CssWarning {
+                line: line_at(css, offset),
+                subject: selector.to_string(),
+                message: format!("Selector targets module {} which is not in the config", name),
+            });
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// IMPORT FLATTENING
+// ============================================================================
+
+/// Get the file an `@import` statement refers to
+///
+/// `statement` is the text between `@import` and `;`, e.g. ` "colors.css"`
+/// or ` url('colors.css')`.
+fn import_target(statement: &str) -> Option<&str> {
+    let mut target = statement.trim();
+    if let Some(inner) = target.strip_prefix("url(").and_then(|t| t.strip_suffix(')')) {
+        target = inner.trim();
+    }
+
+    for quote in ['"', '\''] {
+        if let Some(inner) = target.strip_prefix(quote).and_then(|t| t.strip_suffix(quote)) {
+            return Some(inner);
+        }
+    }
+
+    (!target.is_empty() && !target.contains(char::is_whitespace)).then_some(target)
+}
+
+/// Inline the `@import`s of one file, recursing into imported files
+///
+/// `chain` holds the canonical paths of the files currently being inlined,
+/// outermost first, and is used to report import cycles.
+fn inline_imports(path: &Path, chain: &mut Vec<PathBuf>) -> Result<String> {
+    let canonical = fs::canonicalize(path)
+        .map_err(|e| AppError::NotFound(format!("{}: {}", path.display(), e)))?;
+
+    if let Some(start) = chain.iter().position(|p| *p == canonical) {
+        let cycle: Vec<String> = chain[start..]
+            .iter()
+            .chain(std::iter::once(&canonical))
+            .map(|p| p.display().to_string())
+            .collect();
+        return Err(AppError::Validation(format!("CSS @import cycle: {}", cycle.join(" → "))));
+    }
+
+    let css = fs::read_to_string(&canonical)?;
+    let masked = mask_comments_and_strings(&css);
+    let base_dir = canonical.parent().unwrap_or(Path::new("/")).to_path_buf();
+    chain.push(canonical);
+
+    let mut flattened = String::with_capacity(css.len());
+    let mut copied = 0;
+
+    for (at, _) in masked.match_indices("@import") {
+        if at < copied {
+            continue;
+        }
+
+        let end = masked[at..].find(';').map(|pos| at + pos + 1).unwrap_or(masked.len());
+        let statement = css[at + "@import".len()..end].trim_end_matches(';');
+
+        // Leave remote and malformed imports to GTK
+        let Some(target) = import_target(statement).filter(|t| !t.contains("://")) else {
+            continue;
+        };
+
+        flattened.push_str(&css[copied..at]);
+        flattened.push_str(&format!("/* @import {} */\n", target));
+        flattened.push_str(&inline_imports(&base_dir.join(target), chain)?);
+        copied = end;
+    }
+
+    flattened.push_str(&css[copied..]);
+    chain.pop();
+
+    Ok(flattened)
+}
+
+/// Inline every local `@import` of a stylesheet into one CSS document
+///
+/// Imports resolve relative to the importing file. A cycle fails with a
+/// validation error listing the whole chain (`a.css → b.css → a.css`).
+pub fn flatten_css(path: &Path) -> Result<String> {
+    inline_imports(path, &mut Vec::new())
+}
+
+// ============================================================================
+// FONT OVERRIDES
+// ============================================================================
+
+/// Selectors whose font applies to the whole bar
+const GLOBAL_FONT_SELECTORS: [&str; 4] = ["*", "window", "window#waybar", "#waybar"];
+
+/// Get the last `font-family` / `font` value declared in a block body
+///
+/// Declarations are located in the masked text and their values read from
+/// the original so quoted family names survive.
+fn declared_font(css: &str, masked: &str, body: Range<usize>) -> Option<String> {
+    let mut font = None;
+    let mut offset = body.start;
+
+    for declaration in masked[body.clone()].split(';') {
+        if let Some(colon) = declaration.find(':') {
+            let property = declaration[..colon].trim();
+            if property == "font-family" || property == "font" {
+                let value_start = offset + colon + 1;
+                let value_end = offset + declaration.len();
+                font = Some(css[value_start..value_end].trim().to_string());
+            }
+        }
+        offset += declaration.len() + 1;
+    }
+
+    font
+}
+
+/// Find module rules overriding the bar-wide font
+///
+/// When a global rule (`*`, `window#waybar`, ...) sets a font, every
+/// module rule setting a different one is reported so mixed fonts are
+/// deliberate. Without a global font nothing is reported.
+pub fn find_font_overrides(css: &str) -> Vec<CssWarning> {
+    let masked = mask_comments_and_strings(css);
+    let catalog_names: HashSet<String> = MODULE_CATALOG.iter().map(|spec| css_name(spec.name)).collect();
+    let blocks = top_level_blocks(&masked);
+
+    let global_font = blocks
+        .iter()
+        .rev()
+        .filter(|(_, prelude, _)| {
+            prelude
+                .split(',')
+                .any(|selector| GLOBAL_FONT_SELECTORS.contains(&selector.trim()))
+        })
+        .find_map(|(_, _, body)| declared_font(css, &masked, body.clone()));
+    let Some(global_font) = global_font else {
+        return Vec::new();
+    };
+
+    let mut warnings = Vec::new();
+    for (start, prelude, body) in blocks {
+        let targets_module = prelude
+            .split(',')
+            .any(|selector| !selector_module_names(selector, &catalog_names).is_empty());
+        if !targets_module {
+            continue;
+        }
+
+        if let Some(font) = declared_font(css, &masked, body) {
+            if font != global_font {
+                let offset = start + (prelude.len() - prelude.trim_start().len());
+                warnings.push(CssWarning {
+                    line: line_at(css, offset),
+                    subject: prelude.trim().to_string(),
+                    message: format!("Overrides the global font {} with {}", global_font, font),
+                });
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// HEX COLORS
+// ============================================================================
+
+/// Find the byte offsets of `#` tokens in value position
+///
+/// Values are the text after `:` in a declaration, or an at-rule statement
+/// such as `@define-color fg #fff;`. Selector ids like `#clock` are skipped.
+fn value_hash_offsets(masked: &str) -> Vec<usize> {
+    let bytes = masked.as_bytes();
+    let mut offsets = Vec::new();
+    let mut depth = 0usize;
+    let mut in_value = false;
+    let mut in_at_rule = false;
+    let mut statement_started = false;
+
+    for (pos, byte) in bytes.iter().enumerate() {
+        match byte {
+            b'{' => {
+                depth += 1;
+                in_value = false;
+            }
+            b'}' => {
+                depth = depth.saturating_sub(1);
+                in_value = false;
+                in_at_rule = false;
+                statement_started = false;
+            }
+            b';' => {
+                in_value = false;
+                if depth == 0 {
+                    in_at_rule = false;
+                    statement_started = false;
+                }
+            }
+            b':' if depth > 0 => in_value = true,
+            b'@' if depth == 0 && !statement_started => {
+                in_at_rule = true;
+                statement_started = true;
+            }
+            b'#' if (in_value || (depth == 0 && in_at_rule))
+                && (pos == 0 || !is_ident_byte(bytes[pos - 1])) =>
+            {
+                offsets.push(pos);
+            }
+            byte if depth == 0 && !byte.is_ascii_whitespace() => statement_started = true,
+            _ => {}
+        }
+    }
+
+    offsets
+}
+
+/// Check hex color lengths and optionally expand shorthands
+///
+/// `#rgb` and `#rgba` become `#rrggbb` / `#rrggbbaa` when `expand` is set;
+/// colors of any length other than 3, 4, 6 or 8 digits are reported and left
+/// as written. Colors inside comments and strings are not touched.
+pub fn normalize_hex_colors(css: &str, expand: bool) -> NormalizedColors {
+    let masked = mask_comments_and_strings(css);
+    let mut output = String::with_capacity(css.len());
+    let mut fixes = Vec::new();
+    let mut copied = 0;
+
+    for hash in value_hash_offsets(&masked) {
+        let end = ident_end(&masked, hash + 1);
+        let digits = &css[hash + 1..end];
+        if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            continue;
+        }
+
+        match digits.len() {
+            6 | 8 => {}
+            3 | 4 if expand => {
+                let expanded: String = digits.chars().flat_map(|c| [c, c]).collect();
+                output.push_str(&css[copied..hash]);
+                output.push('#');
+                output.push_str(&expanded);
+                copied = end;
+
+                fixes.push(CssWarning {
+                    line: line_at(css, hash),
+                    subject: format!("#{}", digits),
+                    message: format!("Expanded to #{}", expanded),
+                });
+            }
+            3 | 4 => {}
+            len => fixes.push(CssWarning {
+                line: line_at(css, hash),
+                subject: format!("#{}", digits),
+                message: format!("Invalid hex color: {} digits (expected 3, 4, 6 or 8)", len),
+            }),
+        }
+    }
+    output.push_str(&css[copied..]);
+
+    NormalizedColors { css: output, fixes }
+}
+
+// ============================================================================
+// SYNTAX VALIDATION
+// ============================================================================
+
+/// Build the error for a syntax problem at a byte offset
+fn syntax_error(css: &str, offset: usize, problem: &str) -> AppError {
+    AppError::Validation(format!("CSS syntax error on line {}: {}", line_at(css, offset), problem))
+}
+
+/// Check one declaration inside a block (`start..end` in the masked text)
+///
+/// A declaration needs a `:`; a second `:` outside parentheses means the
+/// `;` ending the previous declaration is missing.
+fn check_declaration(css: &str, masked: &str, start: usize, end: usize) -> Result<()> {
+    let text = &masked[start..end];
+    let Some(first) = text.find(|c: char| !c.is_whitespace()) else {
+        return Ok(());
+    };
+
+    let mut depth = 0usize;
+    let mut colons = Vec::new();
+    for (pos, byte) in text.bytes().enumerate() {
+        match byte {
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b':' if depth == 0 => colons.push(pos),
+            _ => {}
+        }
+    }
+
+    match colons.as_slice() {
+        [] => Err(syntax_error(
+            css,
+            start + first,
+            &format!("declaration \"{}\" has no ':'", text.trim()),
+        )),
+        [_] => Ok(()),
+        [_, second, ..] => {
+            // Point at the line of the declaration that lacks its `;`
+            let previous_end = text[..*second].trim_end().rfind(char::is_whitespace).unwrap_or(0);
+            let line_end = text[..previous_end].trim_end().len();
+            Err(syntax_error(css, start + line_end, "missing ';' after declaration"))
+        }
+    }
+}
+
+/// Check a stylesheet for the mistakes GTK reports most cryptically
+///
+/// Not a CSS grammar: verifies that braces and parentheses balance and that
+/// each declaration in a block has a `:` and is separated from the next by
+/// `;` (the last one in a block may omit it, as CSS allows). Comments and
+/// strings are ignored. The error names the offending line.
+pub fn validate_css(css: &str) -> Result<()> {
+    let masked = mask_comments_and_strings(css);
+    let mut open: Vec<(u8, usize)> = Vec::new();
+    let mut segment_start = 0;
+
+    for (offset, byte) in masked.bytes().enumerate() {
+        let in_block = open.iter().any(|(kind, _)| *kind == b'{');
+        match byte {
+            b'(' => open.push((b'(', offset)),
+            b')' => match open.pop() {
+                Some((b'(', _)) => {}
+                Some((_, opened)) => return Err(syntax_error(css, opened, "'{' closed by ')'")),
+                None => return Err(syntax_error(css, offset, "unexpected ')'")),
+            },
+            b'{' | b'}' | b';' if open.last().is_some_and(|(kind, _)| *kind == b'(') => {
+                let (_, opened) = open[open.len() - 1];
+                return Err(syntax_error(css, opened, "'(' is never closed"));
+            }
+            b'{' => {
+                open.push((b'{', offset));
+                segment_start = offset + 1;
+            }
+            b'}' => {
+                if open.pop().is_none() {
+                    return Err(syntax_error(css, offset, "unexpected '}'"));
+                }
+                check_declaration(css, &masked, segment_start, offset)?;
+                segment_start = offset + 1;
+            }
+            b';' => {
+                if in_block {
+                    check_declaration(css, &masked, segment_start, offset)?;
+                }
+                segment_start = offset + 1;
+            }
+            _ => {}
+        }
+    }
+
+    match open.last() {
+        Some((b'{', opened)) => Err(syntax_error(css, *opened, "'{' is never closed")),
+        Some((_, opened)) => Err(syntax_error(css, *opened, "'(' is never closed")),
+        None => Ok(()),
+    }
+}
+
+// ============================================================================
+// COLOR DEFINITIONS
+// ============================================================================
+
+/// Locate each `@define-color <name> <value>;` statement
+///
+/// Returns the name, the offset of the `@` and the byte range of the value
+/// (trimmed, without the `;`). Statements with no name or no closing `;`
+/// before the next block are skipped.
+fn color_definitions(masked: &str) -> Vec<(&str, usize, Range<usize>)> {
+    const KEYWORD: &str = "@define-color";
+    let mut definitions = Vec::new();
+
+    for (at, _) in masked.match_indices(KEYWORD) {
+        let after = at + KEYWORD.len();
+        let name_start = after + (masked[after..].len() - masked[after..].trim_start().len());
+        let name_end = ident_end(masked, name_start);
+        if name_end == name_start || name_start == after {
+            continue;
+        }
+
+        let Some(semicolon) = masked[name_end..].find([';', '{', '}']).map(|pos| name_end + pos) else {
+            continue;
+        };
+        if masked.as_bytes()[semicolon] != b';' {
+            continue;
+        }
+
+        let value = &masked[name_end..semicolon];
+        let start = name_end + (value.len() - value.trim_start().len());
+        let end = name_end + value.trim_end().len();
+        if start < end {
+            definitions.push((&masked[name_start..name_end], at, start..end));
+        }
+    }
+
+    definitions
+}
+
+/// List the colors defined with `@define-color`, in stylesheet order
+///
+/// Accepts any value form GTK does (`#rrggbb`, `rgba(...)`, `shade(@fg, 1.2)`,
+/// other `@color` references); definitions inside comments are ignored.
+/// A name defined twice is listed twice, the later one being the one in effect.
+pub fn extract_color_definitions(css: &str) -> Vec<ColorDef> {
+    let masked = mask_comments_and_strings(css);
+
+    color_definitions(&masked)
+        .into_iter()
+        .map(|(name, at, value)| ColorDef {
+            name: name.to_string(),
+            value: css[value].to_string(),
+            line: line_at(css, at),
+        })
+        .collect()
+}
+
+/// Change the value of a `@define-color` definition in place
+///
+/// Only the value text is replaced, so comments, spacing and the rest of the
+/// file are kept byte for byte. When the name is defined more than once the
+/// last definition, the one GTK uses, is changed.
+pub fn set_color_definition(css: &str, name: &str, new_value: &str) -> Result<String> {
+    let new_value = new_value.trim();
+    if new_value.is_empty() || new_value.contains([';', '{', '}']) {
+        return Err(AppError::Validation(format!(
+            "Invalid color value for @{}: \"{}\"",
+            name, new_value
+        )));
+    }
+
+    let masked = mask_comments_and_strings(css);
+    let (_, _, value) = color_definitions(&masked)
+        .into_iter()
+        .rfind(|(defined, _, _)| *defined == name)
+        .ok_or_else(|| AppError::NotFound(format!("Color @{} is not defined", name)))?;
+
+    Ok(format!("{}{}{}", &css[..value.start], new_value, &css[value.end..]))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_comments_and_strings() {
+        let css = "/* @a */ a { content: \"@b\"; color: @c; }\n/* multi\nline */ b {}";
+        let masked = mask_comments_and_strings(css);
+
+        assert_eq!(masked.len(), css.len());
+        assert!(!masked.contains("@a"));
+        assert!(!masked.contains("@b"));
+        assert!(masked.contains("@c"));
+        assert_eq!(masked.matches('\n').count(), 2);
+    }
+
+    #[test]
+    fn test_mask_keeps_offsets_with_unicode() {
+        let css = "/* ñ 🚀 */ #clock { color: @fg; }";
+        let masked = mask_comments_and_strings(css);
+
+        assert_eq!(masked.len(), css.len());
+        assert_eq!(masked.find("@fg"), css.find("@fg"));
+    }
+
+    #[test]
+    fn test_line_at() {
+        let css = "a {}\nb {}\nc {}";
+        assert_eq!(line_at(css, 0), 1);
+        assert_eq!(line_at(css, css.find('b').unwrap()), 2);
+        assert_eq!(line_at(css, css.find('c').unwrap()), 3);
+    }
+
+    #[test]
+    fn test_undefined_color_reference() {
+        let css = "@define-color fg #ffffff;\n\n#clock {\n  color: @fg;\n  background: @foo;\n}";
+        let warnings = find_undefined_colors(css);
+
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].subject, "@foo");
+        assert_eq!(warnings[0].line, 5);
+    }
+
+    #[test]
+    fn test_defined_and_theme_colors_resolve() {
+        let css = "@import \"colors.css\";\n@define-color accent rgba(1, 2, 3, 0.5);\n\
+                   window#waybar { color: @accent; background: alpha(@theme_bg_color, 0.8); }";
+        assert!(find_undefined_colors(css).is_empty());
+    }
+
+    #[test]
+    fn test_references_in_comments_are_ignored() {
+        let css = "/* color: @old_fg; */\n#battery { color: red; }";
+        assert!(find_undefined_colors(css).is_empty());
+    }
+
+    #[test]
+    fn test_top_level_selectors() {
+        let css = "@define-color fg #fff;\n#clock,\n  #battery.charging { color: @fg; }\n@media (x) { a {} }";
+        let masked = mask_comments_and_strings(css);
+        let selectors: Vec<_> = top_level_selectors(&masked).into_iter().map(|(_, s)| s).collect();
+        assert_eq!(selectors, vec!["#clock", "#battery.charging"]);
+    }
+
+    #[test]
+    fn test_rule_for_removed_module_is_unused() {
+        let config = serde_json::json!({
+            "modules-left": ["hyprland/workspaces"],
+            "modules-right": ["clock#utc", "custom/weather"]
+        });
+        let css = "window#waybar { background: black; }\n\
+                   #workspaces button.active { color: red; }\n\
+                   #clock, #battery.charging { color: white; }\n\
+                   #custom-weather, #custom-spotify { color: green; }\n\
+                   tooltip { border: none; }\n\
+                   .modules-left > widget:first-child > #workspaces { margin: 0; }";
+
+        let warnings = find_unused_css_rules(css, &config);
+        let subjects: Vec<_> = warnings.iter().map(|w| (w.line, w.subject.as_str())).collect();
+        assert_eq!(subjects, vec![(3, "#battery.charging"), (4, "#custom-spotify")]);
+    }
+
+    #[test]
+    fn test_import_target() {
+        assert_eq!(import_target(" \"colors.css\""), Some("colors.css"));
+        assert_eq!(import_target(" url('themes/dark.css')"), Some("themes/dark.css"));
+        assert_eq!(import_target(" url(plain.css)"), Some("plain.css"));
+        assert_eq!(import_target(""), None);
+    }
+
+    #[test]
+    fn test_flatten_css_inlines_imports() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::create_dir(dir.join("themes")).unwrap();
+        fs::write(dir.join("themes/colors.css"), "@define-color fg #fff;").unwrap();
+        fs::write(
+            dir.join("style.css"),
+            "/* @import \"ignored.css\"; */\n@import \"themes/colors.css\";\n#clock { color: @fg; }",
+        )
+        .unwrap();
+
+        let flattened = flatten_css(&dir.join("style.css")).unwrap();
+        assert!(flattened.contains("@define-color fg #fff;"));
+        assert!(flattened.contains("#clock { color: @fg; }"));
+        assert!(!flattened.contains("@import \"themes/colors.css\";"));
+    }
+
+    #[test]
+    fn test_import_cycle_reports_chain() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("a.css"), "@import \"b.css\";").unwrap();
+        fs::write(dir.join("b.css"), "@import url(\"c.css\");").unwrap();
+        fs::write(dir.join("c.css"), "@import 'a.css';").unwrap();
+
+        let Err(AppError::Validation(message)) = flatten_css(&dir.join("a.css")) else {
+            panic!("expected a validation error");
+        };
+
+        let positions: Vec<usize> = ["a.css →", "b.css →", "c.css →"]
+            .iter()
+            .map(|name| message.find(name).expect(name))
+            .collect();
+        assert!(positions.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(message.trim_end().ends_with("a.css"));
+    }
+
+    #[test]
+    fn test_missing_import_is_not_found() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("style.css");
+        fs::write(&path, "@import \"missing.css\";").unwrap();
+
+        assert!(matches!(flatten_css(&path), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_module_font_overrides_global_font() {
+        let css = "* { font-family: \"JetBrainsMono Nerd Font\"; font-size: 13px; }\n\
+                   #clock { font-family: \"Fira Sans\"; }\n\
+                   #battery { font-family: \"JetBrainsMono Nerd Font\"; }\n\
+                   #custom-weather,\n#tray { font: bold 12px Noto Sans; }\n\
+                   /* #cpu { font-family: Hack; } */\n\
+                   tooltip { font-family: Hack; }";
+
+        let warnings = find_font_overrides(css);
+        let found: Vec<_> = warnings.iter().map(|w| (w.line, w.subject.as_str())).collect();
+        assert_eq!(found, vec![(2, "#clock"), (4, "#custom-weather,\n#tray")]);
+        assert_eq!(
+            warnings[0].message,
+            "Overrides the global font \"JetBrainsMono Nerd Font\" with \"Fira Sans\""
+        );
+    }
+
+    #[test]
+    fn test_no_global_font_means_no_overrides() {
+        let css = "window#waybar { background: black; }\n#clock { font-family: Hack; }";
+        assert!(find_font_overrides(css).is_empty());
+    }
+
+    #[test]
+    fn test_normalize_hex_colors_expands_shorthands() {
+        let css = "@define-color fg #fff;\n\
+                   #fab { color: #FfF; background: #1234; }\n\
+                   /* #abc stays */\n\
+                   #clock { border-color: #ff; }";
+
+        let result = normalize_hex_colors(css, true);
+        assert_eq!(
+            result.css,
+            "@define-color fg #ffffff;\n\
+             #fab { color: #FFffFF; background: #11223344; }\n\
+             /* #abc stays */\n\
+             #clock { border-color: #ff; }"
+        );
+
+        let fixes: Vec<_> = result.fixes.iter().map(|f| (f.line, f.subject.as_str())).collect();
+        assert_eq!(fixes, vec![(1, "#fff"), (2, "#FfF"), (2, "#1234"), (4, "#ff")]);
+        assert_eq!(result.fixes[3].message, "Invalid hex color: 2 digits (expected 3, 4, 6 or 8)");
+    }
+
+    #[test]
+    fn test_normalize_hex_colors_without_expansion() {
+        let css = "#battery { color: #fff; background: #12345; }";
+
+        let result = normalize_hex_colors(css, false);
+        assert_eq!(result.css, css);
+        assert_eq!(result.fixes.len(), 1);
+        assert_eq!(result.fixes[0].subject, "#12345");
+    }
+
+    #[test]
+    fn test_validate_css_accepts_valid_stylesheet() {
+        let css = "@define-color fg #cdd6f4;\n\
+            /* unbalanced { in a comment */\n\
+            * { font-family: \"JetBrains Mono; {\"; }\n\
+            #clock { color: alpha(@fg, 0.8); background: url(\"a:b.png\") }\n\
+            @keyframes blink { to { color: red; } }\n\
+            #battery:hover { margin: 0 }\n";
+
+        assert!(validate_css(css).is_ok());
+    }
+
+    #[test]
+    fn test_validate_css_reports_offending_line() {
+        let error = |css: &str| match validate_css(css) {
+            Err(AppError::Validation(message)) => message,
+            other => panic!("Expected a validation error, got {:?}", other),
+        };
+
+        assert_eq!(
+            error("#clock {\n  color: red;\n"),
+            "CSS syntax error on line 1: '{' is never closed"
+        );
+        assert_eq!(error("#clock { }\n}\n"), "CSS syntax error on line 2: unexpected '}'");
+        assert_eq!(
+            error("#clock {\n  color: alpha(@fg, 0.5;\n}"),
+            "CSS syntax error on line 2: '(' is never closed"
+        );
+        assert_eq!(
+            error("#clock {\n  color red;\n}"),
+            "CSS syntax error on line 2: declaration \"color red\" has no ':'"
+        );
+        assert_eq!(
+            error("#clock {\n  color: red\n  padding: 0 4px;\n}"),
+            "CSS syntax error on line 2: missing ';' after declaration"
+        );
+    }
+
+    #[test]
+    fn test_extract_color_definitions() {
+        let css = "@define-color bg #1e1e2e;\n/* @define-color old #000; */\n@define-color  overlay rgba(30, 30, 46, 0.9) ;\n@define-color accent @blue;\nwindow { color: @bg; }";
+
+        assert_eq!(
+            extract_color_definitions(css),
+            vec![
+                ColorDef { name: "bg".to_string(), value: "#1e1e2e".to_string(), line: 1 },
+                ColorDef {
+                    name: "overlay".to_string(),
+                    value: "rgba(30, 30, 46, 0.9)".to_string(),
+                    line: 3,
+                },
+                ColorDef { name: "accent".to_string(), value: "@blue".to_string(), line: 4 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_color_definition_rewrites_in_place() {
+        let css = "/* palette */\n@define-color bg #1e1e2e; /* base */\n@define-color fg #cdd6f4;\n";
+
+        assert_eq!(
+            set_color_definition(css, "bg", "rgba(0, 0, 0, 0.8)").unwrap(),
+            "/* palette */\n@define-color bg rgba(0, 0, 0, 0.8); /* base */\n@define-color fg #cdd6f4;\n"
+        );
+
+        // The last definition wins in GTK, so that's the one changed
+        let twice = "@define-color bg #111;\n@define-color bg #222;\n";
+        assert_eq!(
+            set_color_definition(twice, "bg", "#333").unwrap(),
+            "@define-color bg #111;\n@define-color bg #333;\n"
+        );
+
+        assert!(matches!(set_color_definition(css, "accent", "#fff"), Err(AppError::NotFound(_))));
+        assert!(matches!(set_color_definition(css, "bg", "red; x"), Err(AppError::Validation(_))));
+    }
+}
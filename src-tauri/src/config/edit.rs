@@ -0,0 +1,457 @@
+// ============================================================================
+// BULK CONFIG EDITS
+// ============================================================================
+//
+// Convenience operations that rewrite many module definitions at once.
+// They work on the parsed JSON value and return the updated config; the
+// frontend decides whether to save it.
+
+use crate::config::bars_mut;
+use crate::config::catalog::find_module;
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// Result of a bulk edit
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigEdit {
+    /// The updated config
+    pub config: Value,
+    /// Modules that were modified
+    pub changed: Vec<String>,
+}
+
+/// Result of relinking a script to a new path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScriptRelink {
+    /// The updated config
+    pub config: Value,
+    /// Number of command references rewritten
+    pub references: usize,
+    /// Modules that were modified
+    pub changed: Vec<String>,
+    /// Whether the script file was moved on disk
+    pub moved: bool,
+}
+
+/// Set `"tooltip": enabled` on every module definition that supports tooltips
+///
+/// Modules the catalog marks as tooltip-less (e.g. `tray`, `group/*`) and
+/// unknown module types are left untouched, as are modules already set to
+/// the requested value.
+pub fn set_all_tooltips(mut config: Value, enabled: bool) -> ConfigEdit {
+    let mut changed = Vec::new();
+
+    for bar in bars_mut(&mut config) {
+        for (name, module) in bar.iter_mut() {
+            let Some(module) = module.as_object_mut() else {
+                continue;
+            };
+
+            if !find_module(name).is_some_and(|spec| spec.tooltip) {
+                continue;
+            }
+
+            if module.get("tooltip").and_then(Value::as_bool) != Some(enabled) {
+                module.insert("tooltip".to_string(), Value::Bool(enabled));
+                changed.push(name.clone());
+            }
+        }
+    }
+
+    ConfigEdit { config, changed }
+}
+
+/// Apply `rewrite` to every string in a JSON value, recursively
+/// Returns true if any string was changed
+fn rewrite_strings(value: &mut Value, rewrite: &dyn Fn(&str) -> Option<String>) -> bool {
+    match value {
+        Value::String(text) => match rewrite(text) {
+            Some(new_text) if new_text != *text => {
+                *text = new_text;
+                true
+            }
+            _ => false,
+        },
+        Value::Array(items) => {
+            let mut changed = false;
+            for item in items {
+                changed |= rewrite_strings(item, rewrite);
+            }
+            changed
+        }
+        Value::Object(map) => {
+            let mut changed = false;
+            for item in map.values_mut() {
+                changed |= rewrite_strings(item, rewrite);
+            }
+            changed
+        }
+        _ => false,
+    }
+}
+
+/// Apply `rewrite` to every string of every top-level bar entry, recording
+/// which entries (modules or bar options) changed
+fn rewrite_config_strings(mut config: Value, rewrite: &dyn Fn(&str) -> Option<String>) -> ConfigEdit {
+    let mut changed = Vec::new();
+
+    for bar in bars_mut(&mut config) {
+        for (name, value) in bar.iter_mut() {
+            if rewrite_strings(value, rewrite) {
+                changed.push(name.clone());
+            }
+        }
+    }
+
+    ConfigEdit { config, changed }
+}
+
+/// Check if a character ends a path component boundary
+fn is_path_boundary(ch: Option<char>) -> bool {
+    match ch {
+        None => true,
+        Some(c) => c == '/' || c.is_whitespace() || "\"'`;:=&|)".contains(c),
+    }
+}
+
+/// Replace the home directory prefix with `~` wherever it starts a path
+/// (`/home/al/x` → `~/x`, but `/home/alice` is left alone for home `/home/al`)
+pub(crate) fn dehome(text: &str, home: &str) -> Option<String> {
+    let home = home.trim_end_matches('/');
+    if home.is_empty() || !text.contains(home) {
+        return None;
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find(home) {
+        let after = &rest[pos + home.len()..];
+        result.push_str(&rest[..pos]);
+        if is_path_boundary(after.chars().next()) {
+            result.push('~');
+        } else {
+            result.push_str(home);
+        }
+        rest = after;
+    }
+    result.push_str(rest);
+
+    Some(result)
+}
+
+/// Expand `~` back to the home directory where it starts a path
+/// (at the start of the string or after whitespace, a quote or `=`)
+fn rehome(text: &str, home: &str) -> Option<String> {
+    let home = home.trim_end_matches('/');
+    if home.is_empty() || !text.contains('~') {
+        return None;
+    }
+
+    let mut result = String::with_capacity(text.len() + home.len());
+    let mut previous: Option<char> = None;
+    let mut chars = text.chars().peekable();
+    while let Some(ch) = chars.next() {
+        let starts_path = previous.is_none_or(|p| p.is_whitespace() || "\"'`=:;(".contains(p));
+        if ch == '~' && starts_path && is_path_boundary(chars.peek().copied()) {
+            result.push_str(home);
+        } else {
+            result.push(ch);
+        }
+        previous = Some(ch);
+    }
+
+    Some(result)
+}
+
+/// Replace the user's home directory in every string value with `~`
+/// so the config can be shared without leaking the username
+pub fn export_portable(config: Value, home: &str) -> ConfigEdit {
+    rewrite_config_strings(config, &|text| dehome(text, home))
+}
+
+/// Expand `~` paths in every string value to the current user's home
+pub fn import_portable(config: Value, home: &str) -> ConfigEdit {
+    rewrite_config_strings(config, &|text| rehome(text, home))
+}
+
+/// Check if a module option holds a shell command (`exec`, `exec-if`, `on-*`)
+fn is_command_key(key: &str) -> bool {
+    key == "exec" || key == "exec-if" || key.starts_with("on-")
+}
+
+/// Replace every whole-path occurrence of `old` in a command with `new`
+/// Returns the rewritten command and the number of replacements
+fn replace_path(command: &str, old: &str, new: &str) -> (String, usize) {
+    let mut result = String::with_capacity(command.len());
+    let mut count = 0;
+    let mut rest = command;
+    let mut previous: Option<char> = None;
+
+    while let Some(pos) = rest.find(old) {
+        let before = rest[..pos].chars().next_back().or(previous);
+        let after = &rest[pos + old.len()..];
+        let starts_path = before.is_none_or(|c| c.is_whitespace() || "\"'`=:;(".contains(c));
+        let next = after.chars().next();
+        let ends_path = next != Some('/') && is_path_boundary(next);
+
+        result.push_str(&rest[..pos]);
+        if starts_path && ends_path {
+            result.push_str(new);
+            count += 1;
+        } else {
+            result.push_str(old);
+        }
+        previous = old.chars().next_back();
+        rest = after;
+    }
+    result.push_str(rest);
+
+    (result, count)
+}
+
+/// Point every `exec`, `exec-if` and action command referencing `old_path`
+/// at `new_path` instead
+///
+/// Only whole paths are replaced, so relinking `~/bin/up.sh` leaves
+/// `~/bin/up.sh.bak` alone. The file itself is not touched; see [`move_script`].
+pub fn relink_script(mut config: Value, old_path: &str, new_path: &str) -> ScriptRelink {
+    let mut references = 0;
+    let mut changed = Vec::new();
+
+    if !old_path.is_empty() {
+        for bar in bars_mut(&mut config) {
+            for (name, module) in bar.iter_mut() {
+                let Some(module) = module.as_object_mut() else {
+                    continue;
+                };
+
+                let mut module_changed = false;
+                for (key, value) in module.iter_mut() {
+                    let Value::String(command) = value else {
+                        continue;
+                    };
+                    if !is_command_key(key) {
+                        continue;
+                    }
+                    let (rewritten, count) = replace_path(command, old_path, new_path);
+                    if count > 0 {
+                        *command = rewritten;
+                        references += count;
+                        module_changed = true;
+                    }
+                }
+                if module_changed {
+                    changed.push(name.clone());
+                }
+            }
+        }
+    }
+
+    ScriptRelink {
+        config,
+        references,
+        changed,
+        moved: false,
+    }
+}
+
+/// Resolve a script path as written in the config to a filesystem path
+fn script_location(path: &str, home: &str) -> PathBuf {
+    PathBuf::from(rehome(path, home).unwrap_or_else(|| path.to_string()))
+}
+
+/// Move a script from `old_path` to `new_path`, creating missing parent
+/// directories
+///
+/// Paths may start with `~`. Returns false if there is no file at
+/// `old_path`; refuses to overwrite an existing file at `new_path`.
+pub fn move_script(old_path: &str, new_path: &str, home: &str) -> Result<bool> {
+    let old = script_location(old_path, home);
+    let new = script_location(new_path, home);
+
+    if new.exists() {
+        return Err(AppError::AlreadyExists(format!(
+            "Cannot move script onto existing file {}",
+            new.display()
+        )));
+    }
+    if !old.is_file() {
+        return Ok(false);
+    }
+
+    if let Some(parent) = new.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::rename(&old, &new)?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_disable_all_tooltips() {
+        let config = json!({
+            "modules-right": ["clock", "battery", "tray"],
+            "clock": { "format": "{:%H:%M}" },
+            "battery": { "tooltip": true },
+            "tray": { "spacing": 10 },
+            "group/hardware": { "modules": ["cpu"] },
+            "custom/weather": { "exec": "weather.sh" }
+        });
+
+        let edit = set_all_tooltips(config, false);
+
+        let mut changed = edit.changed.clone();
+        changed.sort();
+        assert_eq!(changed, vec!["battery", "clock", "custom/weather"]);
+        assert_eq!(edit.config["clock"]["tooltip"], false);
+        assert_eq!(edit.config["clock"]["format"], "{:%H:%M}");
+        assert!(edit.config["tray"].get("tooltip").is_none());
+        assert!(edit.config["group/hardware"].get("tooltip").is_none());
+        // Bar-level arrays are not module definitions
+        assert!(edit.config["modules-right"].is_array());
+    }
+
+    #[test]
+    fn test_unchanged_modules_are_not_reported() {
+        let config = json!({
+            "clock": { "tooltip": false },
+            "cpu": {}
+        });
+
+        let edit = set_all_tooltips(config, false);
+        assert_eq!(edit.changed, vec!["cpu"]);
+    }
+
+    #[test]
+    fn test_enable_tooltips_multi_bar() {
+        let config = json!([
+            { "clock": { "tooltip": false } },
+            { "memory#ram": {} }
+        ]);
+
+        let edit = set_all_tooltips(config, true);
+        assert_eq!(edit.changed, vec!["clock", "memory#ram"]);
+        assert_eq!(edit.config[0]["clock"]["tooltip"], true);
+        assert_eq!(edit.config[1]["memory#ram"]["tooltip"], true);
+    }
+
+    #[test]
+    fn test_dehome_respects_path_boundaries() {
+        let home = "/home/al";
+        assert_eq!(dehome("/home/al/bin/x.sh", home).unwrap(), "~/bin/x.sh");
+        assert_eq!(dehome("/home/al", home).unwrap(), "~");
+        assert_eq!(dehome("/home/alice/x.sh", home).unwrap(), "/home/alice/x.sh");
+        assert_eq!(
+            dehome("sh -c '/home/al/a.sh && /home/al/b.sh'", home).unwrap(),
+            "sh -c '~/a.sh && ~/b.sh'"
+        );
+    }
+
+    #[test]
+    fn test_rehome_only_expands_path_tildes() {
+        let home = "/home/bob";
+        assert_eq!(rehome("~/bin/x.sh", home).unwrap(), "/home/bob/bin/x.sh");
+        assert_eq!(rehome("a ~/x b", home).unwrap(), "a /home/bob/x b");
+        assert_eq!(rehome("~user/x", home).unwrap(), "~user/x");
+        assert_eq!(rehome("approx~/x", home).unwrap(), "approx~/x");
+    }
+
+    #[test]
+    fn test_portable_round_trip() {
+        let config = json!({
+            "modules-right": ["custom/updates"],
+            "custom/updates": {
+                "exec": "/home/al/.config/waybar/scripts/updates.sh",
+                "on-click": "kitty -e /home/al/bin/update",
+                "format": "{} updates"
+            },
+            "clock": { "format": "{:%H:%M}" }
+        });
+
+        let exported = export_portable(config, "/home/al/");
+        assert_eq!(exported.changed, vec!["custom/updates"]);
+        assert_eq!(
+            exported.config["custom/updates"]["exec"],
+            "~/.config/waybar/scripts/updates.sh"
+        );
+        assert_eq!(exported.config["custom/updates"]["on-click"], "kitty -e ~/bin/update");
+        assert!(!exported.config.to_string().contains("/home/al"));
+
+        let imported = import_portable(exported.config, "/home/bob");
+        assert_eq!(imported.changed, vec!["custom/updates"]);
+        assert_eq!(
+            imported.config["custom/updates"]["exec"],
+            "/home/bob/.config/waybar/scripts/updates.sh"
+        );
+        assert_eq!(imported.config["custom/updates"]["on-click"], "kitty -e /home/bob/bin/update");
+    }
+
+    #[test]
+    fn test_relink_script_referenced_by_two_modules() {
+        let config = json!({
+            "modules-right": ["custom/updates", "custom/updates#count"],
+            "custom/updates": {
+                "exec": "~/.config/waybar/updates.sh --icon",
+                "exec-if": "test -x ~/.config/waybar/updates.sh",
+                "on-click": "kitty -e ~/.config/waybar/updates.sh.bak"
+            },
+            "custom/updates#count": {
+                "exec": "~/.config/waybar/updates.sh --count",
+                "format": "~/.config/waybar/updates.sh"
+            },
+            "clock": { "format": "{:%H:%M}" }
+        });
+
+        let relink = relink_script(
+            config,
+            "~/.config/waybar/updates.sh",
+            "~/.config/waybar/scripts/updates.sh",
+        );
+
+        assert_eq!(relink.references, 3);
+        assert_eq!(relink.changed, vec!["custom/updates", "custom/updates#count"]);
+        assert_eq!(
+            relink.config["custom/updates"]["exec"],
+            "~/.config/waybar/scripts/updates.sh --icon"
+        );
+        assert_eq!(
+            relink.config["custom/updates"]["exec-if"],
+            "test -x ~/.config/waybar/scripts/updates.sh"
+        );
+        assert_eq!(
+            relink.config["custom/updates#count"]["exec"],
+            "~/.config/waybar/scripts/updates.sh --count"
+        );
+        // Other files and non-command options are left alone
+        assert_eq!(
+            relink.config["custom/updates"]["on-click"],
+            "kitty -e ~/.config/waybar/updates.sh.bak"
+        );
+        assert_eq!(relink.config["custom/updates#count"]["format"], "~/.config/waybar/updates.sh");
+    }
+
+    #[test]
+    fn test_move_script() {
+        let dir = tempfile::tempdir().unwrap();
+        let home = dir.path().to_str().unwrap();
+        std::fs::write(dir.path().join("up.sh"), "#!/bin/sh").unwrap();
+        std::fs::write(dir.path().join("taken.sh"), "#!/bin/sh").unwrap();
+
+        assert!(move_script("~/up.sh", "~/taken.sh", home).is_err());
+        assert!(dir.path().join("up.sh").exists());
+
+        assert!(move_script("~/up.sh", "~/scripts/up.sh", home).unwrap());
+        assert!(!dir.path().join("up.sh").exists());
+        assert!(dir.path().join("scripts/up.sh").exists());
+
+        // Nothing left to move
+        assert!(!move_script("~/up.sh", "~/other.sh", home).unwrap());
+    }
+}
@@ -0,0 +1,165 @@
+// ============================================================================
+// FILE ENCODING CHECKS
+// ============================================================================
+//
+// Config and style files must be UTF-8. When they aren't, reading them as a
+// string fails with an opaque error; this inspects the raw bytes instead.
+
+use crate::error::Result;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Byte order marks, longest first so UTF-32 LE isn't mistaken for UTF-16 LE
+const BOMS: [(&[u8], &str); 5] = [
+    (&[0x00, 0x00, 0xFE, 0xFF], "UTF-32BE"),
+    (&[0xFF, 0xFE, 0x00, 0x00], "UTF-32LE"),
+    (&[0xEF, 0xBB, 0xBF], "UTF-8"),
+    (&[0xFE, 0xFF], "UTF-16BE"),
+    (&[0xFF, 0xFE], "UTF-16LE"),
+];
+
+/// What was found about a file's encoding
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EncodingReport {
+    /// Encoding named by a byte order mark, if the file starts with one
+    pub bom: Option<String>,
+    /// Whether the content (after a UTF-8 BOM) is valid UTF-8
+    pub valid_utf8: bool,
+    /// Byte offset of the first invalid UTF-8 sequence
+    pub invalid_offset: Option<usize>,
+    /// Best guess at the file's encoding
+    pub likely_encoding: String,
+    /// Actionable explanation when something is wrong
+    pub message: Option<String>,
+}
+
+/// Guess the encoding of bytes that aren't valid UTF-8 and have no BOM
+fn guess_legacy_encoding(bytes: &[u8]) -> &'static str {
+    let zeros_at = |parity: usize| {
+        bytes.iter().skip(parity).step_by(2).filter(|b| **b == 0).count()
+    };
+    let half = bytes.len() / 2;
+
+    // ASCII text in UTF-16 has a zero in every other byte
+    if half > 0 && zeros_at(1) * 2 > half {
+        "UTF-16LE"
+    } else if half > 0 && zeros_at(0) * 2 > half {
+        "UTF-16BE"
+    } else {
+        "Windows-1252"
+    }
+}
+
+/// Inspect raw bytes for a BOM and invalid UTF-8
+pub fn check_encoding_bytes(bytes: &[u8]) -> EncodingReport {
+    let bom = BOMS.iter().find(|(mark, _)| bytes.starts_with(mark));
+
+    if let Some((_, encoding)) = bom.filter(|(_, encoding)| *encoding != "UTF-8") {
+        return EncodingReport {
+            bom: Some(encoding.to_string()),
+            valid_utf8: false,
+            invalid_offset: None,
+            likely_encoding: encoding.to_string(),
+            message: Some(format!(
+                "File is encoded as {}; re-save it as UTF-8 without a BOM",
+                encoding
+            )),
+        };
+    }
+
+    let skip = bom.map(|(mark, _)| mark.len()).unwrap_or(0);
+    match std::str::from_utf8(&bytes[skip..]) {
+        Ok(_) => EncodingReport {
+            bom: bom.map(|_| "UTF-8".to_string()),
+            valid_utf8: true,
+            invalid_offset: None,
+            likely_encoding: "UTF-8".to_string(),
+            message: bom.map(|_| {
+                "File starts with a UTF-8 byte order mark, which Waybar's parser may reject".to_string()
+            }),
+        },
+        Err(e) => {
+            let offset = skip + e.valid_up_to();
+            let line = bytes[..offset].iter().filter(|b| **b == b'\n').count() + 1;
+            let line_start = bytes[..offset]
+                .iter()
+                .rposition(|b| *b == b'\n')
+                .map(|pos| pos + 1)
+                .unwrap_or(0);
+            let likely_encoding = guess_legacy_encoding(bytes);
+
+            EncodingReport {
+                bom: bom.map(|_| "UTF-8".to_string()),
+                valid_utf8: false,
+                invalid_offset: Some(offset),
+                likely_encoding: likely_encoding.to_string(),
+                message: Some(format!(
+                    "Invalid UTF-8 byte 0x{:02X} at offset {} (line {}, column {}); the file looks like {}, re-save it as UTF-8",
+                    bytes[offset],
+                    offset,
+                    line,
+                    offset - line_start + 1,
+                    likely_encoding
+                )),
+            }
+        }
+    }
+}
+
+/// Inspect a file's raw bytes for a BOM and invalid UTF-8
+pub fn check_encoding(path: &Path) -> Result<EncodingReport> {
+    Ok(check_encoding_bytes(&fs::read(path)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn report_for(bytes: &[u8]) -> EncodingReport {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.jsonc");
+        fs::write(&path, bytes).unwrap();
+        check_encoding(&path).unwrap()
+    }
+
+    #[test]
+    fn test_valid_utf8() {
+        let report = report_for("{ \"format\": \"\u{f017} {:%H:%M} – ñ\" }".as_bytes());
+        assert!(report.valid_utf8);
+        assert!(report.bom.is_none());
+        assert!(report.message.is_none());
+        assert_eq!(report.likely_encoding, "UTF-8");
+    }
+
+    #[test]
+    fn test_utf8_bom() {
+        let report = report_for(b"\xEF\xBB\xBF{ \"height\": 30 }");
+        assert!(report.valid_utf8);
+        assert_eq!(report.bom.as_deref(), Some("UTF-8"));
+        assert!(report.message.unwrap().contains("byte order mark"));
+    }
+
+    #[test]
+    fn test_invalid_byte() {
+        // "café" saved as Windows-1252 on the second line
+        let report = report_for(b"{\n  \"name\": \"caf\xE9\"\n}");
+        assert!(!report.valid_utf8);
+        assert_eq!(report.invalid_offset, Some(16));
+        assert_eq!(report.likely_encoding, "Windows-1252");
+        assert!(report.message.unwrap().contains("line 2, column 15"));
+    }
+
+    #[test]
+    fn test_utf16_bom() {
+        let report = report_for(b"\xFF\xFE{\x00}\x00");
+        assert_eq!(report.bom.as_deref(), Some("UTF-16LE"));
+        assert!(!report.valid_utf8);
+    }
+
+    #[test]
+    fn test_utf16_without_bom() {
+        assert_eq!(guess_legacy_encoding(b"{\x00\"\x00a\x00\"\x00}\x00"), "UTF-16LE");
+    }
+}
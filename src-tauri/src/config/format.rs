@@ -0,0 +1,235 @@
+// ============================================================================
+// WHITESPACE FORMATTING
+// ============================================================================
+//
+// Whitespace-only reformatting of JSONC text. Unlike a serde round-trip this
+// keeps comments, key order and line breaks exactly as written.
+
+use crate::config::parser::{parse_jsonc, tokenize_jsonc, TokenKind};
+use crate::error::{AppError, Result};
+
+/// Indent width used when none is given
+pub const DEFAULT_INDENT_WIDTH: usize = 4;
+
+/// Largest accepted indent width
+const MAX_INDENT_WIDTH: usize = 8;
+
+/// Where a line sits in the JSONC structure
+struct LineState {
+    /// Nesting depth at the start of the line
+    depth: usize,
+    /// Whether the line starts inside a string or block comment
+    starts_inside: bool,
+    /// Whether the line ends inside a string
+    ends_in_string: bool,
+}
+
+/// Get the [`LineState`] of every line (split on `\n`)
+fn line_states(content: &str) -> Vec<LineState> {
+    let tokens = tokenize_jsonc(content);
+    // The token containing `offset`, if one starts before it and ends after it
+    let containing = |from: usize, offset: usize| {
+        tokens[from..]
+            .iter()
+            .take_while(|token| token.span.start < offset)
+            .find(|token| offset < token.span.end)
+    };
+
+    let mut states = Vec::new();
+    let mut depth: usize = 0;
+    let mut next = 0;
+    let mut start = 0;
+
+    for line in content.split('\n') {
+        // Tokens wholly before the line set its depth
+        while next < tokens.len() && tokens[next].span.end <= start {
+            match tokens[next].kind {
+                TokenKind::Punct(b'{' | b'[') => depth += 1,
+                TokenKind::Punct(b'}' | b']') => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+            next += 1;
+        }
+
+        let end = start + line.len();
+        states.push(LineState {
+            depth,
+            starts_inside: containing(next, start)
+                .is_some_and(|token| matches!(token.kind, TokenKind::String | TokenKind::Comment)),
+            ends_in_string: containing(next, end).is_some_and(|token| token.kind == TokenKind::String),
+        });
+        start = end + 1;
+    }
+
+    states
+}
+
+/// Recompute the leading whitespace of every line from its nesting depth
+///
+/// Only indentation changes: comments, key order, line breaks and trailing
+/// text are kept. Lines continuing a block comment or a string are left
+/// untouched and blank lines lose their whitespace. The content must parse
+/// as JSONC.
+pub fn reindent(content: &str, indent_width: usize) -> Result<String> {
+    if indent_width > MAX_INDENT_WIDTH {
+        return Err(AppError::Validation(format!(
+            "Indent width must be at most {}",
+            MAX_INDENT_WIDTH
+        )));
+    }
+    parse_jsonc(content)?;
+
+    let indent = " ".repeat(indent_width);
+    let mut lines = Vec::new();
+
+    for (line, state) in content.split('\n').zip(line_states(content)) {
+        if state.starts_inside {
+            lines.push(line.to_string());
+        } else {
+            let trimmed = line.trim_start();
+            if trimmed.trim_end().is_empty() {
+                lines.push(trimmed.to_string());
+            } else {
+                // A line opening with closers (`}]`) sits one level out
+                let closes = trimmed.starts_with(['}', ']']);
+                let level = state.depth.saturating_sub(usize::from(closes));
+                lines.push(format!("{}{}", indent.repeat(level), trimmed));
+            }
+        }
+    }
+
+    Ok(lines.join("\n"))
+}
+
+/// Split a line into its content and its trailing spaces/tabs, keeping a
+/// `\r` line ending with the content side
+fn split_trailing_whitespace(line: &str) -> (&str, &str, &str) {
+    let (body, ending) = match line.strip_suffix('\r') {
+        Some(body) => (body, "\r"),
+        None => (line, ""),
+    };
+    let content = body.trim_end_matches([' ', '\t']);
+    (content, &body[content.len()..], ending)
+}
+
+/// Visit each line with whether its trailing whitespace can be removed
+///
+/// Whitespace at the end of a line that's still inside a string literal is
+/// part of the string's value and is never reported.
+fn for_each_line<'a>(content: &'a str, mut visit: impl FnMut(&'a str, bool)) {
+    for (line, state) in content.split('\n').zip(line_states(content)) {
+        visit(line, !state.ends_in_string);
+    }
+}
+
+/// List the 1-based lines ending in spaces or tabs outside of strings
+pub fn find_trailing_whitespace(content: &str) -> Vec<usize> {
+    let mut lines = Vec::new();
+    let mut number = 0;
+    for_each_line(content, |line, trimmable| {
+        number += 1;
+        if trimmable && !split_trailing_whitespace(line).1.is_empty() {
+            lines.push(number);
+        }
+    });
+    lines
+}
+
+/// Remove trailing spaces and tabs from every line, except where a string
+/// literal continues onto the next line
+///
+/// Line endings (including `\r\n`) are kept.
+pub fn trim_trailing_whitespace(content: &str) -> String {
+    let mut lines = Vec::new();
+    for_each_line(content, |line, trimmable| {
+        if trimmable {
+            let (text, _, ending) = split_trailing_whitespace(line);
+            lines.push(format!("{}{}", text, ending));
+        } else {
+            lines.push(line.to_string());
+        }
+    });
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reindent_preserves_comments_and_order() {
+        let input = concat!(
+            "// Main bar\n",
+            "{\n",
+            "      \"layer\": \"top\", // keep me\n",
+            "\"modules-left\": [\n",
+            "\t\"clock\",\n",
+            "  \"tray\"\n",
+            "          ],\n",
+            "  /* multi\n",
+            "     line */\n",
+            "        \"clock\": {\n",
+            "\"format\": \"{:%H:%M} }\" // brace in a string\n",
+            "   }\n",
+            "}\n",
+        );
+
+        let expected = concat!(
+            "// Main bar\n",
+            "{\n",
+            "  \"layer\": \"top\", // keep me\n",
+            "  \"modules-left\": [\n",
+            "    \"clock\",\n",
+            "    \"tray\"\n",
+            "  ],\n",
+            "  /* multi\n",
+            "     line */\n",
+            "  \"clock\": {\n",
+            "    \"format\": \"{:%H:%M} }\" // brace in a string\n",
+            "  }\n",
+            "}\n",
+        );
+
+        let output = reindent(input, 2).unwrap();
+        assert_eq!(output, expected);
+        assert_eq!(parse_jsonc(&output).unwrap(), parse_jsonc(input).unwrap());
+    }
+
+    #[test]
+    fn test_reindent_multi_bar_closers() {
+        let input = "[\n{\n\"height\": 30\n}, {\n\"height\": 20\n}]\n";
+        let expected = "[\n    {\n        \"height\": 30\n    }, {\n        \"height\": 20\n    }]\n";
+
+        assert_eq!(reindent(input, DEFAULT_INDENT_WIDTH).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_reindent_rejects_invalid_content() {
+        assert!(matches!(reindent("{ \"a\": ", 4), Err(AppError::Parse(_))));
+        assert!(matches!(reindent("{}", 20), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_trim_trailing_whitespace() {
+        let input = concat!(
+            "{  \n",
+            "    \"format\": \"{:%H:%M} \", \t\r\n",
+            "    // comment   \n",
+            "    \"label\": \"multi \n",
+            "line \"\n",
+            "}",
+        );
+        let expected = concat!(
+            "{\n",
+            "    \"format\": \"{:%H:%M} \",\r\n",
+            "    // comment\n",
+            "    \"label\": \"multi \n",
+            "line \"\n",
+            "}",
+        );
+
+        assert_eq!(find_trailing_whitespace(input), vec![1, 2, 3]);
+        assert_eq!(trim_trailing_whitespace(input), expected);
+        assert!(find_trailing_whitespace(expected).is_empty());
+    }
+}
@@ -0,0 +1,406 @@
+// ============================================================================
+// GIT INTEGRATION
+// ============================================================================
+//
+// Versioned history for dotfiles users whose Waybar config directory lives in
+// a git repository. Shells out to the `git` binary.
+
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use std::process::{Command, Output};
+
+/// Config files tracked by the git integration, relative to the config dir
+pub const TRACKED_FILES: [&str; 3] = ["config.jsonc", "config", "style.css"];
+
+/// Default message for commits created without one
+const DEFAULT_COMMIT_MESSAGE: &str = "Update Waybar configuration";
+
+/// Field separator used in `git log --format` output (ASCII unit separator)
+const LOG_FIELD_SEPARATOR: char = '\u{1f}';
+
+/// A commit touching the config files
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GitCommit {
+    /// Full commit hash
+    pub hash: String,
+    /// Author name
+    pub author: String,
+    /// Author date (ISO 8601)
+    pub date: String,
+    /// Commit subject line
+    pub message: String,
+}
+
+/// Run `git -C <dir> <args>` and return its raw output
+fn git(dir: &str, args: &[&str]) -> Result<Output> {
+    Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| AppError::Internal(format!("Failed to execute git: {}", e)))
+}
+
+/// Run git and return trimmed stdout, mapping a non-zero exit to an error
+fn git_stdout(dir: &str, args: &[&str]) -> Result<String> {
+    let output = git(dir, args)?;
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    } else {
+        Err(AppError::Internal(format!(
+            "git {} failed: {}",
+            args.first().unwrap_or(&""),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/// Check if a directory is inside a git work tree
+pub fn is_git_repo(dir: &str) -> bool {
+    git(dir, &["rev-parse", "--is-inside-work-tree"])
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+/// Config files that exist in the config directory
+fn existing_tracked_files(config_dir: &str) -> Vec<&'static str> {
+    TRACKED_FILES
+        .into_iter()
+        .filter(|name| Path::new(config_dir).join(name).is_file())
+        .collect()
+}
+
+/// Stage and commit the config files in `config_dir`
+///
+/// Only the config and style files are committed, even if other changes are
+/// staged in the repository. Returns the new commit hash.
+pub fn commit_config(config_dir: &str, message: Option<&str>) -> Result<String> {
+    if !is_git_repo(config_dir) {
+        return Err(AppError::Validation(format!(
+            "Config directory is not a git repository: {}",
+            config_dir
+        )));
+    }
+
+    let files = existing_tracked_files(config_dir);
+    if files.is_empty() {
+        return Err(AppError::NotFound(format!(
+            "No config files to commit in: {}",
+            config_dir
+        )));
+    }
+
+    let mut add_args = vec!["add", "--"];
+    add_args.extend(&files);
+    git_stdout(config_dir, &add_args)?;
+
+    let message = message
+        .map(str::trim)
+        .filter(|m| !m.is_empty())
+        .unwrap_or(DEFAULT_COMMIT_MESSAGE);
+
+    let mut commit_args = vec!["commit", "-m", message, "--"];
+    commit_args.extend(&files);
+    let output = git(config_dir, &commit_args)?;
+
+    if !output.status.success() {
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        if stdout.contains("nothing to commit") || stdout.contains("no changes added") {
+            return Err(AppError::Validation("No config changes to commit".to_string()));
+        }
+        return Err(AppError::Internal(format!(
+            "git commit failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    git_stdout(config_dir, &["rev-parse", "HEAD"])
+}
+
+/// List the last `limit` commits touching the config files, newest first
+/// Returns an empty list when the directory isn't a git repository or has no history yet
+pub fn config_git_log(config_dir: &str, limit: usize) -> Result<Vec<GitCommit>> {
+    if !is_git_repo(config_dir) {
+        return Ok(Vec::new());
+    }
+
+    let limit = format!("-n{}", limit);
+    let mut args = vec!["log", limit.as_str(), "--format=%H%x1f%an%x1f%aI%x1f%s", "--"];
+    args.extend(TRACKED_FILES);
+    let output = git(config_dir, &args)?;
+
+    // `git log` fails on a repository without commits
+    if !output.status.success() {
+        return Ok(Vec::new());
+    }
+
+    Ok(parse_git_log(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Restore a config file from a commit
+///
+/// Retrieves `<commit>:<file>` with `git show`, validates it, backs up the
+/// current file and writes the retrieved content. `file` must be one of
+/// [`TRACKED_FILES`]. Returns the restored file path.
+pub fn restore_from_commit(config_dir: &str, commit: &str, file: &str) -> Result<String> {
+    if !TRACKED_FILES.contains(&file) {
+        return Err(AppError::Validation(format!(
+            "Not a config file: {} (expected one of: {})",
+            file,
+            TRACKED_FILES.join(", ")
+        )));
+    }
+
+    // Reject anything git could interpret as an option or a path expression
+    if commit.is_empty()
+        || commit.starts_with('-')
+        || !commit.chars().all(|c| c.is_ascii_alphanumeric() || "~^._/-".contains(c))
+    {
+        return Err(AppError::Validation(format!("Invalid commit reference: {}", commit)));
+    }
+
+    if !is_git_repo(config_dir) {
+        return Err(AppError::Validation(format!(
+            "Config directory is not a git repository: {}",
+            config_dir
+        )));
+    }
+
+    // `./` makes the path relative to the config dir rather than the repo root
+    let object = format!("{}:./{}", commit, file);
+    let output = git(config_dir, &["show", &object])?;
+    if !output.status.success() {
+        return Err(AppError::NotFound(format!(
+            "{} does not exist at commit {}: {}",
+            file,
+            commit,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    let content = String::from_utf8(output.stdout)
+        .map_err(|_| AppError::Validation(format!("{} at {} is not valid UTF-8", file, commit)))?;
+
+    if file.ends_with(".css") {
+        if content.trim().is_empty() {
+            return Err(AppError::Validation("CSS content cannot be empty".to_string()));
+        }
+    } else {
+        crate::config::parser::parse_jsonc(&content)?;
+    }
+
+    let target = Path::new(config_dir).join(file);
+    let target = target
+        .to_str()
+        .ok_or_else(|| AppError::Internal("Invalid UTF-8 in path".to_string()))?;
+    crate::config::writer::write_config_file(target, &content)?;
+
+    Ok(target.to_string())
+}
+
+/// Parse `git log --format=%H%x1f%an%x1f%aI%x1f%s` output
+fn parse_git_log(output: &str) -> Vec<GitCommit> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(4, LOG_FIELD_SEPARATOR);
+            Some(GitCommit {
+                hash: fields.next()?.to_string(),
+                author: fields.next()?.to_string(),
+                date: fields.next()?.to_string(),
+                message: fields.next()?.to_string(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Create a temp git repo with a local identity so commits work in CI
+    fn init_repo() -> TempDir {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_str().unwrap();
+        git_stdout(dir, &["init", "-q"]).unwrap();
+        git_stdout(dir, &["config", "user.name", "Test"]).unwrap();
+        git_stdout(dir, &["config", "user.email", "test@example.com"]).unwrap();
+        git_stdout(dir, &["config", "commit.gpgsign", "false"]).unwrap();
+        temp_dir
+    }
+
+    #[test]
+    fn test_commit_config_creates_commit() {
+        let temp_dir = init_repo();
+        let dir = temp_dir.path().to_str().unwrap();
+        fs::write(temp_dir.path().join("config.jsonc"), "{}").unwrap();
+        fs::write(temp_dir.path().join("style.css"), "* {}").unwrap();
+        fs::write(temp_dir.path().join("notes.txt"), "untracked").unwrap();
+
+        let hash = commit_config(dir, Some("Initial bar")).unwrap();
+
+        assert_eq!(hash.len(), 40);
+        assert_eq!(git_stdout(dir, &["rev-parse", "HEAD"]).unwrap(), hash);
+        assert_eq!(git_stdout(dir, &["log", "-1", "--format=%s"]).unwrap(), "Initial bar");
+
+        let committed = git_stdout(dir, &["show", "--name-only", "--format=", "HEAD"]).unwrap();
+        assert!(committed.contains("config.jsonc"));
+        assert!(committed.contains("style.css"));
+        assert!(!committed.contains("notes.txt"));
+    }
+
+    #[test]
+    fn test_commit_config_default_message() {
+        let temp_dir = init_repo();
+        let dir = temp_dir.path().to_str().unwrap();
+        fs::write(temp_dir.path().join("config.jsonc"), "{}").unwrap();
+
+        commit_config(dir, None).unwrap();
+
+        let subject = git_stdout(dir, &["log", "-1", "--format=%s"]).unwrap();
+        assert_eq!(subject, DEFAULT_COMMIT_MESSAGE);
+    }
+
+    #[test]
+    fn test_commit_config_nothing_to_commit() {
+        let temp_dir = init_repo();
+        let dir = temp_dir.path().to_str().unwrap();
+        fs::write(temp_dir.path().join("config.jsonc"), "{}").unwrap();
+        commit_config(dir, None).unwrap();
+
+        let result = commit_config(dir, None);
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_commit_config_not_a_repo() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("config.jsonc"), "{}").unwrap();
+
+        let result = commit_config(temp_dir.path().to_str().unwrap(), None);
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_parse_git_log() {
+        let output = "abc123\u{1f}Jane Doe\u{1f}2024-04-05T10:00:00+02:00\u{1f}Tweak clock: add seconds\n";
+        let commits = parse_git_log(output);
+
+        assert_eq!(
+            commits,
+            vec![GitCommit {
+                hash: "abc123".to_string(),
+                author: "Jane Doe".to_string(),
+                date: "2024-04-05T10:00:00+02:00".to_string(),
+                message: "Tweak clock: add seconds".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_config_git_log() {
+        let temp_dir = init_repo();
+        let dir = temp_dir.path().to_str().unwrap();
+
+        fs::write(temp_dir.path().join("config.jsonc"), "{}").unwrap();
+        let first = commit_config(dir, Some("First")).unwrap();
+
+        // A commit that doesn't touch the config files must be skipped
+        fs::write(temp_dir.path().join("README.md"), "dotfiles").unwrap();
+        git_stdout(dir, &["add", "README.md"]).unwrap();
+        git_stdout(dir, &["commit", "-q", "-m", "Readme"]).unwrap();
+
+        fs::write(temp_dir.path().join("style.css"), "* {}").unwrap();
+        let second = commit_config(dir, Some("Second")).unwrap();
+
+        let log = config_git_log(dir, 10).unwrap();
+        assert_eq!(log.len(), 2);
+        assert_eq!(log[0].hash, second);
+        assert_eq!(log[0].message, "Second");
+        assert_eq!(log[0].author, "Test");
+        assert!(!log[0].date.is_empty());
+        assert_eq!(log[1].hash, first);
+
+        assert_eq!(config_git_log(dir, 1).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_config_git_log_empty_cases() {
+        let plain_dir = TempDir::new().unwrap();
+        assert!(config_git_log(plain_dir.path().to_str().unwrap(), 10).unwrap().is_empty());
+
+        let empty_repo = init_repo();
+        assert!(config_git_log(empty_repo.path().to_str().unwrap(), 10).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_restore_from_commit() {
+        let temp_dir = init_repo();
+        let dir = temp_dir.path().to_str().unwrap();
+        let config_path = temp_dir.path().join("config.jsonc");
+
+        fs::write(&config_path, r#"{"modules-left": ["clock"]}"#).unwrap();
+        let first = commit_config(dir, Some("First")).unwrap();
+        fs::write(&config_path, r#"{"modules-left": ["battery"]}"#).unwrap();
+        commit_config(dir, Some("Second")).unwrap();
+
+        restore_from_commit(dir, &first, "config.jsonc").unwrap();
+
+        let restored = fs::read_to_string(&config_path).unwrap();
+        assert!(restored.contains("clock"));
+
+        // The replaced version must have been backed up
+        let backups: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_str().unwrap().contains(".backup."))
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert!(fs::read_to_string(backups[0].path()).unwrap().contains("battery"));
+    }
+
+    #[test]
+    fn test_restore_from_commit_missing_file() {
+        let temp_dir = init_repo();
+        let dir = temp_dir.path().to_str().unwrap();
+        fs::write(temp_dir.path().join("config.jsonc"), "{}").unwrap();
+        let hash = commit_config(dir, None).unwrap();
+
+        let result = restore_from_commit(dir, &hash, "style.css");
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_restore_from_commit_rejects_invalid_content() {
+        let temp_dir = init_repo();
+        let dir = temp_dir.path().to_str().unwrap();
+        let config_path = temp_dir.path().join("config.jsonc");
+
+        fs::write(&config_path, "{ broken").unwrap();
+        let broken = commit_config(dir, None).unwrap();
+        fs::write(&config_path, "{}").unwrap();
+        commit_config(dir, None).unwrap();
+
+        let result = restore_from_commit(dir, &broken, "config.jsonc");
+        assert!(matches!(result, Err(AppError::Parse(_))));
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), "{}");
+    }
+
+    #[test]
+    fn test_restore_from_commit_rejects_bad_arguments() {
+        let temp_dir = init_repo();
+        let dir = temp_dir.path().to_str().unwrap();
+
+        assert!(matches!(
+            restore_from_commit(dir, "HEAD", "../secrets"),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            restore_from_commit(dir, "--output=/tmp/x", "config.jsonc"),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -0,0 +1,574 @@
+// ============================================================================
+// INCLUDE RESOLUTION
+// ============================================================================
+//
+// Waybar's `include` option pulls module definitions from other files. Each
+// entry may be a glob (`~/.config/waybar/modules/*.jsonc`); matches are
+// merged in sorted order. Objects are merged key by key, with values already
+// present in the including bar taking precedence.
+
+use crate::config::parser::parse_jsonc;
+use crate::config::writer::format_json;
+use crate::config::{bars, bars_mut, module_definitions};
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A config with its includes merged in
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncludeResolution {
+    /// Config with included definitions merged into each bar
+    pub config: Value,
+    /// Included files in merge order
+    pub files: Vec<String>,
+    /// Keys defined more than once, and which definition was used
+    pub conflicts: Vec<IncludeConflict>,
+    /// Files matched again after being merged already, and skipped
+    pub duplicates: Vec<DuplicateInclude>,
+}
+
+/// A file included more than once into the same bar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateInclude {
+    /// The repeated file
+    pub file: String,
+    /// The `include` entry that matched it again
+    pub pattern: String,
+}
+
+/// A value defined both by an including file and an included one (or by two
+/// included files)
+///
+/// Objects defined in both are merged, so only differing non-object values
+/// conflict.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IncludeConflict {
+    /// Config key, with nested keys joined by `.` (e.g. `clock.format`)
+    pub key: String,
+    /// File whose value was used
+    pub kept: String,
+    /// File whose value was ignored
+    pub ignored: String,
+}
+
+/// A config with all includes inlined
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlattenedConfig {
+    /// Formatted, self-contained config
+    pub content: String,
+    /// Keys defined more than once, and which definition was used
+    pub conflicts: Vec<IncludeConflict>,
+}
+
+/// Bookkeeping while resolving includes
+struct IncludeState {
+    /// Canonical paths on the current include chain
+    visited: HashSet<PathBuf>,
+    /// Canonical paths already merged into the current bar
+    merged: HashSet<PathBuf>,
+    /// Included files in merge order
+    files: Vec<PathBuf>,
+    conflicts: Vec<IncludeConflict>,
+    duplicates: Vec<DuplicateInclude>,
+}
+
+/// Directory module files are split into, relative to the config dir
+const SPLIT_MODULES_DIR: &str = "modules";
+
+/// A file a multi-file operation proposes to write
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlannedFile {
+    /// Path relative to the config directory
+    pub path: String,
+    /// Full file content
+    pub content: String,
+}
+
+/// Get the `include` entries of a bar (a single string or an array)
+pub fn include_patterns(bar: &Map<String, Value>) -> Vec<String> {
+    match bar.get("include") {
+        Some(Value::String(pattern)) => vec![pattern.clone()],
+        Some(Value::Array(items)) => items
+            .iter()
+            .filter_map(Value::as_str)
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Check if any bar of a config has `include` entries
+pub fn has_includes(config: &Value) -> bool {
+    bars(config).into_iter().any(|bar| !include_patterns(bar).is_empty())
+}
+
+/// Expand one `include` entry into the files it matches, sorted
+///
+/// `~/` expands to the home directory and relative patterns are resolved
+/// against `base_dir`. A pattern without glob characters yields the file
+/// itself if it exists.
+pub fn expand_include(pattern: &str, base_dir: &Path) -> Result<Vec<PathBuf>> {
+    let pattern = match pattern.strip_prefix("~/") {
+        Some(rest) => {
+            let home = std::env::var("HOME")
+                .map_err(|_| AppError::Config("HOME environment variable not set".to_string()))?;
+            format!("{}/{}", home, rest)
+        }
+        None => pattern.to_string(),
+    };
+
+    let pattern = if Path::new(&pattern).is_absolute() {
+        pattern
+    } else {
+        let base = glob::Pattern::escape(&base_dir.to_string_lossy());
+        format!("{}/{}", base.trim_end_matches('/'), pattern)
+    };
+
+    let paths = glob::glob(&pattern)
+        .map_err(|e| AppError::Validation(format!("Invalid include pattern {}: {}", pattern, e)))?;
+
+    let mut files: Vec<PathBuf> = paths
+        .filter_map(|entry| entry.ok())
+        .filter(|path| path.is_file())
+        .collect();
+    files.sort();
+
+    Ok(files)
+}
+
+/// Merge the includes of `bar`, recursing into included files' own includes
+///
+/// `owner` is the file `bar` was read from; relative patterns resolve
+/// against its directory. `state.visited` holds the canonical paths of the
+/// files on the current include chain, starting with the main config, so a
+/// glob that matches an including file is skipped instead of looping. A file
+/// already merged into the bar (through another entry or an overlapping
+/// glob) is skipped too and reported as a duplicate.
+///
+/// Objects defined both in `bar` and in an included file are merged
+/// recursively, see [`merge_missing`].
+fn merge_includes(bar: &mut Map<String, Value>, owner: &Path, state: &mut IncludeState) -> Result<()> {
+    let base_dir = owner.parent().unwrap_or(Path::new("."));
+    // Which included file each merged key path came from; paths absent here are the owner's
+    let mut origins: HashMap<Vec<String>, PathBuf> = HashMap::new();
+
+    for pattern in include_patterns(bar) {
+        for path in expand_include(&pattern, base_dir)? {
+            let canonical = fs::canonicalize(&path)?;
+            if state.visited.contains(&canonical) {
+                log::warn!("Skipping include cycle: {}", path.display());
+                continue;
+            }
+            if !state.merged.insert(canonical.clone()) {
+                log::warn!("Skipping duplicate include: {}", path.display());
+                state.duplicates.push(DuplicateInclude {
+                    file: path.to_string_lossy().to_string(),
+                    pattern: pattern.clone(),
+                });
+                continue;
+            }
+
+            let content = fs::read_to_string(&path)?;
+            let Value::Object(mut included) = parse_jsonc(&content)? else {
+                log::warn!("Skipping include that is not an object: {}", path.display());
+                continue;
+            };
+
+            state.visited.insert(canonical.clone());
+            state.files.push(path.clone());
+            merge_includes(&mut included, &path, state)?;
+            state.visited.remove(&canonical);
+
+            included.shift_remove("include");
+            let mut merge = MergeSource { owner, path: &path, origins: &mut origins, state };
+            merge_missing(bar, included, &mut Vec::new(), &mut merge);
+        }
+    }
+
+    Ok(())
+}
+
+/// The included file being merged, with the bookkeeping [`merge_missing`] updates
+struct MergeSource<'a> {
+    owner: &'a Path,
+    path: &'a Path,
+    origins: &'a mut HashMap<Vec<String>, PathBuf>,
+    state: &'a mut IncludeState,
+}
+
+/// Add the keys of `included` that `target` lacks, recursing into objects
+/// both define
+///
+/// `key_path` is the path of `target` within the bar. A non-object value
+/// defined in both keeps the one in `target`; if they differ, a conflict
+/// names the file the kept value came from.
+fn merge_missing(
+    target: &mut Map<String, Value>,
+    included: Map<String, Value>,
+    key_path: &mut Vec<String>,
+    merge: &mut MergeSource,
+) {
+    for (key, value) in included {
+        key_path.push(key.clone());
+
+        match (target.get_mut(&key), value) {
+            (Some(Value::Object(existing)), Value::Object(nested)) => {
+                merge_missing(existing, nested, key_path, merge);
+            }
+            (Some(existing), value) => {
+                if *existing != value {
+                    // The closest enclosing path an include added, else the owner
+                    let kept = (1..=key_path.len())
+                        .rev()
+                        .find_map(|len| merge.origins.get(&key_path[..len]))
+                        .map(PathBuf::as_path)
+                        .unwrap_or(merge.owner);
+                    merge.state.conflicts.push(IncludeConflict {
+                        key: key_path.join("."),
+                        kept: kept.to_string_lossy().to_string(),
+                        ignored: merge.path.to_string_lossy().to_string(),
+                    });
+                }
+            }
+            (None, value) => {
+                merge.origins.insert(key_path.clone(), merge.path.to_path_buf());
+                target.insert(key, value);
+            }
+        }
+
+        key_path.pop();
+    }
+}
+
+/// Merge the files referenced by `include` into each bar of the config
+/// loaded from `config_path`
+///
+/// Objects are merged key by key. Values already in the including file win,
+/// then earlier includes win over later ones; every ignored value that
+/// differs is reported as a conflict.
+pub fn resolve_includes(mut config: Value, config_path: &Path) -> Result<IncludeResolution> {
+    let main = fs::canonicalize(config_path)?;
+    let mut state = IncludeState {
+        visited: HashSet::new(),
+        merged: HashSet::new(),
+        files: Vec::new(),
+        conflicts: Vec::new(),
+        duplicates: Vec::new(),
+    };
+
+    for bar in bars_mut(&mut config) {
+        state.visited = HashSet::from([main.clone()]);
+        state.merged.clear();
+        merge_includes(bar, config_path, &mut state)?;
+    }
+
+    Ok(IncludeResolution {
+        config,
+        files: state.files.iter().map(|p| p.to_string_lossy().to_string()).collect(),
+        conflicts: state.conflicts,
+        duplicates: state.duplicates,
+    })
+}
+
+/// Inline all includes into a single self-contained config
+///
+/// The result has no `include` entries and lists the key conflicts found
+/// while merging. Nothing is written.
+pub fn flatten_config(config: Value, config_path: &Path) -> Result<FlattenedConfig> {
+    let mut resolution = resolve_includes(config, config_path)?;
+
+    for bar in bars_mut(&mut resolution.config) {
+        bar.shift_remove("include");
+    }
+
+    Ok(FlattenedConfig {
+        content: format_json(&resolution.config)?,
+        conflicts: resolution.conflicts,
+    })
+}
+
+// ============================================================================
+// SPLITTING
+// ============================================================================
+
+/// File name for a split-out module (`custom/weather#2` → `custom-weather-2.jsonc`)
+fn module_file_name(name: &str) -> String {
+    let stem: String = name
+        .chars()
+        .map(|c| if c == '/' || c == '#' || c.is_whitespace() { '-' } else { c })
+        .collect();
+    format!("{}.jsonc", stem)
+}
+
+/// Plan splitting a single-bar config into one file per module definition
+///
+/// Each module block moves to `modules/<name>.jsonc`; the main config keeps
+/// the bar options and sections and gains a `modules/*.jsonc` include. The
+/// split is checked by merging the planned files back together, which must
+/// give the original config. Nothing is written; the main config comes first
+/// in the returned list.
+pub fn split_config(config: &Value, main_file: &str) -> Result<Vec<PlannedFile>> {
+    let Value::Object(bar) = config else {
+        return Err(AppError::Validation(
+            "Only single-bar configs can be split into include files".to_string(),
+        ));
+    };
+
+    let glob = format!("{}/*.jsonc", SPLIT_MODULES_DIR);
+    let mut main = bar.clone();
+    let mut module_files = Vec::new();
+
+    for (name, module) in module_definitions(bar) {
+        main.shift_remove(name);
+        let mut content = Map::new();
+        content.insert(name.clone(), Value::Object(module.clone()));
+        module_files.push(PlannedFile {
+            path: format!("{}/{}", SPLIT_MODULES_DIR, module_file_name(name)),
+            content: format_json(&Value::Object(content))?,
+        });
+    }
+
+    if module_files.is_empty() {
+        return Err(AppError::Validation("Config has no module definitions to split".to_string()));
+    }
+
+    let mut includes = include_patterns(bar);
+    if !includes.contains(&glob) {
+        includes.push(glob);
+    }
+    main.insert(
+        "include".to_string(),
+        Value::Array(includes.into_iter().map(Value::String).collect()),
+    );
+
+    let mut files = vec![PlannedFile {
+        path: main_file.to_string(),
+        content: format_json(&Value::Object(main))?,
+    }];
+    files.extend(module_files);
+
+    // Reassemble and compare, ignoring the include list itself
+    let mut reassembled = Map::new();
+    for file in &files {
+        if let Value::Object(part) = parse_jsonc(&file.content)? {
+            reassembled.extend(part);
+        }
+    }
+    let mut original = bar.clone();
+    reassembled.remove("include");
+    original.remove("include");
+    if reassembled != original {
+        return Err(AppError::Internal("Split config does not reassemble to the original".to_string()));
+    }
+
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_include_glob_merges_sorted_matches() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write(dir, "modules/clock.jsonc", r#"{ "clock": { "format": "{:%H}" }, "cpu": { "interval": 1 } }"#);
+        write(dir, "modules/battery.jsonc", r#"// battery
+{ "battery": { "bat": "BAT0" }, "cpu": { "interval": 5 } }"#);
+        write(dir, "modules/notes.txt", "not json");
+        let main = write(
+            dir,
+            "config.jsonc",
+            r#"{ "include": "modules/*.jsonc", "clock": { "format": "main" } }"#,
+        );
+
+        let config = parse_jsonc(&fs::read_to_string(&main).unwrap()).unwrap();
+        let resolution = resolve_includes(config, &main).unwrap();
+
+        let names: Vec<_> = resolution
+            .files
+            .iter()
+            .map(|f| Path::new(f).file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+        assert_eq!(names, vec!["battery.jsonc", "clock.jsonc"]);
+
+        // Main config wins, then earlier includes
+        assert_eq!(resolution.config["clock"]["format"], "main");
+        assert_eq!(resolution.config["battery"]["bat"], "BAT0");
+        assert_eq!(resolution.config["cpu"]["interval"], 5);
+    }
+
+    #[test]
+    fn test_glob_matching_main_config_is_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write(dir, "extra.jsonc", r#"{ "include": "*.jsonc", "tray": {} }"#);
+        let main = write(dir, "config.jsonc", r#"{ "include": ["*.jsonc"] }"#);
+
+        let config = parse_jsonc(&fs::read_to_string(&main).unwrap()).unwrap();
+        let resolution = resolve_includes(config, &main).unwrap();
+
+        assert_eq!(resolution.files.len(), 1);
+        assert!(resolution.files[0].ends_with("extra.jsonc"));
+        assert_eq!(resolution.config["tray"], json!({}));
+    }
+
+    #[test]
+    fn test_include_patterns() {
+        let bar = json!({ "include": ["a.jsonc", 3, "b/*.jsonc"] });
+        assert_eq!(include_patterns(bar.as_object().unwrap()), vec!["a.jsonc", "b/*.jsonc"]);
+        assert!(include_patterns(json!({}).as_object().unwrap()).is_empty());
+
+        assert!(has_includes(&json!([{ "height": 30 }, { "include": "modules.jsonc" }])));
+        assert!(!has_includes(&json!({ "include": [] })));
+    }
+
+    #[test]
+    fn test_split_config() {
+        let config = json!({
+            "position": "top",
+            "modules-left": ["clock"],
+            "modules-right": ["custom/weather"],
+            "clock": { "format": "{:%H:%M}" },
+            "custom/weather": { "exec": "weather.sh", "interval": 600 }
+        });
+
+        let files = split_config(&config, "config.jsonc").unwrap();
+        let paths: Vec<_> = files.iter().map(|f| f.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["config.jsonc", "modules/clock.jsonc", "modules/custom-weather.jsonc"]
+        );
+
+        let main = parse_jsonc(&files[0].content).unwrap();
+        assert_eq!(main["include"], json!(["modules/*.jsonc"]));
+        assert_eq!(main["modules-right"], json!(["custom/weather"]));
+        assert!(main.get("clock").is_none());
+
+        let weather = parse_jsonc(&files[2].content).unwrap();
+        assert_eq!(weather["custom/weather"]["interval"], 600);
+
+        // Written out, the includes resolve back to the original definitions
+        let temp_dir = TempDir::new().unwrap();
+        for file in &files {
+            write(temp_dir.path(), &file.path, &file.content);
+        }
+        let main_path = temp_dir.path().join("config.jsonc");
+        let resolution = resolve_includes(main, &main_path).unwrap();
+        assert_eq!(resolution.files.len(), 2);
+        assert_eq!(resolution.config["clock"], config["clock"]);
+        assert_eq!(resolution.config["custom/weather"], config["custom/weather"]);
+    }
+
+    #[test]
+    fn test_split_rejects_multi_bar_config() {
+        let config = json!([{ "clock": {} }, { "cpu": {} }]);
+        assert!(matches!(split_config(&config, "config.jsonc"), Err(AppError::Validation(_))));
+    }
+
+    #[test]
+    fn test_flatten_config_with_two_includes() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let clock = write(dir, "clock.jsonc", r#"{ "clock": { "format": "{:%H}" }, "height": 20 }"#);
+        let battery = write(dir, "battery.jsonc", r#"{ "battery": { "bat": "BAT1" }, "clock": { "format": "{:%M}" } }"#);
+        let main = write(
+            dir,
+            "config.jsonc",
+            r#"{ "include": ["clock.jsonc", "battery.jsonc"], "height": 30, "modules-left": ["clock", "battery"] }"#,
+        );
+
+        let config = parse_jsonc(&fs::read_to_string(&main).unwrap()).unwrap();
+        let flattened = flatten_config(config, &main).unwrap();
+        let conflicts = flattened.conflicts;
+
+        let flat = parse_jsonc(&flattened.content).unwrap();
+        assert!(flat.is_object());
+        assert!(flat.get("include").is_none());
+        assert_eq!(flat["height"], 30);
+        assert_eq!(flat["clock"]["format"], "{:%H}");
+        assert_eq!(flat["battery"]["bat"], "BAT1");
+
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].key, "height");
+        assert_eq!(conflicts[0].kept, main.to_string_lossy());
+        assert_eq!(conflicts[0].ignored, clock.to_string_lossy());
+        assert_eq!(conflicts[1].key, "clock.format");
+        assert_eq!(conflicts[1].kept, clock.to_string_lossy());
+        assert_eq!(conflicts[1].ignored, battery.to_string_lossy());
+    }
+
+    #[test]
+    fn test_included_objects_merge_with_including_file_winning() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        let clock = write(
+            dir,
+            "clock.jsonc",
+            r#"{ "clock": { "format": "{:%H}", "tooltip-format": "{:%A}", "actions": { "on-click": "mode" } } }"#,
+        );
+        let main = write(
+            dir,
+            "config.jsonc",
+            r#"{ "include": "clock.jsonc", "clock": { "format": "{:%M}", "actions": { "on-scroll-up": "shift_up" } } }"#,
+        );
+
+        let config = parse_jsonc(&fs::read_to_string(&main).unwrap()).unwrap();
+        let resolution = resolve_includes(config, &main).unwrap();
+
+        assert_eq!(
+            resolution.config["clock"],
+            json!({
+                "format": "{:%M}",
+                "actions": { "on-scroll-up": "shift_up", "on-click": "mode" },
+                "tooltip-format": "{:%A}"
+            })
+        );
+        assert_eq!(
+            resolution.conflicts,
+            vec![IncludeConflict {
+                key: "clock.format".to_string(),
+                kept: main.to_string_lossy().to_string(),
+                ignored: clock.to_string_lossy().to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_duplicate_includes_are_reported_once_merged() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        write(dir, "modules/clock.jsonc", r#"{ "clock": { "interval": 1 } }"#);
+        write(dir, "modules/cpu.jsonc", r#"{ "cpu": { "interval": 5 } }"#);
+        let main = write(
+            dir,
+            "config.jsonc",
+            r#"{ "include": ["modules/clock.jsonc", "modules/*.jsonc", "./modules/clock.jsonc"] }"#,
+        );
+
+        let config = parse_jsonc(&fs::read_to_string(&main).unwrap()).unwrap();
+        let resolution = resolve_includes(config, &main).unwrap();
+
+        assert_eq!(resolution.files.len(), 2);
+        assert!(resolution.conflicts.is_empty());
+
+        let patterns: Vec<_> = resolution.duplicates.iter().map(|d| d.pattern.as_str()).collect();
+        assert_eq!(patterns, vec!["modules/*.jsonc", "./modules/clock.jsonc"]);
+        assert!(resolution
+            .duplicates
+            .iter()
+            .all(|d| d.file.ends_with("clock.jsonc")));
+    }
+}
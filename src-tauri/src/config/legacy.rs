@@ -0,0 +1,165 @@
+// ============================================================================
+// LEGACY CONFIG MIGRATION
+// ============================================================================
+//
+// Older setups keep the bar definition in an extension-less `config` file.
+// Waybar reads `config.jsonc` first, so migrating is a copy plus a decision
+// about what to do with the old file.
+
+use crate::config::parser::parse_jsonc;
+use crate::config::writer::{create_backup, write_config_file};
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Extension-less config file name
+const LEGACY_CONFIG: &str = "config";
+
+/// Current config file name
+const JSONC_CONFIG: &str = "config.jsonc";
+
+/// Name the legacy file is renamed to with [`LegacyConfigAction::Rename`]
+const RENAMED_LEGACY_CONFIG: &str = "config.pre-jsonc";
+
+/// What to do with the legacy `config` file after migrating
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LegacyConfigAction {
+    /// Leave it in place (Waybar ignores it once `config.jsonc` exists)
+    Keep,
+    /// Delete it
+    Remove,
+    /// Rename it to `config.pre-jsonc`
+    Rename,
+}
+
+/// Copy the legacy `config` file to `config.jsonc`
+///
+/// The legacy file must parse as JSONC and is backed up before anything is
+/// touched. If `config.jsonc` already exists the migration is refused unless
+/// `force` is set, in which case the existing file is backed up and replaced.
+/// Returns the path of the new `config.jsonc`.
+pub fn migrate_to_jsonc(config_dir: &str, action: LegacyConfigAction, force: bool) -> Result<String> {
+    let dir = Path::new(config_dir);
+    let legacy_path = dir.join(LEGACY_CONFIG);
+    let jsonc_path = dir.join(JSONC_CONFIG);
+
+    if !legacy_path.is_file() {
+        return Err(AppError::NotFound(format!(
+            "No legacy config file in {}",
+            config_dir
+        )));
+    }
+
+    if jsonc_path.exists() && !force {
+        return Err(AppError::AlreadyExists(format!(
+            "{} already exists; use force to overwrite it",
+            jsonc_path.display()
+        )));
+    }
+
+    let content = fs::read_to_string(&legacy_path)?;
+    parse_jsonc(&content)?;
+
+    let legacy_str = legacy_path.to_string_lossy().to_string();
+    let jsonc_str = jsonc_path.to_string_lossy().to_string();
+
+    create_backup(&legacy_str)?;
+    // Backs up an existing config.jsonc when forced
+    write_config_file(&jsonc_str, &content)?;
+
+    match action {
+        LegacyConfigAction::Keep => {}
+        LegacyConfigAction::Remove => fs::remove_file(&legacy_path)?,
+        LegacyConfigAction::Rename => fs::rename(&legacy_path, dir.join(RENAMED_LEGACY_CONFIG))?,
+    }
+
+    log::info!("Migrated {} to {}", legacy_str, jsonc_str);
+    Ok(jsonc_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const LEGACY_CONTENT: &str = "// old config\n{ \"modules-left\": [\"clock\"] }";
+
+    fn backups_of(dir: &Path, file_name: &str) -> usize {
+        fs::read_dir(dir)
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                e.file_name()
+                    .to_string_lossy()
+                    .starts_with(&format!("{}.backup.", file_name))
+            })
+            .count()
+    }
+
+    #[test]
+    fn test_migrate_legacy_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("config"), LEGACY_CONTENT).unwrap();
+
+        let new_path =
+            migrate_to_jsonc(dir.to_str().unwrap(), LegacyConfigAction::Rename, false).unwrap();
+
+        assert_eq!(new_path, dir.join("config.jsonc").to_string_lossy());
+        assert_eq!(fs::read_to_string(&new_path).unwrap(), LEGACY_CONTENT);
+        assert!(!dir.join("config").exists());
+        assert!(dir.join("config.pre-jsonc").exists());
+        assert_eq!(backups_of(dir, "config"), 1);
+    }
+
+    #[test]
+    fn test_migrate_remove_legacy_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("config"), LEGACY_CONTENT).unwrap();
+
+        migrate_to_jsonc(dir.to_str().unwrap(), LegacyConfigAction::Remove, false).unwrap();
+
+        assert!(!dir.join("config").exists());
+        assert!(dir.join("config.jsonc").exists());
+    }
+
+    #[test]
+    fn test_both_exist_requires_force() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("config"), LEGACY_CONTENT).unwrap();
+        fs::write(dir.join("config.jsonc"), "{}").unwrap();
+
+        let result = migrate_to_jsonc(dir.to_str().unwrap(), LegacyConfigAction::Keep, false);
+        assert!(matches!(result, Err(AppError::AlreadyExists(_))));
+        assert_eq!(fs::read_to_string(dir.join("config.jsonc")).unwrap(), "{}");
+
+        migrate_to_jsonc(dir.to_str().unwrap(), LegacyConfigAction::Keep, true).unwrap();
+        assert_eq!(fs::read_to_string(dir.join("config.jsonc")).unwrap(), LEGACY_CONTENT);
+        assert!(dir.join("config").exists());
+        assert_eq!(backups_of(dir, "config.jsonc"), 1);
+    }
+
+    #[test]
+    fn test_invalid_legacy_config_is_not_migrated() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        fs::write(dir.join("config"), "{ broken").unwrap();
+
+        let result = migrate_to_jsonc(dir.to_str().unwrap(), LegacyConfigAction::Remove, false);
+        assert!(matches!(result, Err(AppError::Parse(_))));
+        assert!(dir.join("config").exists());
+        assert!(!dir.join("config.jsonc").exists());
+    }
+
+    #[test]
+    fn test_missing_legacy_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let result =
+            migrate_to_jsonc(temp_dir.path().to_str().unwrap(), LegacyConfigAction::Keep, false);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,1846 @@
+// ============================================================================
+// CONFIG LINTS
+// ============================================================================
+//
+// Advisory checks over a parsed Waybar config. Lints never fail: they return
+// a list of warnings the UI can show next to the offending module or bar.
+
+use crate::config::catalog::{
+    find_module, is_known_option, module_type, COMMON_OPTIONS, MODULE_CATALOG,
+};
+use crate::config::{bars, is_vertical_bar, module_definitions, MODULE_SECTIONS};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// A non-fatal finding about a config
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LintWarning {
+    /// Module or bar the warning applies to (e.g. `group/hardware`, `bar 0`)
+    pub target: String,
+    /// Config key involved, if any
+    pub key: Option<String>,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+impl LintWarning {
+    fn new(target: impl Into<String>, key: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            target: target.into(),
+            key: key.map(str::to_string),
+            message: message.into(),
+        }
+    }
+}
+
+/// A bar option whose value differs between bars
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BarFieldDifference {
+    /// Bar option (e.g. `height`)
+    pub field: String,
+    /// Value per bar, in config order; `None` where the bar doesn't set it
+    pub values: Vec<Option<Value>>,
+}
+
+/// Modules sharing the same `on-click` command
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DuplicateClickAction {
+    /// The shared command
+    pub command: String,
+    /// Modules using it, sorted
+    pub modules: Vec<String>,
+}
+
+/// A problem with a module placed on a bar
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleIssue {
+    /// Module name as written in the config (e.g. `batery`)
+    pub module: String,
+    /// Human-readable explanation
+    pub issue: String,
+}
+
+/// A player named in an `mpris` module's `player` or `ignored-players`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfiguredPlayer {
+    /// Module the player is configured on (e.g. `mpris#music`)
+    pub module: String,
+    /// `player` or `ignored-players`
+    pub key: String,
+    /// Player name as written in the config
+    pub player: String,
+    /// Whether a matching player is currently on the bus
+    pub active: bool,
+}
+
+/// Active MPRIS players compared with the ones the config names
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MprisPlayerReport {
+    /// Players currently on the session bus
+    pub active_players: Vec<String>,
+    /// Players named in the config, in config order
+    pub configured: Vec<ConfiguredPlayer>,
+}
+
+/// Label a bar for warnings: its `name` if set, otherwise its index
+fn bar_label(index: usize, bar: &Map<String, Value>) -> String {
+    match bar.get("name").and_then(Value::as_str) {
+        Some(name) => format!("bar {} ({})", index, name),
+        None => format!("bar {}", index),
+    }
+}
+
+// ============================================================================
+// GROUP ORIENTATION
+// ============================================================================
+
+/// Orientation values accepted by Waybar `group/*` modules
+const GROUP_ORIENTATIONS: [&str; 4] = ["inherit", "horizontal", "vertical", "orthogonal"];
+
+/// Check `orientation` of every `group/*` module
+///
+/// Flags values Waybar doesn't accept, and explicit `horizontal`/`vertical`
+/// groups that run against the bar's own orientation (derived from
+/// `position`), which usually renders as a squashed or overflowing group.
+/// `inherit` and `orthogonal` are relative to the bar and always consistent.
+pub fn check_group_orientation(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for (index, bar) in bars(config).into_iter().enumerate() {
+        let bar_orientation = if is_vertical_bar(bar) { "vertical" } else { "horizontal" };
+
+        for (name, module) in module_definitions(bar) {
+            if !name.starts_with("group/") {
+                continue;
+            }
+
+            let Some(orientation) = module.get("orientation") else {
+                continue;
+            };
+
+            let Some(orientation) = orientation.as_str().filter(|o| GROUP_ORIENTATIONS.contains(o))
+            else {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some("orientation"),
+                    format!(
+                        "Invalid orientation {}; expected one of: {}",
+                        orientation,
+                        GROUP_ORIENTATIONS.join(", ")
+                    ),
+                ));
+                continue;
+            };
+
+            if matches!(orientation, "horizontal" | "vertical") && orientation != bar_orientation {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some("orientation"),
+                    format!(
+                        "{} group on a {} {}; use \"inherit\" or \"orthogonal\" unless this is intentional",
+                        orientation,
+                        bar_orientation,
+                        bar_label(index, bar)
+                    ),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// LAYER / EXCLUSIVE / PASSTHROUGH
+// ============================================================================
+
+/// A risky combination of bar-level `layer`, `exclusive` and `passthrough`
+/// settings. `None` fields match any value.
+struct LayerRule {
+    layer: Option<&'static str>,
+    exclusive: Option<bool>,
+    passthrough: Option<bool>,
+    consequence: &'static str,
+}
+
+/// Known risky combinations, matched against the effective values (Waybar
+/// defaults: `layer: "bottom"`, `exclusive: true`, `passthrough: false`)
+const LAYER_RULES: [LayerRule; 4] = [
+    // Overlay sits above fullscreen surfaces, and the exclusive zone still
+    // shrinks the usable area for everything else
+    LayerRule {
+        layer: Some("overlay"),
+        exclusive: Some(true),
+        passthrough: None,
+        consequence: "the bar reserves screen space and also draws above fullscreen windows, covering video and games",
+    },
+    // Overlay without an exclusive zone floats over windows and, unless
+    // passthrough is on, swallows clicks meant for what's underneath
+    LayerRule {
+        layer: Some("overlay"),
+        exclusive: Some(false),
+        passthrough: Some(false),
+        consequence: "the bar floats over windows and intercepts clicks on the content beneath it",
+    },
+    // Reserving space for a bar that ignores input leaves a dead strip
+    LayerRule {
+        layer: None,
+        exclusive: Some(true),
+        passthrough: Some(true),
+        consequence: "space is reserved for the bar but clicks pass through it, so its modules can't be used",
+    },
+    // A bottom-layer bar without an exclusive zone gets covered by windows
+    LayerRule {
+        layer: Some("bottom"),
+        exclusive: Some(false),
+        passthrough: None,
+        consequence: "windows are allowed to cover the bar, which can leave it hidden behind them",
+    },
+];
+
+/// Check bar-level `layer`, `exclusive` and `passthrough` combinations
+/// against [`LAYER_RULES`], explaining the likely visual consequence
+pub fn check_layer_settings(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for (index, bar) in bars(config).into_iter().enumerate() {
+        let layer = bar.get("layer").and_then(Value::as_str).unwrap_or("bottom");
+        let exclusive = bar.get("exclusive").and_then(Value::as_bool).unwrap_or(true);
+        let passthrough = bar.get("passthrough").and_then(Value::as_bool).unwrap_or(false);
+
+        for rule in &LAYER_RULES {
+            let matches = rule.layer.is_none_or(|l| l == layer)
+                && rule.exclusive.is_none_or(|e| e == exclusive)
+                && rule.passthrough.is_none_or(|p| p == passthrough);
+
+            if matches {
+                warnings.push(LintWarning::new(
+                    bar_label(index, bar),
+                    Some("layer"),
+                    format!(
+                        "layer \"{}\" with exclusive: {}, passthrough: {}: {}",
+                        layer, exclusive, passthrough, rule.consequence
+                    ),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// FORMAT PLACEHOLDERS
+// ============================================================================
+
+/// Check if a key holds a format string (`format`, `format-alt`,
+/// `tooltip-format` and their state variants like `format-charging`)
+fn is_format_key(key: &str) -> bool {
+    key == "format"
+        || key == "tooltip-format"
+        || (key.starts_with("format-") && key != "format-icons")
+        || key.starts_with("tooltip-format-")
+}
+
+/// Find the first brace problem in a format string
+///
+/// `{{` and `}}` outside a placeholder are escaped literal braces.
+fn find_brace_error(format: &str) -> Option<String> {
+    let mut chars = format.char_indices().peekable();
+    let mut open_at: Option<usize> = None;
+
+    while let Some((pos, ch)) = chars.next() {
+        match (ch, open_at) {
+            ('{', None) if chars.peek().map(|(_, c)| *c) == Some('{') => {
+                chars.next();
+            }
+            ('}', None) if chars.peek().map(|(_, c)| *c) == Some('}') => {
+                chars.next();
+            }
+            ('{', None) => open_at = Some(pos),
+            ('{', Some(start)) => {
+                return Some(format!("placeholder opened at position {} is never closed", start));
+            }
+            ('}', Some(_)) => open_at = None,
+            ('}', None) => {
+                return Some(format!("unmatched '}}' at position {} (use '}}}}' for a literal brace)", pos));
+            }
+            _ => {}
+        }
+    }
+
+    open_at.map(|start| format!("placeholder opened at position {} is never closed", start))
+}
+
+/// Check format strings of every module for unbalanced `{}` placeholders
+pub fn check_format_placeholders(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            for (key, value) in module {
+                let Some(format) = value.as_str().filter(|_| is_format_key(key)) else {
+                    continue;
+                };
+
+                if let Some(problem) = find_brace_error(format) {
+                    warnings.push(LintWarning::new(
+                        name.as_str(),
+                        Some(key),
+                        format!("Malformed format \"{}\": {}", format, problem),
+                    ));
+                }
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// MULTI-BAR CONSISTENCY
+// ============================================================================
+
+/// Bar options that usually match across bars meant to look uniform
+const UNIFORM_BAR_FIELDS: [&str; 7] = [
+    "height",
+    "spacing",
+    "margin",
+    "margin-top",
+    "margin-right",
+    "margin-bottom",
+    "margin-left",
+];
+
+/// Compare sizing options across the bars of a multi-bar config
+///
+/// Advisory: reports each of [`UNIFORM_BAR_FIELDS`] whose value isn't the
+/// same on every bar (unset counts as a value), so the user can confirm the
+/// difference is intentional. Single-bar configs have nothing to compare.
+pub fn compare_bar_dimensions(config: &Value) -> Vec<BarFieldDifference> {
+    let bars = bars(config);
+    if bars.len() < 2 {
+        return Vec::new();
+    }
+
+    UNIFORM_BAR_FIELDS
+        .iter()
+        .filter_map(|field| {
+            let values: Vec<Option<Value>> = bars.iter().map(|bar| bar.get(*field).cloned()).collect();
+            let differs = values.iter().any(|value| *value != values[0]);
+
+            differs.then(|| BarFieldDifference {
+                field: field.to_string(),
+                values,
+            })
+        })
+        .collect()
+}
+
+/// Compare the `layer` of each bar in a multi-bar config
+///
+/// Advisory: bars on different layers stack in ways that are easy to get
+/// wrong, so any difference is reported with the per-bar values. Unset and
+/// `"bottom"` count as the same, since that is Waybar's default.
+pub fn compare_bar_layers(config: &Value) -> Option<BarFieldDifference> {
+    let bars = bars(config);
+    if bars.len() < 2 {
+        return None;
+    }
+
+    let effective = |bar: &Map<String, Value>| {
+        bar.get("layer").cloned().unwrap_or_else(|| Value::String("bottom".to_string()))
+    };
+    let first = effective(bars[0]);
+    let differs = bars.iter().any(|bar| effective(bar) != first);
+
+    differs.then(|| BarFieldDifference {
+        field: "layer".to_string(),
+        values: bars.iter().map(|bar| bar.get("layer").cloned()).collect(),
+    })
+}
+
+// ============================================================================
+// TEXT LENGTH LIMITS
+// ============================================================================
+
+/// Check `max-length` / `min-length` of every module
+///
+/// Flags negative or non-integer values, `max-length: 0` (which hides the
+/// module entirely), and a `max-length` smaller than `min-length`.
+pub fn check_length_limits(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            let mut lengths = [None, None];
+
+            for (slot, key) in ["min-length", "max-length"].iter().enumerate() {
+                let Some(value) = module.get(*key) else {
+                    continue;
+                };
+
+                match value.as_i64() {
+                    Some(length) if length >= 0 => lengths[slot] = Some(length),
+                    _ => warnings.push(LintWarning::new(
+                        name.as_str(),
+                        Some(key),
+                        format!("{} must be a non-negative integer, got {}", key, value),
+                    )),
+                }
+            }
+
+            let [min_length, max_length] = lengths;
+
+            if max_length == Some(0) {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some("max-length"),
+                    "max-length is 0, which hides the module entirely",
+                ));
+            } else if let (Some(min), Some(max)) = (min_length, max_length) {
+                if max < min {
+                    warnings.push(LintWarning::new(
+                        name.as_str(),
+                        Some("max-length"),
+                        format!("max-length ({}) is less than min-length ({})", max, min),
+                    ));
+                }
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// CPU FORMAT ICONS
+// ============================================================================
+
+/// Check if a format string uses per-core icons (`{icon0}`, not `{icon}`)
+fn uses_per_core_icons(format: &str) -> bool {
+    format
+        .match_indices("{icon")
+        .any(|(pos, prefix)| format[pos + prefix.len()..].starts_with(|c: char| c.is_ascii_digit()))
+}
+
+/// Check `format-icons` arrays of per-core `cpu` modules against the core count
+///
+/// Only modules whose format strings use per-core icons are checked; a
+/// mismatched array leaves cores without an icon or wraps them around.
+/// `{icon}` and `{usage0}` alone don't index the array by core.
+pub fn check_cpu_format_icons(config: &Value, core_count: usize) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            if module_type(name) != "cpu" {
+                continue;
+            }
+
+            let per_core = module
+                .iter()
+                .filter(|(key, _)| is_format_key(key))
+                .filter_map(|(_, value)| value.as_str())
+                .any(uses_per_core_icons);
+
+            let Some(icons) = module.get("format-icons").and_then(Value::as_array) else {
+                continue;
+            };
+
+            if per_core && icons.len() != core_count {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some("format-icons"),
+                    format!("{} entries for {} cores", icons.len(), core_count),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// WORKSPACE ICONS
+// ============================================================================
+
+/// `format-icons` keys of workspace modules that name states, not workspaces
+const WORKSPACE_STATE_ICONS: [&str; 8] = [
+    "default", "active", "focused", "visible", "urgent", "empty", "persistent", "special",
+];
+
+/// Check per-workspace `format-icons` maps against the live workspaces
+///
+/// `module_type` selects the workspaces module to check (e.g.
+/// `hyprland/workspaces`) and `workspaces` holds the names the compositor
+/// reports. Warns about icon keys matching no workspace, and - when the map
+/// has no `default` icon - about workspaces without an icon.
+pub fn check_workspace_icons(
+    config: &Value,
+    module_type_name: &str,
+    workspaces: &[String],
+) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            if module_type(name) != module_type_name {
+                continue;
+            }
+
+            let Some(icons) = module.get("format-icons").and_then(Value::as_object) else {
+                continue;
+            };
+
+            for key in icons.keys() {
+                if !WORKSPACE_STATE_ICONS.contains(&key.as_str()) && !workspaces.contains(key) {
+                    warnings.push(LintWarning::new(
+                        name.as_str(),
+                        Some("format-icons"),
+                        format!("Icon for workspace \"{}\" which doesn't exist", key),
+                    ));
+                }
+            }
+
+            if icons.contains_key("default") {
+                continue;
+            }
+
+            for workspace in workspaces {
+                if !icons.contains_key(workspace) {
+                    warnings.push(LintWarning::new(
+                        name.as_str(),
+                        Some("format-icons"),
+                        format!("Workspace \"{}\" has no icon and there is no default", workspace),
+                    ));
+                }
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// UNKNOWN KEYS
+// ============================================================================
+
+/// Maximum edit distance for a "did you mean" suggestion
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Levenshtein distance between two strings
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Check module options against the catalog's known keys
+///
+/// Approximates the "unknown key" warnings Waybar logs at startup. Custom
+/// modules accept arbitrary keys and unknown module types can't be checked,
+/// so both are skipped.
+pub fn check_unknown_keys(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            let Some(spec) = find_module(name).filter(|spec| spec.name != "custom") else {
+                continue;
+            };
+
+            for key in module.keys() {
+                if is_known_option(spec, key) {
+                    continue;
+                }
+
+                let suggestion = COMMON_OPTIONS
+                    .iter()
+                    .chain(spec.options)
+                    .map(|option| (edit_distance(key, option), option))
+                    .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+                    .min_by_key(|(distance, _)| *distance);
+
+                let message = match suggestion {
+                    Some((_, option)) => format!(
+                        "Unknown option \"{}\" for {} (did you mean \"{}\"?)",
+                        key, spec.name, option
+                    ),
+                    None => format!("Unknown option \"{}\" for {}", key, spec.name),
+                };
+                warnings.push(LintWarning::new(name.as_str(), Some(key), message));
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// DUPLICATE CLICK ACTIONS
+// ============================================================================
+
+/// Commands too generic to suggest copy-pasting when shared
+const TRIVIAL_COMMANDS: [&str; 3] = ["true", "false", ":"];
+
+/// Find modules with byte-identical `on-click` commands
+///
+/// Usually a sign of a copy-pasted module definition. Empty and trivial
+/// commands are ignored, and a module defined in several bars counts once.
+pub fn find_duplicate_click_actions(config: &Value) -> Vec<DuplicateClickAction> {
+    let mut by_command: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            let Some(command) = module.get("on-click").and_then(Value::as_str) else {
+                continue;
+            };
+            if command.trim().is_empty() || TRIVIAL_COMMANDS.contains(&command.trim()) {
+                continue;
+            }
+            by_command.entry(command).or_default().insert(name.as_str());
+        }
+    }
+
+    by_command
+        .into_iter()
+        .filter(|(_, modules)| modules.len() > 1)
+        .map(|(command, modules)| DuplicateClickAction {
+            command: command.to_string(),
+            modules: modules.into_iter().map(str::to_string).collect(),
+        })
+        .collect()
+}
+
+// ============================================================================
+// GROUP DRAWERS
+// ============================================================================
+
+/// Check `drawer` settings are on groups and groups have children
+///
+/// Waybar silently ignores `drawer` on anything but a `group/*` module, and
+/// a group without a `modules` array renders nothing.
+pub fn check_group_drawers(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            if !name.starts_with("group/") {
+                if module.contains_key("drawer") {
+                    warnings.push(LintWarning::new(
+                        name.as_str(),
+                        Some("drawer"),
+                        "drawer only applies to group/* modules and is ignored here",
+                    ));
+                }
+                continue;
+            }
+
+            let has_children = module
+                .get("modules")
+                .and_then(Value::as_array)
+                .is_some_and(|children| !children.is_empty());
+            if !has_children {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some("modules"),
+                    "Group has no modules array listing its children",
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// SELF-RELOAD OPTIONS
+// ============================================================================
+
+/// Bar option making Waybar watch and reload its stylesheet by itself
+const RELOAD_STYLE_KEY: &str = "reload_style_on_change";
+
+/// Check Waybar's own reload options against the app's reload strategy
+///
+/// The app reloads Waybar with SIGUSR2 after each save, which re-reads both
+/// the config and the stylesheet. The rules:
+/// - `reload_style_on_change: true` makes Waybar also reload on its own when
+///   style.css changes, so a style save reloads twice (usually a flicker)
+/// - the option must be a boolean; anything else is ignored by Waybar
+/// - bars disagreeing on it reload inconsistently on style edits
+/// - set inside a module it is ignored, since it's a bar option
+pub fn check_reload_options(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+    let bars = bars(config);
+    let mut enabled_bars = 0;
+
+    for (index, bar) in bars.iter().enumerate() {
+        let label = bar_label(index, bar);
+
+        match bar.get(RELOAD_STYLE_KEY) {
+            Some(Value::Bool(true)) => {
+                enabled_bars += 1;
+                warnings.push(LintWarning::new(
+                    label.as_str(),
+                    Some(RELOAD_STYLE_KEY),
+                    "Waybar reloads style.css by itself and again on the app's SIGUSR2 reload after saving; \
+                     disable one of them if the double reload flickers",
+                ));
+            }
+            Some(Value::Bool(false)) | None => {}
+            Some(other) => warnings.push(LintWarning::new(
+                label.as_str(),
+                Some(RELOAD_STYLE_KEY),
+                format!("Expected true or false, found {}", other),
+            )),
+        }
+
+        for (name, module) in module_definitions(bar) {
+            if module.contains_key(RELOAD_STYLE_KEY) {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some(RELOAD_STYLE_KEY),
+                    format!("{} is a bar option and is ignored inside a module", RELOAD_STYLE_KEY),
+                ));
+            }
+        }
+    }
+
+    if enabled_bars > 0 && enabled_bars < bars.len() {
+        warnings.push(LintWarning::new(
+            "config",
+            Some(RELOAD_STYLE_KEY),
+            format!(
+                "Only {} of {} bars reload style.css on change; style edits apply inconsistently until the next reload",
+                enabled_bars,
+                bars.len()
+            ),
+        ));
+    }
+
+    warnings
+}
+
+// ============================================================================
+// NETWORK INTERFACES
+// ============================================================================
+
+/// Check `network` modules' `interface` against the machine's interfaces
+///
+/// Waybar accepts glob patterns (`wl*`), so a value matches when any
+/// interface fits the pattern. A module whose interface matches nothing
+/// shows nothing at all.
+pub fn check_network_interfaces(config: &Value, interfaces: &[String]) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            if module_type(name) != "network" {
+                continue;
+            }
+
+            let Some(interface) = module.get("interface").and_then(Value::as_str) else {
+                continue;
+            };
+
+            let matches = match glob::Pattern::new(interface) {
+                Ok(pattern) => interfaces.iter().any(|i| pattern.matches(i)),
+                Err(_) => interfaces.iter().any(|i| i == interface),
+            };
+            if !matches {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some("interface"),
+                    format!(
+                        "No network interface matches \"{}\" (available: {})",
+                        interface,
+                        interfaces.join(", ")
+                    ),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// MPRIS PLAYERS
+// ============================================================================
+
+/// Check if a configured player name refers to an active player
+/// (`firefox` also matches the instanced `firefox.instance_1_42`)
+fn is_player_active(player: &str, active_players: &[String]) -> bool {
+    active_players.iter().any(|active| {
+        active == player
+            || active.strip_prefix(player).is_some_and(|rest| rest.starts_with('.'))
+    })
+}
+
+/// Cross-reference the `player` and `ignored-players` of every `mpris`
+/// module with the players currently on the bus
+///
+/// A `player` that is never active leaves the module blank, which is the
+/// usual cause of an empty media module.
+pub fn check_mpris_players(config: &Value, active_players: &[String]) -> MprisPlayerReport {
+    let mut configured = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            if module_type(name) != "mpris" {
+                continue;
+            }
+
+            let players = module.get("player").and_then(Value::as_str).map(|p| ("player", p));
+            let ignored = module
+                .get("ignored-players")
+                .and_then(Value::as_array)
+                .into_iter()
+                .flatten()
+                .filter_map(Value::as_str)
+                .map(|p| ("ignored-players", p));
+
+            for (key, player) in players.into_iter().chain(ignored) {
+                configured.push(ConfiguredPlayer {
+                    module: name.clone(),
+                    key: key.to_string(),
+                    player: player.to_string(),
+                    active: is_player_active(player, active_players),
+                });
+            }
+        }
+    }
+
+    MprisPlayerReport {
+        active_players: active_players.to_vec(),
+        configured,
+    }
+}
+
+// ============================================================================
+// SCROLL ACTIONS
+// ============================================================================
+
+/// Scroll action pairs, each direction with its opposite
+const SCROLL_PAIRS: [(&str, &str); 4] = [
+    ("on-scroll-up", "on-scroll-down"),
+    ("on-scroll-down", "on-scroll-up"),
+    ("on-scroll-left", "on-scroll-right"),
+    ("on-scroll-right", "on-scroll-left"),
+];
+
+/// Check modules define scroll actions in both directions
+///
+/// One-directional scrolling is occasionally intentional, so this is only a
+/// hint that the complementary action may be missing.
+pub fn check_scroll_actions(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            for (present, missing) in SCROLL_PAIRS {
+                if module.contains_key(present) && !module.contains_key(missing) {
+                    warnings.push(LintWarning::new(
+                        name.as_str(),
+                        Some(missing),
+                        format!("Has {} but no {}; add it unless this is intentional", present, missing),
+                    ));
+                }
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// TOOLTIP FIELDS
+// ============================================================================
+
+/// Get the placeholder names of a format string (`{capacity}`, `{time:%H}`)
+///
+/// Escaped braces and positional placeholders (`{}`, `{:%H}`) are skipped.
+fn placeholder_names(format: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = format;
+
+    while let Some(open) = rest.find('{') {
+        let after = &rest[open + 1..];
+        if let Some(escaped) = after.strip_prefix('{') {
+            rest = escaped;
+            continue;
+        }
+        let Some(close) = after.find('}') else {
+            break;
+        };
+
+        let name = after[..close].split(':').next().unwrap_or_default().trim();
+        if !name.is_empty() {
+            names.push(name);
+        }
+        rest = &after[close + 1..];
+    }
+
+    names
+}
+
+/// Check a placeholder against a module's fields, accepting numbered
+/// variants like `{usage3}` or `{icon0}`
+fn is_known_field(fields: &[&str], name: &str) -> bool {
+    fields.contains(&name) || fields.contains(&name.trim_end_matches(|c: char| c.is_ascii_digit()))
+}
+
+/// Check `tooltip-format` placeholders against the fields each module provides
+///
+/// Only modules whose fields are listed in the catalog are checked. Unknown
+/// placeholders render literally, so each gets a warning with the closest
+/// field or, failing that, the fields available.
+pub fn check_tooltip_fields(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            let Some(spec) = find_module(name).filter(|spec| !spec.fields.is_empty()) else {
+                continue;
+            };
+
+            for (key, value) in module {
+                if key != "tooltip-format" && !key.starts_with("tooltip-format-") {
+                    continue;
+                }
+                let Some(format) = value.as_str() else {
+                    continue;
+                };
+
+                for placeholder in placeholder_names(format) {
+                    if is_known_field(spec.fields, placeholder) {
+                        continue;
+                    }
+
+                    let lowered = placeholder.to_lowercase();
+                    let suggestion = spec
+                        .fields
+                        .iter()
+                        .map(|field| (edit_distance(&lowered, &field.to_lowercase()), field))
+                        .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+                        .min_by_key(|(distance, _)| *distance);
+
+                    let hint = match suggestion {
+                        Some((_, field)) => format!("did you mean {{{}}}?", field),
+                        None => format!("available: {}", spec.fields.join(", ")),
+                    };
+                    warnings.push(LintWarning::new(
+                        name.as_str(),
+                        Some(key),
+                        format!("Unknown placeholder {{{}}} for {} ({})", placeholder, spec.name, hint),
+                    ));
+                }
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// CLOCK INTERVALS
+// ============================================================================
+
+/// strftime specifiers that display seconds (`%T` is `%H:%M:%S`, `%r` is
+/// the 12-hour time with seconds, `%c` and `%X` the locale's date/time)
+const SECONDS_SPECIFIERS: [&str; 6] = ["%S", "%T", "%r", "%X", "%c", "%s"];
+
+/// Interval suggested for clocks that only show minutes
+const MINUTE_INTERVAL: u32 = 60;
+
+/// Flag `clock` modules updating every second without displaying seconds
+///
+/// Checks `format` (Waybar's default is `{:%H:%M}`) and `format-alt`; if
+/// either shows seconds the fast interval is justified.
+pub fn check_clock_intervals(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            if module_type(name) != "clock" {
+                continue;
+            }
+            let Some(interval) = module.get("interval").and_then(Value::as_f64) else {
+                continue;
+            };
+            if interval > 1.0 {
+                continue;
+            }
+
+            let shows_seconds = ["format", "format-alt"].iter().any(|key| {
+                module
+                    .get(*key)
+                    .and_then(Value::as_str)
+                    .is_some_and(|format| SECONDS_SPECIFIERS.iter().any(|s| format.contains(s)))
+            });
+            if !shows_seconds {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some("interval"),
+                    format!(
+                        "interval {} redraws the clock every second but its format shows no seconds; \
+                         use \"interval\": {} or add %S to the format",
+                        module["interval"], MINUTE_INTERVAL
+                    ),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// MODULE NAMES
+// ============================================================================
+
+/// Module types that only exist through a definition block in the bar
+const USER_DEFINED_TYPES: [&str; 3] = ["custom", "group", "cffi"];
+
+/// Modules placed on a bar: its sections, then the members of its groups
+fn placed_modules(bar: &Map<String, Value>) -> Vec<&str> {
+    fn strings(value: Option<&Value>) -> Vec<&str> {
+        value
+            .and_then(Value::as_array)
+            .map(|items| items.iter().filter_map(Value::as_str).collect())
+            .unwrap_or_default()
+    }
+
+    let mut placed: Vec<&str> = MODULE_SECTIONS
+        .iter()
+        .flat_map(|section| strings(bar.get(*section)))
+        .collect();
+    for (name, group) in module_definitions(bar) {
+        if name.starts_with("group/") {
+            placed.extend(strings(group.get("modules")));
+        }
+    }
+
+    placed
+}
+
+/// Check that every placed module is a Waybar module type, and that
+/// `custom/*`, `group/*` and `cffi/*` modules are defined in their bar
+///
+/// Unknown types get the closest catalog name as a suggestion, which catches
+/// typos like `batery` that Waybar would otherwise skip with a log message.
+pub fn check_module_names(config: &Value) -> Vec<ModuleIssue> {
+    let mut issues: Vec<ModuleIssue> = Vec::new();
+
+    for (index, bar) in bars(config).into_iter().enumerate() {
+        for name in placed_modules(bar) {
+            let kind = module_type(name);
+
+            let issue = if USER_DEFINED_TYPES.contains(&kind) {
+                if bar.get(name).is_some_and(Value::is_object) {
+                    continue;
+                }
+                format!("{} has no definition in {}", name, bar_label(index, bar))
+            } else if find_module(name).is_some() {
+                continue;
+            } else {
+                let suggestion = MODULE_CATALOG
+                    .iter()
+                    .map(|spec| (edit_distance(kind, spec.name), spec.name))
+                    .filter(|(distance, _)| *distance <= MAX_SUGGESTION_DISTANCE)
+                    .min_by_key(|(distance, _)| *distance);
+                match suggestion {
+                    Some((_, known)) => format!("Unknown module type {}; did you mean {}?", kind, known),
+                    None => format!("Unknown module type {}", kind),
+                }
+            };
+
+            let issue = ModuleIssue {
+                module: name.to_string(),
+                issue,
+            };
+            if !issues.contains(&issue) {
+                issues.push(issue);
+            }
+        }
+    }
+
+    issues
+}
+
+// ============================================================================
+// COMPOSITOR FEATURES
+// ============================================================================
+
+/// A bar option that only works on some compositors
+struct CompositorFeature {
+    /// Bar option
+    key: &'static str,
+    /// Value that needs support; `None` matches any value
+    value: Option<&'static str>,
+    /// Compositors where the feature works
+    supported_on: &'static [&'static str],
+    /// What happens elsewhere
+    consequence: &'static str,
+}
+
+/// Compositor capability table, checked against the detected compositor
+const COMPOSITOR_FEATURES: [CompositorFeature; 4] = [
+    // Bar IPC is sway's `bar` command protocol (sway-bar(5))
+    CompositorFeature {
+        key: "ipc",
+        value: None,
+        supported_on: &["sway"],
+        consequence: "bar IPC uses sway's bar commands and does nothing on other compositors",
+    },
+    CompositorFeature {
+        key: "modifier-reset",
+        value: None,
+        supported_on: &["sway"],
+        consequence: "the modifier key state comes from sway's bar IPC and is never reported",
+    },
+    // Hide mode reveals the bar while sway's bar modifier is held
+    CompositorFeature {
+        key: "mode",
+        value: Some("hide"),
+        supported_on: &["sway"],
+        consequence: "the hidden bar is only revealed by sway's modifier key, so it stays hidden",
+    },
+    CompositorFeature {
+        key: "mode",
+        value: Some("invisible"),
+        supported_on: &["sway"],
+        consequence: "only sway's bar IPC can switch the bar out of invisible mode",
+    },
+];
+
+/// Flag bar options that [`COMPOSITOR_FEATURES`] lists as unsupported on
+/// `compositor`
+///
+/// Unknown compositors aren't checked, since nothing is known about them.
+pub fn check_compositor_features(config: &Value, compositor: &str) -> Vec<LintWarning> {
+    let compositor = compositor.to_lowercase();
+    if compositor.is_empty() || compositor == "unknown" {
+        return Vec::new();
+    }
+
+    let mut warnings = Vec::new();
+
+    for (index, bar) in bars(config).into_iter().enumerate() {
+        for feature in &COMPOSITOR_FEATURES {
+            let Some(value) = bar.get(feature.key) else {
+                continue;
+            };
+            if value == &Value::Bool(false)
+                || feature.value.is_some_and(|expected| value.as_str() != Some(expected))
+                || feature.supported_on.contains(&compositor.as_str())
+            {
+                continue;
+            }
+
+            warnings.push(LintWarning::new(
+                bar_label(index, bar),
+                Some(feature.key),
+                format!(
+                    "\"{}\": {} is not supported on {}: {}",
+                    feature.key, value, compositor, feature.consequence
+                ),
+            ));
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// FORMAT ICON TYPES
+// ============================================================================
+
+/// Describe what's wrong with an icon list, if anything
+fn icon_list_problem(icons: &[Value]) -> Option<String> {
+    if icons.is_empty() {
+        return Some("is an empty array".to_string());
+    }
+
+    let non_strings: Vec<String> = icons
+        .iter()
+        .enumerate()
+        .filter(|(_, icon)| !icon.is_string())
+        .map(|(index, _)| index.to_string())
+        .collect();
+    match non_strings.len() {
+        0 => None,
+        n if n == icons.len() => Some("contains no strings".to_string()),
+        _ => Some(format!("mixes strings with other values (at index {})", non_strings.join(", "))),
+    }
+}
+
+/// Check that every `format-icons` is well-typed
+///
+/// Waybar accepts a string, a non-empty array of strings, or an object whose
+/// values are strings or such arrays (per-state icons, e.g. battery's
+/// `{"charging": [...], "default": [...]}`). Anything else renders no icon.
+pub fn check_format_icon_types(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            let Some(icons) = module.get("format-icons") else {
+                continue;
+            };
+
+            let problems: Vec<String> = match icons {
+                Value::String(_) => Vec::new(),
+                Value::Array(items) => icon_list_problem(items).into_iter().collect(),
+                Value::Object(map) if map.is_empty() => vec!["is an empty object".to_string()],
+                Value::Object(map) => map
+                    .iter()
+                    .filter_map(|(state, value)| {
+                        let problem = match value {
+                            Value::String(_) => None,
+                            Value::Array(items) => icon_list_problem(items),
+                            other => Some(format!("is {}, not a string or array", other)),
+                        };
+                        problem.map(|problem| format!("entry \"{}\" {}", state, problem))
+                    })
+                    .collect(),
+                other => vec![format!("is {}, not a string, array or object", other)],
+            };
+
+            for problem in problems {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some("format-icons"),
+                    format!("format-icons {}", problem),
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// ALTERNATE FORMATS
+// ============================================================================
+
+/// Flag modules whose `format-alt` is byte-for-byte the same as `format`
+///
+/// Clicking such a module toggles between two identical labels, so one of
+/// them was most likely meant to be customized.
+pub fn check_format_alt(config: &Value) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    for bar in bars(config) {
+        for (name, module) in module_definitions(bar) {
+            let format = module.get("format").and_then(Value::as_str);
+            let format_alt = module.get("format-alt").and_then(Value::as_str);
+
+            if format.is_some() && format == format_alt {
+                warnings.push(LintWarning::new(
+                    name.as_str(),
+                    Some("format-alt"),
+                    "format-alt is the same as format, so clicking the module changes nothing; \
+                     change one of them or remove format-alt",
+                ));
+            }
+        }
+    }
+
+    warnings
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_horizontal_group_on_vertical_bar() {
+        let config = json!({
+            "position": "left",
+            "modules-left": ["group/hardware"],
+            "group/hardware": {
+                "orientation": "horizontal",
+                "modules": ["cpu", "memory"]
+            }
+        });
+
+        let warnings = check_group_orientation(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "group/hardware");
+        assert_eq!(warnings[0].key.as_deref(), Some("orientation"));
+        assert!(warnings[0].message.contains("vertical bar 0"));
+    }
+
+    #[test]
+    fn test_matching_group_orientation() {
+        let config = json!({
+            "position": "right",
+            "group/hardware": { "orientation": "vertical", "modules": ["cpu"] },
+            "group/power": { "orientation": "inherit", "modules": ["battery"] },
+            "group/tray": { "orientation": "orthogonal", "modules": ["tray"] }
+        });
+
+        assert!(check_group_orientation(&config).is_empty());
+    }
+
+    #[test]
+    fn test_default_position_is_horizontal() {
+        let config = json!({
+            "group/hardware": { "orientation": "horizontal", "modules": ["cpu"] }
+        });
+
+        assert!(check_group_orientation(&config).is_empty());
+    }
+
+    #[test]
+    fn test_invalid_group_orientation() {
+        let config = json!({
+            "group/hardware": { "orientation": "diagonal", "modules": ["cpu"] }
+        });
+
+        let warnings = check_group_orientation(&config);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("Invalid orientation"));
+    }
+
+    #[test]
+    fn test_group_orientation_multi_bar() {
+        let config = json!([
+            { "position": "top", "group/a": { "orientation": "horizontal" } },
+            { "name": "side", "position": "left", "group/b": { "orientation": "horizontal" } }
+        ]);
+
+        let warnings = check_group_orientation(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "group/b");
+        assert!(warnings[0].message.contains("bar 1 (side)"));
+    }
+
+    #[test]
+    fn test_overlay_exclusive_is_flagged() {
+        let config = json!({ "layer": "overlay", "exclusive": true });
+
+        let warnings = check_layer_settings(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "bar 0");
+        assert!(warnings[0].message.contains("fullscreen"));
+    }
+
+    #[test]
+    fn test_top_exclusive_is_safe() {
+        let config = json!({ "layer": "top", "exclusive": true });
+        assert!(check_layer_settings(&config).is_empty());
+    }
+
+    #[test]
+    fn test_layer_defaults_are_safe() {
+        assert!(check_layer_settings(&json!({})).is_empty());
+    }
+
+    #[test]
+    fn test_exclusive_passthrough_is_flagged() {
+        let config = json!([{ "layer": "top", "passthrough": true }]);
+
+        let warnings = check_layer_settings(&config);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("clicks pass through"));
+    }
+
+    #[test]
+    fn test_unbalanced_placeholder() {
+        let config = json!({
+            "battery": {
+                "format": "{capacity}% {icon}",
+                "format-alt": "{time",
+                "tooltip-format": "{percentage%"
+            }
+        });
+
+        let warnings = check_format_placeholders(&config);
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].key.as_deref(), Some("format-alt"));
+        assert_eq!(warnings[1].key.as_deref(), Some("tooltip-format"));
+        assert!(warnings[1].message.contains("never closed"));
+    }
+
+    #[test]
+    fn test_escaped_braces_are_valid() {
+        let config = json!({
+            "clock": {
+                "format": "{{ {:%H:%M} }}",
+                "tooltip-format": "<tt>{calendar}</tt>"
+            },
+            "custom/json": { "format": "{{\"text\": \"{}\"}}" }
+        });
+
+        assert!(check_format_placeholders(&config).is_empty());
+    }
+
+    #[test]
+    fn test_stray_closing_brace() {
+        let config = json!({ "cpu": { "format-warning": "{usage}}% " } });
+
+        let warnings = check_format_placeholders(&config);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].message.contains("unmatched '}'"));
+    }
+
+    #[test]
+    fn test_format_icons_are_not_format_strings() {
+        let config = json!({ "battery": { "format-icons": ["{", "}"] } });
+        assert!(check_format_placeholders(&config).is_empty());
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("tooltp", "tooltip"), 1);
+        assert_eq!(edit_distance("format", "format"), 0);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+
+    #[test]
+    fn test_misspelled_option_is_flagged() {
+        let config = json!({
+            "clock": { "format": "{:%H:%M}", "tooltp": false, "timezone": "UTC" },
+            "battery#bat2": { "bat": "BAT2", "format-charging": "{capacity}%" }
+        });
+
+        let warnings = check_unknown_keys(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "clock");
+        assert_eq!(warnings[0].key.as_deref(), Some("tooltp"));
+        assert!(warnings[0].message.contains("did you mean \"tooltip\""));
+    }
+
+    #[test]
+    fn test_unknown_key_without_suggestion() {
+        let config = json!({ "tray": { "colour-scheme": "dark" } });
+
+        let warnings = check_unknown_keys(&config);
+        assert_eq!(warnings.len(), 1);
+        assert!(!warnings[0].message.contains("did you mean"));
+    }
+
+    #[test]
+    fn test_custom_and_unknown_modules_are_skipped() {
+        let config = json!({
+            "custom/weather": { "exec": "weather.sh", "anything": 1 },
+            "cffi/plugin": { "whatever": true },
+            "batery": { "tooltp": true }
+        });
+        assert!(check_unknown_keys(&config).is_empty());
+    }
+
+    #[test]
+    fn test_cpu_format_icons_length_mismatch() {
+        let config = json!({
+            "cpu": {
+                "format": "{icon0}{icon1}{icon2}{icon3} {usage}%",
+                "format-icons": ["▁", "▂", "▃", "▄", "▅", "▆", "▇", "█"]
+            }
+        });
+
+        let warnings = check_cpu_format_icons(&config, 4);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "cpu");
+        assert_eq!(warnings[0].key.as_deref(), Some("format-icons"));
+        assert_eq!(warnings[0].message, "8 entries for 4 cores");
+    }
+
+    #[test]
+    fn test_cpu_format_icons_length_matches() {
+        let config = json!({
+            "cpu#cores": {
+                "format-alt": "{icon0}{icon1}",
+                "format-icons": ["a", "b"]
+            },
+            // Aggregate usage only: the array is a plain usage ramp
+            "cpu": { "format": "{icon} {usage}%", "format-icons": ["▁", "▂", "▃"] },
+            // Per-core usage without per-core icons doesn't index the array
+            "cpu#usage": { "format": "{usage0}% {usage1}% {icon}", "format-icons": ["▁", "▂", "▃"] }
+        });
+
+        assert!(check_cpu_format_icons(&config, 2).is_empty());
+    }
+
+    #[test]
+    fn test_max_length_zero() {
+        let config = json!({ "hyprland/window": { "max-length": 0 } });
+
+        let warnings = check_length_limits(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].key.as_deref(), Some("max-length"));
+        assert!(warnings[0].message.contains("hides the module"));
+    }
+
+    #[test]
+    fn test_max_length_less_than_min_length() {
+        let config = json!([
+            { "clock": { "min-length": 10, "max-length": 5 } },
+            { "cpu": { "min-length": 3, "max-length": 8 } }
+        ]);
+
+        let warnings = check_length_limits(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "clock");
+        assert!(warnings[0].message.contains("max-length (5) is less than min-length (10)"));
+    }
+
+    #[test]
+    fn test_negative_length() {
+        let config = json!({ "memory": { "min-length": -2, "max-length": 1.5 } });
+
+        let warnings = check_length_limits(&config);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings.iter().all(|w| w.message.contains("non-negative integer")));
+    }
+
+    #[test]
+    fn test_bars_differing_in_height() {
+        let config = json!([
+            { "name": "main", "height": 30, "spacing": 4 },
+            { "name": "secondary", "height": 24, "spacing": 4, "margin-top": 2 }
+        ]);
+
+        let differences = compare_bar_dimensions(&config);
+        assert_eq!(differences.len(), 2);
+        assert_eq!(differences[0].field, "height");
+        assert_eq!(differences[0].values, vec![Some(json!(30)), Some(json!(24))]);
+        assert_eq!(differences[1].field, "margin-top");
+        assert_eq!(differences[1].values, vec![None, Some(json!(2))]);
+    }
+
+    #[test]
+    fn test_uniform_or_single_bar_has_no_differences() {
+        let uniform = json!([{ "height": 30 }, { "height": 30 }]);
+        assert!(compare_bar_dimensions(&uniform).is_empty());
+
+        let single = json!({ "height": 30 });
+        assert!(compare_bar_dimensions(&single).is_empty());
+    }
+
+    #[test]
+    fn test_bars_on_different_layers() {
+        let config = json!([
+            { "name": "main", "layer": "top" },
+            { "name": "osd", "layer": "overlay" }
+        ]);
+
+        let difference = compare_bar_layers(&config).unwrap();
+        assert_eq!(difference.field, "layer");
+        assert_eq!(difference.values, vec![Some(json!("top")), Some(json!("overlay"))]);
+    }
+
+    #[test]
+    fn test_default_layer_matches_bottom() {
+        let config = json!([{ "layer": "bottom" }, {}]);
+        assert!(compare_bar_layers(&config).is_none());
+        assert!(compare_bar_layers(&json!({ "layer": "top" })).is_none());
+    }
+
+    #[test]
+    fn test_workspace_icons_against_live_workspaces() {
+        // Captured from `hyprctl -j workspaces`
+        let live = crate::system::hyprland::parse_workspaces(
+            r#"[{"id": 1, "name": "1"}, {"id": 2, "name": "2"}, {"id": 3, "name": "3"}]"#,
+        )
+        .unwrap();
+        let names: Vec<String> = live.into_iter().map(|w| w.name).collect();
+
+        let config = json!({
+            "hyprland/workspaces": {
+                "format": "{icon}",
+                "format-icons": { "1": "", "2": "", "9": "", "urgent": "" }
+            }
+        });
+
+        let warnings = check_workspace_icons(&config, "hyprland/workspaces", &names);
+        let messages: Vec<_> = warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Icon for workspace \"9\" which doesn't exist",
+                "Workspace \"3\" has no icon and there is no default",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_workspace_icons_with_default() {
+        let config = json!({
+            "sway/workspaces": { "format-icons": { "1": "a", "default": "b" } },
+            "hyprland/workspaces": { "format-icons": { "7": "x" } }
+        });
+        let names = vec!["1".to_string(), "2".to_string()];
+
+        assert!(check_workspace_icons(&config, "sway/workspaces", &names).is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_click_actions() {
+        let config = json!([
+            {
+                "custom/music": { "on-click": "playerctl play-pause" },
+                "mpris": { "on-click": "playerctl play-pause" },
+                "pulseaudio": { "on-click": "pavucontrol" },
+                "custom/noop": { "on-click": "" },
+                "custom/other-noop": { "on-click": "true" },
+                "custom/also-noop": { "on-click": "true" }
+            },
+            { "mpris": { "on-click": "playerctl play-pause" } }
+        ]);
+
+        assert_eq!(
+            find_duplicate_click_actions(&config),
+            vec![DuplicateClickAction {
+                command: "playerctl play-pause".to_string(),
+                modules: vec!["custom/music".to_string(), "mpris".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_drawer_on_non_group_and_empty_group() {
+        let config = json!({
+            "clock": { "drawer": { "transition-duration": 500 } },
+            "group/power": {
+                "drawer": { "children-class": "power-child" },
+                "modules": ["custom/power", "custom/reboot"]
+            },
+            "group/empty": { "drawer": {} },
+            "group/none": { "modules": [] }
+        });
+
+        let warnings = check_group_drawers(&config);
+        let found: Vec<_> = warnings
+            .iter()
+            .map(|w| (w.target.as_str(), w.key.as_deref()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                ("clock", Some("drawer")),
+                ("group/empty", Some("modules")),
+                ("group/none", Some("modules")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reload_style_on_change() {
+        let config = json!([
+            { "reload_style_on_change": true, "clock": { "reload_style_on_change": true } },
+            { "reload_style_on_change": "yes" }
+        ]);
+
+        let warnings = check_reload_options(&config);
+        let targets: Vec<_> = warnings.iter().map(|w| w.target.as_str()).collect();
+        assert_eq!(targets, vec!["bar 0", "clock", "bar 1", "config"]);
+        assert!(warnings[0].message.contains("SIGUSR2"));
+        assert!(warnings[3].message.starts_with("Only 1 of 2 bars"));
+
+        assert!(check_reload_options(&json!({ "reload_style_on_change": false })).is_empty());
+    }
+
+    #[test]
+    fn test_network_interfaces() {
+        let interfaces = vec!["enp3s0".to_string(), "lo".to_string(), "wlan0".to_string()];
+        let config = json!({
+            "network": { "interface": "wl*" },
+            "network#wired": { "interface": "eth0" },
+            "network#any": { "format": "{ifname}" }
+        });
+
+        let warnings = check_network_interfaces(&config, &interfaces);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "network#wired");
+        assert_eq!(
+            warnings[0].message,
+            "No network interface matches \"eth0\" (available: enp3s0, lo, wlan0)"
+        );
+    }
+
+    #[test]
+    fn test_one_directional_scroll() {
+        let config = json!({
+            "pulseaudio": { "on-scroll-up": "pamixer -i 5" },
+            "backlight": { "on-scroll-up": "light -A 5", "on-scroll-down": "light -U 5" }
+        });
+
+        let warnings = check_scroll_actions(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "pulseaudio");
+        assert_eq!(warnings[0].key.as_deref(), Some("on-scroll-down"));
+    }
+
+    #[test]
+    fn test_tooltip_fields() {
+        let config = json!({
+            "battery": {
+                "tooltip-format": "{timeleft} left at {capacity}%",
+                "tooltip-format-charging": "{timeto} until full"
+            },
+            "cpu": { "tooltip-format": "{usage0} {usage1} {load:.2f}" },
+            "clock": { "tooltip-format": "{:%H:%M} {{literal}}" }
+        });
+
+        let warnings = check_tooltip_fields(&config);
+        let messages: Vec<_> = warnings.iter().map(|w| w.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec![
+                "Unknown placeholder {timeleft} for battery \
+                 (available: capacity, power, icon, time, timeTo, cycles, health)",
+                "Unknown placeholder {timeto} for battery (did you mean {timeTo}?)",
+            ]
+        );
+        assert_eq!(warnings[1].key.as_deref(), Some("tooltip-format-charging"));
+    }
+
+    #[test]
+    fn test_clock_interval_without_seconds() {
+        let config = json!({
+            "clock": { "interval": 1, "format": "{:%H:%M}" },
+            "clock#fast": { "interval": 1, "format": "{:%H:%M:%S}" },
+            "clock#alt": { "interval": 1, "format": "{:%H:%M}", "format-alt": "{:%T}" },
+            "clock#default": { "interval": 60 }
+        });
+
+        let warnings = check_clock_intervals(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "clock");
+        assert_eq!(warnings[0].key.as_deref(), Some("interval"));
+        assert!(warnings[0].message.contains("\"interval\": 60"));
+    }
+
+    #[test]
+    fn test_module_name_typos_and_missing_definitions() {
+        let config = json!({
+            "modules-left": ["hyprland/workspaces", "custom/weather", "custom/missing"],
+            "modules-center": ["clock#utc", "group/hardware"],
+            "modules-right": ["batery", "tray", "frobnicator"],
+            "custom/weather": { "exec": "weather.sh" },
+            "group/hardware": { "modules": ["cpu", "memroy"] }
+        });
+
+        let issues = check_module_names(&config);
+        let modules: Vec<&str> = issues.iter().map(|i| i.module.as_str()).collect();
+        assert_eq!(modules, vec!["custom/missing", "batery", "frobnicator", "memroy"]);
+        assert_eq!(issues[0].issue, "custom/missing has no definition in bar 0");
+        assert_eq!(issues[1].issue, "Unknown module type batery; did you mean battery?");
+        assert_eq!(issues[2].issue, "Unknown module type frobnicator");
+        assert!(issues[3].issue.contains("did you mean memory?"));
+    }
+
+    #[test]
+    fn test_mpris_players_against_bus() {
+        let config = json!({
+            "mpris": { "player": "spotify", "ignored-players": ["firefox", "chromium"] },
+            "mpris#podcasts": { "player": "gpodder" }
+        });
+        let active = vec!["firefox.instance_1_42".to_string(), "spotify".to_string()];
+
+        let report = check_mpris_players(&config, &active);
+        assert_eq!(report.active_players, active);
+        let statuses: Vec<_> = report
+            .configured
+            .iter()
+            .map(|c| (c.module.as_str(), c.key.as_str(), c.player.as_str(), c.active))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("mpris", "player", "spotify", true),
+                ("mpris", "ignored-players", "firefox", true),
+                ("mpris", "ignored-players", "chromium", false),
+                ("mpris#podcasts", "player", "gpodder", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sway_only_features_on_hyprland() {
+        let config = json!([
+            { "name": "main", "ipc": true, "mode": "hide", "layer": "top" },
+            { "mode": "dock", "ipc": false }
+        ]);
+
+        let warnings = check_compositor_features(&config, "hyprland");
+        assert_eq!(warnings.len(), 2);
+        assert_eq!(warnings[0].target, "bar 0 (main)");
+        assert_eq!(warnings[0].key.as_deref(), Some("ipc"));
+        assert_eq!(warnings[1].key.as_deref(), Some("mode"));
+        assert!(warnings[1].message.contains("\"hide\" is not supported on hyprland"));
+
+        assert!(check_compositor_features(&config, "Sway").is_empty());
+        assert!(check_compositor_features(&config, "unknown").is_empty());
+    }
+
+    #[test]
+    fn test_format_icons_empty_and_mixed_arrays() {
+        let config = json!({
+            "backlight": { "format-icons": [] },
+            "pulseaudio": { "format-icons": ["", 2, null] },
+            "battery": { "format-icons": { "charging": "", "default": ["", ""] } },
+            "cpu": { "format-icons": { "default": [], "high": true } }
+        });
+
+        let warnings = check_format_icon_types(&config);
+        let messages: Vec<(&str, &str)> =
+            warnings.iter().map(|w| (w.target.as_str(), w.message.as_str())).collect();
+        assert_eq!(
+            messages,
+            vec![
+                ("backlight", "format-icons is an empty array"),
+                ("pulseaudio", "format-icons mixes strings with other values (at index 1, 2)"),
+                ("cpu", "format-icons entry \"default\" is an empty array"),
+                ("cpu", "format-icons entry \"high\" is true, not a string or array"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_valid_format_icons_map() {
+        let config = json!({
+            "network": { "format-icons": { "wifi": "", "ethernet": "" } },
+            "clock": { "format-icons": "" }
+        });
+        assert!(check_format_icon_types(&config).is_empty());
+    }
+
+    #[test]
+    fn test_identical_format_alt() {
+        let config = json!({
+            "clock": { "format": "{:%H:%M}", "format-alt": "{:%H:%M}" },
+            "battery": { "format": "{capacity}%", "format-alt": "{time}" },
+            "memory": { "format-alt": "{used}" }
+        });
+
+        let warnings = check_format_alt(&config);
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].target, "clock");
+        assert_eq!(warnings[0].key.as_deref(), Some("format-alt"));
+    }
+}
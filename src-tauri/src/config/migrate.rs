@@ -0,0 +1,228 @@
+// ============================================================================
+// COMPOSITOR MIGRATION
+// ============================================================================
+//
+// Rewrites compositor-specific module names (`sway/*` ↔ `hyprland/*`) in
+// bar sections, group members and module definitions. Modules without an
+// equivalent on the target compositor are left as they are.
+
+use crate::config::catalog::module_type;
+use crate::config::{bars_mut, MODULE_SECTIONS};
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+
+/// Equivalent module types, as (sway, hyprland) pairs
+const MODULE_EQUIVALENTS: [(&str, &str); 4] = [
+    ("sway/workspaces", "hyprland/workspaces"),
+    ("sway/window", "hyprland/window"),
+    ("sway/language", "hyprland/language"),
+    ("sway/mode", "hyprland/submap"),
+];
+
+/// Compositors modules can be migrated between
+const MIGRATABLE_COMPOSITORS: [&str; 2] = ["sway", "hyprland"];
+
+/// A module renamed by a migration
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleRename {
+    /// Name before the migration (e.g. `sway/workspaces#main`)
+    pub old_name: String,
+    /// Name after the migration (e.g. `hyprland/workspaces#main`)
+    pub new_name: String,
+    /// Where the name was replaced (e.g. `bar 0: modules-left`)
+    pub locations: Vec<String>,
+}
+
+/// The outcome of a migration
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Migration {
+    /// Renamed modules, sorted by old name
+    pub changes: Vec<ModuleRename>,
+    /// The migrated config
+    pub config: Value,
+}
+
+/// Map a module name to its equivalent on the target compositor,
+/// keeping any `#instance` suffix
+fn migrated_name(name: &str, from: &str) -> Option<String> {
+    let base = module_type(name);
+    let suffix = &name[base.len()..];
+
+    MODULE_EQUIVALENTS.iter().find_map(|(sway, hyprland)| match from {
+        "sway" if *sway == base => Some(format!("{}{}", hyprland, suffix)),
+        "hyprland" if *hyprland == base => Some(format!("{}{}", sway, suffix)),
+        _ => None,
+    })
+}
+
+/// Rename the migratable entries of a module list in place
+fn migrate_list(
+    list: Option<&mut Value>,
+    from: &str,
+    location: &str,
+    renames: &mut BTreeMap<String, ModuleRename>,
+) {
+    let Some(Value::Array(items)) = list else {
+        return;
+    };
+
+    for item in items {
+        let Some(name) = item.as_str() else {
+            continue;
+        };
+        if let Some(new_name) = migrated_name(name, from) {
+            record(renames, name, &new_name, location);
+            *item = Value::String(new_name);
+        }
+    }
+}
+
+/// Record one replacement of `old_name`
+fn record(renames: &mut BTreeMap<String, ModuleRename>, old_name: &str, new_name: &str, location: &str) {
+    renames
+        .entry(old_name.to_string())
+        .or_insert_with(|| ModuleRename {
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+            locations: Vec::new(),
+        })
+        .locations
+        .push(location.to_string());
+}
+
+/// Migrate one bar's sections, group members and definitions
+fn migrate_bar(
+    bar: &mut Map<String, Value>,
+    label: &str,
+    from: &str,
+    renames: &mut BTreeMap<String, ModuleRename>,
+) {
+    for section in MODULE_SECTIONS {
+        migrate_list(bar.get_mut(section), from, &format!("{}: {}", label, section), renames);
+    }
+
+    let groups: Vec<String> = bar.keys().filter(|k| k.starts_with("group/")).cloned().collect();
+    for group in groups {
+        if let Some(Value::Object(module)) = bar.get_mut(&group) {
+            migrate_list(module.get_mut("modules"), from, &format!("{}: {}", label, group), renames);
+        }
+    }
+
+    let definitions: Vec<(String, String)> = bar
+        .iter()
+        .filter(|(_, value)| value.is_object())
+        .filter_map(|(name, _)| migrated_name(name, from).map(|new_name| (name.clone(), new_name)))
+        .collect();
+    for (name, new_name) in definitions {
+        // Keep an existing definition of the target module rather than clobber it
+        if bar.contains_key(&new_name) {
+            continue;
+        }
+        // Rename in place so the definition keeps its position in the file
+        if let Some(index) = bar.keys().position(|key| *key == name) {
+            let definition = bar.shift_remove(&name).unwrap_or_default();
+            record(renames, &name, &new_name, &format!("{}: definition", label));
+            bar.shift_insert(index, new_name, definition);
+        }
+    }
+}
+
+/// Migrate compositor-specific modules from one compositor to the other
+///
+/// Nothing is written; the returned config carries the renamed modules.
+pub fn migrate_modules(config: &Value, from: &str, to: &str) -> Result<Migration> {
+    let from = from.to_lowercase();
+    let to = to.to_lowercase();
+    if !MIGRATABLE_COMPOSITORS.contains(&from.as_str())
+        || !MIGRATABLE_COMPOSITORS.contains(&to.as_str())
+        || from == to
+    {
+        return Err(AppError::Validation(format!(
+            "Cannot migrate modules from {} to {}; supported: {}",
+            from,
+            to,
+            MIGRATABLE_COMPOSITORS.join(" ↔ ")
+        )));
+    }
+
+    let mut config = config.clone();
+    let mut renames = BTreeMap::new();
+    for (index, bar) in bars_mut(&mut config).into_iter().enumerate() {
+        migrate_bar(bar, &format!("bar {}", index), &from, &mut renames);
+    }
+
+    Ok(Migration {
+        changes: renames.into_values().collect(),
+        config,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_preview_sway_to_hyprland() {
+        let config = json!({
+            "modules-left": ["sway/workspaces", "sway/mode", "sway/scratchpad"],
+            "modules-center": ["sway/window#title"],
+            "modules-right": ["group/info", "clock"],
+            "group/info": { "modules": ["sway/language", "battery"] },
+            "sway/workspaces": { "disable-scroll": true },
+            "sway/window#title": { "max-length": 50 }
+        });
+
+        let migration = migrate_modules(&config, "sway", "hyprland").unwrap();
+
+        let changes: Vec<_> = migration
+            .changes
+            .iter()
+            .map(|c| (c.old_name.as_str(), c.new_name.as_str(), c.locations.clone()))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("sway/language", "hyprland/language", vec!["bar 0: group/info".to_string()]),
+                ("sway/mode", "hyprland/submap", vec!["bar 0: modules-left".to_string()]),
+                (
+                    "sway/window#title",
+                    "hyprland/window#title",
+                    vec!["bar 0: modules-center".to_string(), "bar 0: definition".to_string()]
+                ),
+                (
+                    "sway/workspaces",
+                    "hyprland/workspaces",
+                    vec!["bar 0: modules-left".to_string(), "bar 0: definition".to_string()]
+                ),
+            ]
+        );
+
+        let migrated = &migration.config;
+        assert_eq!(
+            migrated["modules-left"],
+            json!(["hyprland/workspaces", "hyprland/submap", "sway/scratchpad"])
+        );
+        assert_eq!(migrated["group/info"]["modules"], json!(["hyprland/language", "battery"]));
+        assert_eq!(migrated["hyprland/window#title"]["max-length"], 50);
+        assert!(migrated.get("sway/workspaces").is_none());
+        // Renamed definitions stay where they were
+        let keys: Vec<&String> = migrated.as_object().unwrap().keys().collect();
+        assert_eq!(keys[4..], ["hyprland/workspaces", "hyprland/window#title"]);
+    }
+
+    #[test]
+    fn test_migration_rejects_unsupported_compositors() {
+        let config = json!({});
+        assert!(matches!(
+            migrate_modules(&config, "sway", "river"),
+            Err(AppError::Validation(_))
+        ));
+        assert!(matches!(
+            migrate_modules(&config, "sway", "sway"),
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -2,11 +2,32 @@
 // CONFIG MODULE
 // ============================================================================
 
+pub mod archive;
+pub mod backups;
+pub mod catalog;
+pub mod cleanup;
+pub mod css;
+pub mod edit;
+pub mod encoding;
+pub mod format;
+pub mod git;
+pub mod include;
+pub mod legacy;
+pub mod lint;
+pub mod migrate;
 pub mod parser;
+pub mod preview;
+pub mod quotes;
+pub mod scaffold;
+pub mod schema;
+pub mod snapshots;
+pub mod themes;
+pub mod watch;
 pub mod writer;
 
 use crate::error::{AppError, Result};
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::path::{Path, PathBuf};
 
 /// Raw Waybar configuration file content
@@ -16,6 +37,13 @@ pub struct WaybarConfigFile {
     pub content: String,
     /// Absolute path to the config file
     pub path: String,
+    /// Non-fatal problems found while loading (e.g. duplicate keys)
+    #[serde(default)]
+    pub warnings: Vec<String>,
+    /// Whether the config pulls in other files with `include`
+    /// Saving writes only this file, so included modules aren't part of it
+    #[serde(default)]
+    pub has_includes: bool,
 }
 
 /// Configuration file paths
@@ -31,17 +59,36 @@ pub struct ConfigPaths {
 
 impl ConfigPaths {
     /// Get default Waybar configuration paths
+    /// Uses `$XDG_CONFIG_HOME/waybar`, or `$HOME/.config/waybar` when unset
+    #[allow(clippy::should_implement_trait)]
     pub fn default() -> Result<Self> {
-        let home = std::env::var("HOME")
-            .map_err(|_| AppError::Config("HOME environment variable not set".to_string()))?;
+        Self::from_env(|name| std::env::var(name).ok())
+    }
+
+    /// Build the default paths from environment variables read through `lookup`
+    pub fn from_env(lookup: impl Fn(&str) -> Option<String>) -> Result<Self> {
+        // The XDG spec says to ignore an empty value as if it were unset
+        let config_home = match lookup("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+            Some(dir) => dir,
+            None => {
+                let home = lookup("HOME").ok_or_else(|| {
+                    AppError::Config("HOME environment variable not set".to_string())
+                })?;
+                format!("{}/.config", home)
+            }
+        };
 
-        let config_dir = format!("{}/.config/waybar", home);
+        Ok(Self::in_dir(&format!("{}/waybar", config_home.trim_end_matches('/'))))
+    }
 
-        Ok(Self {
-            config_dir: config_dir.clone(),
+    /// Default file paths inside a given config directory
+    pub fn in_dir(config_dir: &str) -> Self {
+        let config_dir = config_dir.trim_end_matches('/');
+        Self {
+            config_dir: config_dir.to_string(),
             config_file: format!("{}/config.jsonc", config_dir),
             style_file: format!("{}/style.css", config_dir),
-        })
+        }
     }
 
     /// Detect actual config file path (tries multiple locations)
@@ -65,3 +112,287 @@ impl ConfigPaths {
         Ok(())
     }
 }
+
+/// A parsed config, by the shape of its root
+///
+/// Waybar accepts a single bar object or an array with one object per bar.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "bars")]
+pub enum ConfigDocument {
+    /// A single bar object at the root
+    Single(Value),
+    /// An array of bar objects, in config order
+    Multi(Vec<Value>),
+}
+
+impl ConfigDocument {
+    /// Classify a parsed config root
+    /// Fails when the root, or an element of a root array, isn't an object
+    pub fn from_value(value: Value) -> Result<Self> {
+        match value {
+            Value::Object(_) => Ok(Self::Single(value)),
+            Value::Array(items) => {
+                if let Some(index) = items.iter().position(|item| !item.is_object()) {
+                    return Err(AppError::Validation(format!(
+                        "Bar {} of the config is not an object",
+                        index
+                    )));
+                }
+                Ok(Self::Multi(items))
+            }
+            _ => Err(AppError::Validation(
+                "Config must be a bar object or an array of bar objects".to_string(),
+            )),
+        }
+    }
+
+    /// Number of bars defined
+    pub fn bar_count(&self) -> usize {
+        match self {
+            Self::Single(_) => 1,
+            Self::Multi(bars) => bars.len(),
+        }
+    }
+
+    /// The bar objects, in config order
+    pub fn bars(&self) -> Vec<&Value> {
+        match self {
+            Self::Single(bar) => vec![bar],
+            Self::Multi(bars) => bars.iter().collect(),
+        }
+    }
+
+    /// Turn the document back into a config value
+    pub fn into_value(self) -> Value {
+        match self {
+            Self::Single(bar) => bar,
+            Self::Multi(bars) => Value::Array(bars),
+        }
+    }
+}
+
+/// Get the bar definitions from a parsed config
+/// Waybar accepts either a single bar object or an array of bar objects
+pub fn bars(config: &Value) -> Vec<&Map<String, Value>> {
+    match config {
+        Value::Object(bar) => vec![bar],
+        Value::Array(items) => items.iter().filter_map(Value::as_object).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Mutable counterpart of [`bars`]
+pub fn bars_mut(config: &mut Value) -> Vec<&mut Map<String, Value>> {
+    match config {
+        Value::Object(bar) => vec![bar],
+        Value::Array(items) => items.iter_mut().filter_map(Value::as_object_mut).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Check if a bar is laid out vertically (docked to the left or right edge)
+/// Waybar defaults to `"position": "top"` when unset
+pub fn is_vertical_bar(bar: &Map<String, Value>) -> bool {
+    matches!(
+        bar.get("position").and_then(Value::as_str),
+        Some("left") | Some("right")
+    )
+}
+
+/// Iterate over the module definition blocks of a bar
+/// Every object-valued key in a bar is a module definition (e.g. `"clock": {...}`)
+pub fn module_definitions(
+    bar: &Map<String, Value>,
+) -> impl Iterator<Item = (&String, &Map<String, Value>)> {
+    bar.iter()
+        .filter_map(|(name, value)| value.as_object().map(|module| (name, module)))
+}
+
+/// Bar keys listing the modules shown in each section
+pub const MODULE_SECTIONS: [&str; 3] = ["modules-left", "modules-center", "modules-right"];
+
+/// List the modules placed on any bar, sorted and deduplicated
+///
+/// Includes modules in the three bar sections and, recursively, the members
+/// of `group/*` modules. Definitions that aren't placed anywhere are not listed.
+pub fn list_config_modules(config: &Value) -> Vec<String> {
+    fn strings(value: Option<&Value>) -> Vec<String> {
+        value
+            .and_then(Value::as_array)
+            .map(|items| items.iter().filter_map(Value::as_str).map(str::to_string).collect())
+            .unwrap_or_default()
+    }
+
+    let mut modules = std::collections::BTreeSet::new();
+
+    for bar in bars(config) {
+        let mut pending: Vec<String> = MODULE_SECTIONS
+            .iter()
+            .flat_map(|section| strings(bar.get(*section)))
+            .collect();
+
+        while let Some(name) = pending.pop() {
+            if name.starts_with("group/") && !modules.contains(&name) {
+                pending.extend(strings(bar.get(&name).and_then(|group| group.get("modules"))));
+            }
+            modules.insert(name);
+        }
+    }
+
+    modules.into_iter().collect()
+}
+
+/// How [`merge_configs_with`] combines two arrays under the same key
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ArrayStrategy {
+    /// The overlay's array replaces the base's
+    #[default]
+    Replace,
+    /// The overlay's items are appended to the base's
+    Concat,
+}
+
+/// Deep-merge `overlay` into `base`, replacing arrays
+/// See [`merge_configs_with`]
+pub fn merge_configs(base: Value, overlay: Value) -> Value {
+    merge_configs_with(base, overlay, ArrayStrategy::Replace)
+}
+
+/// Deep-merge `overlay` into `base`
+///
+/// Objects are merged key by key, recursively; keys keep their position in
+/// `base` and new keys are appended. Arrays are combined per `strategy`. Any
+/// other conflict is won by the overlay.
+pub fn merge_configs_with(base: Value, overlay: Value, strategy: ArrayStrategy) -> Value {
+    match (base, overlay) {
+        (Value::Object(mut base), Value::Object(overlay)) => {
+            for (key, value) in overlay {
+                match base.get_mut(&key) {
+                    Some(existing) => {
+                        let current = existing.take();
+                        *existing = merge_configs_with(current, value, strategy);
+                    }
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+            Value::Object(base)
+        }
+        (Value::Array(mut base), Value::Array(overlay)) if strategy == ArrayStrategy::Concat => {
+            base.extend(overlay);
+            Value::Array(base)
+        }
+        (_, overlay) => overlay,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_merge_configs() {
+        let base = json!({
+            "layer": "top",
+            "modules-right": ["clock"],
+            "clock": { "format": "{:%H:%M}", "tooltip": true }
+        });
+        let overlay = json!({
+            "modules-right": ["battery"],
+            "clock": { "tooltip": false },
+            "battery": { "interval": 30 }
+        });
+
+        let merged = merge_configs(base.clone(), overlay.clone());
+        assert_eq!(
+            merged,
+            json!({
+                "layer": "top",
+                "modules-right": ["battery"],
+                "clock": { "format": "{:%H:%M}", "tooltip": false },
+                "battery": { "interval": 30 }
+            })
+        );
+        let keys: Vec<&String> = merged.as_object().unwrap().keys().collect();
+        assert_eq!(keys, vec!["layer", "modules-right", "clock", "battery"]);
+
+        let concatenated = merge_configs_with(base, overlay, ArrayStrategy::Concat);
+        assert_eq!(concatenated["modules-right"], json!(["clock", "battery"]));
+    }
+
+    #[test]
+    fn test_merge_configs_overlay_wins_on_type_conflict() {
+        assert_eq!(merge_configs(json!({ "a": { "b": 1 } }), json!({ "a": 2 })), json!({ "a": 2 }));
+        assert_eq!(merge_configs(json!([1]), json!({ "a": 1 })), json!({ "a": 1 }));
+    }
+
+    #[test]
+    fn test_list_config_modules() {
+        let config = json!([
+            {
+                "modules-left": ["hyprland/workspaces", "group/hardware"],
+                "modules-right": ["clock"],
+                "group/hardware": { "modules": ["cpu", "memory"] },
+                "battery": {}
+            },
+            { "modules-center": ["clock", "custom/weather"] }
+        ]);
+
+        assert_eq!(
+            list_config_modules(&config),
+            vec!["clock", "cpu", "custom/weather", "group/hardware", "hyprland/workspaces", "memory"]
+        );
+    }
+
+    #[test]
+    fn test_config_paths_use_xdg_config_home() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let xdg = temp_dir.path().to_str().unwrap().to_string();
+
+        let paths = ConfigPaths::from_env(|name| match name {
+            "XDG_CONFIG_HOME" => Some(xdg.clone()),
+            "HOME" => Some("/home/al".to_string()),
+            _ => None,
+        })
+        .unwrap();
+
+        assert_eq!(paths.config_dir, format!("{}/waybar", xdg));
+        assert_eq!(paths.config_file, format!("{}/waybar/config.jsonc", xdg));
+        assert_eq!(paths.style_file, format!("{}/waybar/style.css", xdg));
+    }
+
+    #[test]
+    fn test_config_paths_fall_back_to_home() {
+        for xdg in [None, Some("")] {
+            let paths = ConfigPaths::from_env(|name| match name {
+                "XDG_CONFIG_HOME" => xdg.map(str::to_string),
+                "HOME" => Some("/home/al".to_string()),
+                _ => None,
+            })
+            .unwrap();
+            assert_eq!(paths.config_dir, "/home/al/.config/waybar");
+        }
+
+        assert!(ConfigPaths::from_env(|_| None).is_err());
+    }
+
+    #[test]
+    fn test_config_document_shapes() {
+        let single = ConfigDocument::from_value(json!({ "height": 30 })).unwrap();
+        assert!(matches!(single, ConfigDocument::Single(_)));
+        assert_eq!(single.bar_count(), 1);
+
+        let multi = json!([{ "name": "top" }, { "name": "bottom" }]);
+        let document = ConfigDocument::from_value(multi.clone()).unwrap();
+        assert_eq!(document.bar_count(), 2);
+        assert_eq!(document.bars()[1]["name"], "bottom");
+        assert_eq!(document.into_value(), multi);
+
+        assert_eq!(ConfigDocument::from_value(json!([])).unwrap().bar_count(), 0);
+        assert!(ConfigDocument::from_value(json!([{}, 3])).is_err());
+        assert!(ConfigDocument::from_value(json!("bar")).is_err());
+    }
+}
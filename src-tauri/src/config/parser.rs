@@ -3,16 +3,63 @@
 // ============================================================================
 
 use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use std::ops::Range;
+use std::path::Path;
+use std::time::Instant;
+
+/// JSONC content with its comments removed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StrippedJsonc {
+    /// The content without comments
+    pub content: String,
+    /// Byte ranges of the removed comments in the original content, in order
+    /// (a `//` comment's range excludes its terminating `\n` or `\r\n`, which
+    /// is kept)
+    pub comments: Vec<Range<usize>>,
+    /// Whether a leading UTF-8 byte order mark was removed
+    pub bom: bool,
+}
+
+/// Byte order mark some editors write at the start of UTF-8 files
+const BOM: char = '\u{FEFF}';
 
 /// Strip JSONC comments from JSON content
-/// Handles both single-line (//) and multi-line (/* */) comments
+/// Handles both single-line (//) and multi-line (/* */) comments, and drops
+/// a leading byte order mark
 pub fn strip_jsonc_comments(content: &str) -> String {
+    strip_jsonc_comments_ext(content, false)
+}
+
+/// Strip JSONC comments, optionally also `#` line comments
+///
+/// `#` comments aren't JSONC, but configs run through a preprocessor may
+/// keep them; `allow_hash` strips them too. A `#` inside a string (e.g. a
+/// `"#fff"` color) is always kept.
+pub fn strip_jsonc_comments_ext(content: &str, allow_hash: bool) -> String {
+    strip_comments(content, allow_hash).content
+}
+
+/// Strip JSONC comments, also returning where each comment was
+pub fn strip_jsonc_comments_mapped(content: &str) -> StrippedJsonc {
+    strip_comments(content, false)
+}
+
+/// Strip comments, with `#` line comments too when `allow_hash` is set
+fn strip_comments(content: &str, allow_hash: bool) -> StrippedJsonc {
     let mut result = String::with_capacity(content.len());
-    let mut chars = content.chars().peekable();
+    let mut comments = Vec::new();
+    let mut chars = content.char_indices().peekable();
+    // Only a BOM at the very start is an encoding marker; elsewhere it's content
+    let bom = content.starts_with(BOM);
+    if bom {
+        chars.next();
+    }
     let mut in_string = false;
     let mut escape_next = false;
 
-    while let Some(ch) = chars.next() {
+    while let Some((start, ch)) = chars.next() {
         // Handle string literals (preserve everything inside strings)
         if ch == '"' && !escape_next {
             in_string = !in_string;
@@ -31,29 +78,41 @@ pub fn strip_jsonc_comments(content: &str) -> String {
         // Skip comments only outside of strings
         if !in_string {
             // Single-line comment
-            if ch == '/' && chars.peek() == Some(&'/') {
-                chars.next(); // consume second /
+            let double_slash = ch == '/' && chars.peek().map(|&(_, c)| c) == Some('/');
+            if double_slash || (allow_hash && ch == '#') {
+                if double_slash {
+                    chars.next(); // consume second /
+                }
                 // Skip until end of line
-                for c in chars.by_ref() {
+                let mut end = content.len();
+                let mut prev = ch;
+                for (pos, c) in chars.by_ref() {
                     if c == '\n' {
-                        result.push(c); // preserve newline
+                        // Preserve the line ending, CRLF included
+                        end = if prev == '\r' { pos - 1 } else { pos };
+                        result.push_str(&content[end..=pos]);
                         break;
                     }
+                    prev = c;
                 }
+                comments.push(start..end);
                 continue;
             }
 
             // Multi-line comment
-            if ch == '/' && chars.peek() == Some(&'*') {
+            if ch == '/' && chars.peek().map(|&(_, c)| c) == Some('*') {
                 chars.next(); // consume *
                 // Skip until */
+                let mut end = content.len();
                 let mut prev = ' ';
-                for c in chars.by_ref() {
+                for (pos, c) in chars.by_ref() {
                     if prev == '*' && c == '/' {
+                        end = pos + 1;
                         break;
                     }
                     prev = c;
                 }
+                comments.push(start..end);
                 continue;
             }
         }
@@ -61,13 +120,218 @@ pub fn strip_jsonc_comments(content: &str) -> String {
         result.push(ch);
     }
 
-    result
+    StrippedJsonc {
+        content: result,
+        comments,
+        bom,
+    }
+}
+
+impl StrippedJsonc {
+    /// Map a byte offset in the stripped content back to the original
+    pub fn original_offset(&self, stripped_offset: usize) -> usize {
+        let mut removed = if self.bom { BOM.len_utf8() } else { 0 };
+        for span in &self.comments {
+            if stripped_offset < span.start - removed {
+                break;
+            }
+            removed += span.len();
+        }
+        stripped_offset + removed
+    }
+
+    /// Map a 1-based line/column of the stripped content to the 1-based
+    /// line/column (in characters) of the original content
+    pub fn original_position(&self, original: &str, line: usize, column: usize) -> (usize, usize) {
+        let line_start = self
+            .content
+            .split_inclusive('\n')
+            .take(line.saturating_sub(1))
+            .map(str::len)
+            .sum::<usize>();
+        let stripped_offset = (line_start + column.saturating_sub(1)).min(self.content.len());
+        let offset = self.original_offset(stripped_offset).min(original.len());
+
+        let before = &original[..offset];
+        let original_line = before.matches('\n').count() + 1;
+        let original_line_start = before.rfind('\n').map_or(0, |pos| pos + 1);
+        (original_line, before[original_line_start..].chars().count() + 1)
+    }
+}
+
+/// Kind of a [`JsoncToken`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenKind {
+    /// A double-quoted string, quotes included (to the end of the content
+    /// when unterminated)
+    String,
+    /// A single-quoted literal closed on the same line, quotes included. Not
+    /// JSON, but lexed as a unit so its content isn't mistaken for structure
+    SingleQuoted,
+    /// A `//` comment (without its line ending) or a `/* */` comment
+    Comment,
+    /// One of `{ } [ ] , :`
+    Punct(u8),
+    /// Anything else up to the next whitespace or token: numbers, `true`,
+    /// `false`, `null`, or stray characters
+    Bare,
+}
+
+/// A token of JSONC content
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsoncToken {
+    pub kind: TokenKind,
+    /// Byte range in the content
+    pub span: Range<usize>,
+    /// 1-based line the token starts on
+    pub line: usize,
+}
+
+/// Split JSONC content into tokens, skipping whitespace
+///
+/// This is the one lexer behind the scanners that need more than the
+/// comment-free text (duplicate keys, reindenting, single quotes, comment
+/// carry-over). It never fails: invalid content still yields tokens, with
+/// an unterminated string or comment running to the end.
+pub fn tokenize_jsonc(content: &str) -> Vec<JsoncToken> {
+    let bytes = content.as_bytes();
+    let len = bytes.len();
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+
+    while i < len {
+        let start = i;
+        let kind = match bytes[i] {
+            b'\n' => {
+                line += 1;
+                i += 1;
+                continue;
+            }
+            byte if byte.is_ascii_whitespace() => {
+                i += 1;
+                continue;
+            }
+            b'"' => {
+                i += 1;
+                while i < len && bytes[i] != b'"' {
+                    i += if bytes[i] == b'\\' { 2 } else { 1 };
+                }
+                i = (i + 1).min(len);
+                TokenKind::String
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                i = content[i..].find('\n').map_or(len, |pos| i + pos);
+                // Like `strip_jsonc_comments_mapped`, leave a CRLF's `\r` out
+                if i > start + 2 && bytes[i - 1] == b'\r' {
+                    i -= 1;
+                }
+                TokenKind::Comment
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = content[i + 2..].find("*/").map_or(len, |pos| i + 2 + pos + 2);
+                TokenKind::Comment
+            }
+            b'\'' => {
+                let line_end = content[i..].find('\n').map_or(len, |pos| i + pos);
+                match content[i + 1..line_end].find('\'') {
+                    Some(pos) => {
+                        i += pos + 2;
+                        TokenKind::SingleQuoted
+                    }
+                    None => {
+                        i += 1;
+                        TokenKind::Bare
+                    }
+                }
+            }
+            punct @ (b'{' | b'}' | b'[' | b']' | b',' | b':') => {
+                i += 1;
+                TokenKind::Punct(punct)
+            }
+            _ => {
+                i += 1;
+                while i < len
+                    && !bytes[i].is_ascii_whitespace()
+                    && !matches!(bytes[i], b'"' | b'\'' | b'/' | b'{' | b'}' | b'[' | b']' | b',' | b':')
+                {
+                    i += 1;
+                }
+                TokenKind::Bare
+            }
+        };
+
+        tokens.push(JsoncToken { kind, span: start..i, line });
+        line += content[start..i].matches('\n').count();
+    }
+
+    tokens
 }
 
 /// Parse JSONC content and return parsed JSON value
+///
+/// Object keys keep the order they have in the file. Error locations refer
+/// to the original content, comments included.
 pub fn parse_jsonc(content: &str) -> Result<serde_json::Value> {
-    let stripped = strip_jsonc_comments(content);
-    serde_json::from_str(&stripped).map_err(|e| AppError::Parse(format!("Failed to parse JSON: {}", e)))
+    let stripped = strip_jsonc_comments_mapped(content);
+    serde_json::from_str(&stripped.content).map_err(|e| {
+        if e.line() == 0 {
+            return AppError::Parse(format!("Failed to parse JSON: {}", e));
+        }
+
+        let message = e.to_string();
+        let location = format!(" at line {} column {}", e.line(), e.column());
+        let reason = message.strip_suffix(&location).unwrap_or(&message);
+        let (line, column) = stripped.original_position(content, e.line(), e.column());
+        AppError::Parse(format!(
+            "Failed to parse JSON: {} at line {} column {}",
+            reason, line, column
+        ))
+    })
+}
+
+/// Find keys defined more than once in the same object
+///
+/// Returns each repeated key with the 1-based line of every definition
+/// after the first. Nested objects are checked independently, so the same
+/// key in two different objects is not a duplicate. Comments are skipped.
+pub fn find_duplicate_keys(content: &str) -> Vec<(String, usize)> {
+    /// An open `{` (keys seen so far, whether a key comes next) or `[`
+    enum Scope {
+        Object(HashSet<String>, bool),
+        Array,
+    }
+
+    let mut scopes: Vec<Scope> = Vec::new();
+    let mut duplicates = Vec::new();
+
+    for token in tokenize_jsonc(content) {
+        match token.kind {
+            TokenKind::String => {
+                if let Some(Scope::Object(keys, expecting_key @ true)) = scopes.last_mut() {
+                    *expecting_key = false;
+                    if let Ok(key) = serde_json::from_str::<String>(&content[token.span]) {
+                        if !keys.insert(key.clone()) {
+                            duplicates.push((key, token.line));
+                        }
+                    }
+                }
+            }
+            TokenKind::Punct(b'{') => scopes.push(Scope::Object(HashSet::new(), true)),
+            TokenKind::Punct(b'[') => scopes.push(Scope::Array),
+            TokenKind::Punct(b'}' | b']') => {
+                scopes.pop();
+            }
+            TokenKind::Punct(b',') => {
+                if let Some(Scope::Object(_, expecting_key)) = scopes.last_mut() {
+                    *expecting_key = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    duplicates
 }
 
 /// Validate that content is valid JSON
@@ -77,6 +341,102 @@ pub fn validate_json(content: &str) -> Result<()> {
         .map_err(|e| AppError::Validation(format!("Invalid JSON: {}", e)))
 }
 
+/// Upper bound for `benchmark_parse` iterations
+const MAX_BENCHMARK_ITERATIONS: u32 = 10_000;
+
+/// Timings of repeated config parses
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ParseBenchmark {
+    /// Number of timed parses
+    pub iterations: u32,
+    /// Fastest parse in microseconds
+    pub min_us: f64,
+    /// Slowest parse in microseconds
+    pub max_us: f64,
+    /// Mean parse time in microseconds
+    pub mean_us: f64,
+}
+
+/// Time parsing the config at `path`, include resolution included
+///
+/// A diagnostic for large modular configs; not used on the load/save path.
+/// The main file is read once, then parsed and resolved `iterations` times.
+pub fn benchmark_parse(path: &Path, iterations: u32) -> Result<ParseBenchmark> {
+    if iterations == 0 || iterations > MAX_BENCHMARK_ITERATIONS {
+        return Err(AppError::Validation(format!(
+            "Iterations must be between 1 and {}",
+            MAX_BENCHMARK_ITERATIONS
+        )));
+    }
+
+    let content = std::fs::read_to_string(path)?;
+    let mut timings = Vec::with_capacity(iterations as usize);
+
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let config = parse_jsonc(&content)?;
+        crate::config::include::resolve_includes(config, path)?;
+        timings.push(start.elapsed().as_secs_f64() * 1_000_000.0);
+    }
+
+    Ok(ParseBenchmark {
+        iterations,
+        min_us: timings.iter().copied().fold(f64::INFINITY, f64::min),
+        max_us: timings.iter().copied().fold(0.0, f64::max),
+        mean_us: timings.iter().sum::<f64>() / timings.len() as f64,
+    })
+}
+
+/// Write `value` as compact JSON with object keys sorted at every level
+fn write_canonical(value: &serde_json::Value, out: &mut String) {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(b.0));
+            out.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push_str(&serde_json::Value::String(key.clone()).to_string());
+                out.push(':');
+                write_canonical(value, out);
+            }
+            out.push('}');
+        }
+        serde_json::Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(']');
+        }
+        scalar => out.push_str(&scalar.to_string()),
+    }
+}
+
+/// Hash the canonical form of a config, ignoring comments and formatting
+///
+/// Keys are sorted at every level and whitespace is dropped before hashing,
+/// so configs that differ only in layout, comments or key order share a
+/// fingerprint. Bar order in multi-bar configs is significant. The hash is
+/// 64-bit FNV-1a, stable across builds, as a 16-digit hex string.
+pub fn config_fingerprint(content: &str) -> Result<String> {
+    const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+    const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut canonical = String::with_capacity(content.len());
+    write_canonical(&parse_jsonc(content)?, &mut canonical);
+
+    let hash = canonical
+        .bytes()
+        .fold(FNV_OFFSET, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME));
+    Ok(format!("{:016x}", hash))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -153,6 +513,40 @@ mod tests {
         assert!(output.contains("\"value\""));
     }
 
+    #[test]
+    fn test_strip_keeps_crlf_line_endings() {
+        let input = "{\r\n  // bar height\r\n  \"height\": 30 // px\r\n}\r\n";
+
+        let stripped = strip_jsonc_comments_mapped(input);
+        assert_eq!(stripped.content, "{\r\n  \r\n  \"height\": 30 \r\n}\r\n");
+        assert_eq!(&input[stripped.comments[0].clone()], "// bar height");
+        assert_eq!(&input[stripped.comments[1].clone()], "// px");
+    }
+
+    #[test]
+    fn test_strip_hash_comments_when_allowed() {
+        let input = "{\n# templated by make\n  \"color\": \"#fff\" # text color\n}";
+
+        let stripped = strip_jsonc_comments_ext(input, true);
+        assert_eq!(stripped, "{\n\n  \"color\": \"#fff\" \n}");
+        let parsed: serde_json::Value = serde_json::from_str(&stripped).unwrap();
+        assert_eq!(parsed["color"], "#fff");
+
+        // Standard JSONC leaves `#` alone
+        assert_eq!(strip_jsonc_comments(input), input);
+        assert_eq!(strip_jsonc_comments_ext(input, false), input);
+    }
+
+    #[test]
+    fn test_strip_reports_comment_spans() {
+        let input = "{\n  // lead\n  \"a\": 1, /* b */ \"c\": \"//x\"\n}";
+        let stripped = strip_jsonc_comments_mapped(input);
+
+        let spans: Vec<&str> = stripped.comments.iter().map(|r| &input[r.clone()]).collect();
+        assert_eq!(spans, vec!["// lead", "/* b */"]);
+        assert_eq!(stripped.content, strip_jsonc_comments(input));
+    }
+
     #[test]
     fn test_preserve_escaped_quotes_in_strings() {
         let input = r#"{"key": "value with \"quotes\""}"#;
@@ -253,6 +647,50 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_error_points_at_original_line() {
+        let input = "{\n  /* a\n     long\n     comment */ \"a\": 1,\n  \"b\": 2 \"c\": 3\n}";
+        match parse_jsonc(input) {
+            Err(AppError::Parse(msg)) => {
+                assert_eq!(msg, "Failed to parse JSON: expected `,` or `}` at line 5 column 10");
+            }
+            other => panic!("Expected Parse error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_parse_bom_prefixed_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.jsonc");
+        let saved = "\u{FEFF}// Saved with a BOM\n{ \"height\": 30, \"name\": \"\u{FEFF}bar\" }";
+        std::fs::write(&path, saved).unwrap();
+
+        let content = std::fs::read_to_string(&path).unwrap();
+        let value = parse_jsonc(&content).unwrap();
+        assert_eq!(value["height"], 30);
+        // A BOM inside a string is content and stays
+        assert_eq!(value["name"], "\u{FEFF}bar");
+
+        let stripped = strip_jsonc_comments_mapped(&content);
+        assert!(stripped.bom);
+        let brace = stripped.content.find('{').unwrap();
+        assert_eq!(stripped.original_offset(brace), content.find('{').unwrap());
+    }
+
+    #[test]
+    fn test_original_offset_skips_comments() {
+        let input = "{ /* x */ \"a\": 1 // y\n}";
+        let stripped = strip_jsonc_comments_mapped(input);
+        let quote = stripped.content.find('"').unwrap();
+
+        assert_eq!(stripped.original_offset(quote), input.find('"').unwrap());
+        assert_eq!(stripped.original_offset(0), 0);
+        assert_eq!(
+            stripped.original_offset(stripped.content.find('}').unwrap()),
+            input.find('}').unwrap()
+        );
+    }
+
     #[test]
     fn test_parse_jsonc_trailing_commas() {
         // JSONC typically allows trailing commas
@@ -349,4 +787,126 @@ mod tests {
         let output = strip_jsonc_comments(input);
         assert!(output.contains("*.txt"));
     }
+
+    #[test]
+    fn test_benchmark_parse() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("clock.jsonc"), r#"{ "clock": {} }"#).unwrap();
+        let path = temp_dir.path().join("config.jsonc");
+        std::fs::write(
+            &path,
+            r#"{
+                // Sample config
+                "include": ["clock.jsonc"],
+                "modules-left": ["clock", "cpu"],
+                "cpu": { "interval": 5, "format": "{usage}%" }
+            }"#,
+        )
+        .unwrap();
+
+        let benchmark = benchmark_parse(&path, 20).unwrap();
+        assert_eq!(benchmark.iterations, 20);
+        assert!(benchmark.min_us > 0.0);
+        assert!(benchmark.min_us <= benchmark.mean_us);
+        assert!(benchmark.mean_us <= benchmark.max_us);
+    }
+
+    #[test]
+    fn test_benchmark_parse_rejects_zero_iterations() {
+        let result = benchmark_parse(Path::new("/nonexistent/config.jsonc"), 0);
+        assert!(matches!(result, Err(AppError::Validation(_))));
+    }
+
+    // ========================================
+    // Fingerprint Tests
+    // ========================================
+
+    #[test]
+    fn test_fingerprint_ignores_formatting() {
+        let compact = r#"[{"height":30,"modules-left":["clock","tray"]},{"layer":"top"}]"#;
+        let formatted = r#"[
+            // main bar
+            {
+                "modules-left": [ "clock", "tray" ],
+                "height": 30
+            },
+            /* second bar */ { "layer": "top" }
+        ]"#;
+
+        let fingerprint = config_fingerprint(compact).unwrap();
+        assert_eq!(fingerprint.len(), 16);
+        assert_eq!(fingerprint, config_fingerprint(formatted).unwrap());
+    }
+
+    #[test]
+    fn test_fingerprint_detects_functional_changes() {
+        let base = config_fingerprint(r#"[{"height": 30}, {"height": 20}]"#).unwrap();
+
+        assert_ne!(base, config_fingerprint(r#"[{"height": 31}, {"height": 20}]"#).unwrap());
+        assert_ne!(base, config_fingerprint(r#"[{"height": 20}, {"height": 30}]"#).unwrap());
+        assert!(config_fingerprint("{ invalid").is_err());
+    }
+
+    // ========================================
+    // Duplicate Key Tests
+    // ========================================
+
+    #[test]
+    fn test_find_duplicate_keys() {
+        let input = r#"{
+    "clock": { "format": "{:%H}", "interval": 1 },
+    // "clock": commented out, not a duplicate
+    "battery": { "format": "{capacity}", "interval": 5 },
+    "modules-left": ["clock", "clock"],
+    "clock": {
+        "format": "a",
+        "format": "b"
+    }
+}"#;
+
+        assert_eq!(
+            find_duplicate_keys(input),
+            vec![("clock".to_string(), 6), ("format".to_string(), 8)]
+        );
+    }
+
+    #[test]
+    fn test_tokenize_jsonc() {
+        let input = "{ // note\r\n  \"a\": [1, 'x'], /* b\n */ \"c\\\"\": true }";
+        let tokens: Vec<_> = tokenize_jsonc(input)
+            .into_iter()
+            .map(|token| (token.kind, &input[token.span], token.line))
+            .collect();
+
+        assert_eq!(
+            tokens,
+            vec![
+                (TokenKind::Punct(b'{'), "{", 1),
+                (TokenKind::Comment, "// note", 1),
+                (TokenKind::String, "\"a\"", 2),
+                (TokenKind::Punct(b':'), ":", 2),
+                (TokenKind::Punct(b'['), "[", 2),
+                (TokenKind::Bare, "1", 2),
+                (TokenKind::Punct(b','), ",", 2),
+                (TokenKind::SingleQuoted, "'x'", 2),
+                (TokenKind::Punct(b']'), "]", 2),
+                (TokenKind::Punct(b','), ",", 2),
+                (TokenKind::Comment, "/* b\n */", 2),
+                (TokenKind::String, "\"c\\\"\"", 3),
+                (TokenKind::Punct(b':'), ":", 3),
+                (TokenKind::Bare, "true", 3),
+                (TokenKind::Punct(b'}'), "}", 3),
+            ]
+        );
+
+        let unterminated = tokenize_jsonc("\"abc");
+        assert_eq!(unterminated.len(), 1);
+        assert_eq!(unterminated[0].span, 0..4);
+    }
+
+    #[test]
+    fn test_duplicate_keys_in_separate_bars() {
+        let input = r#"[{ "height": 30 }, { "height": 20, "layer": "top", "layer": "bottom" }]"#;
+        assert_eq!(find_duplicate_keys(input), vec![("layer".to_string(), 1)]);
+    }
 }
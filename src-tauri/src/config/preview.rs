@@ -0,0 +1,195 @@
+// ============================================================================
+// TEXTUAL BAR PREVIEW
+// ============================================================================
+//
+// Lightweight ASCII approximation of how Waybar lays out the modules of each
+// bar, for sanity-checking module ordering without launching Waybar.
+
+use crate::config::{bars, is_vertical_bar};
+use serde_json::{Map, Value};
+
+/// Inner width (in characters) of a horizontal bar preview
+pub const PREVIEW_WIDTH: usize = 78;
+
+/// Gap inserted between modules of the same section
+const MODULE_GAP: &str = "  ";
+
+/// Render every bar of a config as text, separated by blank lines
+pub fn render_ascii_preview(config: &Value) -> String {
+    bars(config)
+        .into_iter()
+        .enumerate()
+        .map(|(index, bar)| render_bar(index, bar))
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Get the module names of a `modules-*` section
+fn section(bar: &Map<String, Value>, key: &str) -> Vec<String> {
+    bar.get(key)
+        .and_then(Value::as_array)
+        .map(|modules| {
+            modules
+                .iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Render a single bar with a one-line header
+fn render_bar(index: usize, bar: &Map<String, Value>) -> String {
+    let position = bar.get("position").and_then(Value::as_str).unwrap_or("top");
+    let left = section(bar, "modules-left");
+    let center = section(bar, "modules-center");
+    let right = section(bar, "modules-right");
+
+    let mut header = format!("bar {}", index);
+    if let Some(name) = bar.get("name").and_then(Value::as_str) {
+        header.push_str(&format!(" ({})", name));
+    }
+
+    if is_vertical_bar(bar) {
+        header.push_str(&format!(" [{}, vertical]", position));
+        format!("{}\n{}", header, render_vertical(&left, &center, &right))
+    } else {
+        header.push_str(&format!(" [{}, horizontal]", position));
+        format!("{}\n{}", header, render_horizontal(&left, &center, &right))
+    }
+}
+
+/// Render a horizontal bar: left-aligned, centered and right-aligned sections
+/// on a single line. Falls back to `|`-separated sections when they don't fit.
+fn render_horizontal(left: &[String], center: &[String], right: &[String]) -> String {
+    let left = left.join(MODULE_GAP);
+    let center = center.join(MODULE_GAP);
+    let right = right.join(MODULE_GAP);
+    let (left_len, center_len, right_len) = (
+        left.chars().count(),
+        center.chars().count(),
+        right.chars().count(),
+    );
+
+    // Center the middle section, nudging it right if the left section is long
+    let center_start = (PREVIEW_WIDTH.saturating_sub(center_len) / 2).max(left_len + 1);
+    let right_start = PREVIEW_WIDTH.saturating_sub(right_len);
+    let fits = center_start + center_len < right_start;
+
+    let line = if fits {
+        format!(
+            "{}{}{}{}{}",
+            left,
+            " ".repeat(center_start - left_len),
+            center,
+            " ".repeat(right_start - center_start - center_len),
+            right
+        )
+    } else {
+        format!("{} | {} | {}", left, center, right)
+    };
+
+    let width = line.chars().count().max(PREVIEW_WIDTH);
+    let border = format!("+{}+", "-".repeat(width));
+    format!("{}\n|{:<width$}|\n{}", border, line, border, width = width)
+}
+
+/// Render a vertical bar: one module per line, top to bottom, with an
+/// elided spacer line between the sections
+fn render_vertical(left: &[String], center: &[String], right: &[String]) -> String {
+    let width = left
+        .iter()
+        .chain(center)
+        .chain(right)
+        .map(|name| name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(3);
+
+    let border = format!("+{}+", "-".repeat(width + 2));
+    let mut lines = vec![border.clone()];
+    for (i, section) in [left, center, right].into_iter().enumerate() {
+        if i > 0 {
+            lines.push(format!("| {:^width$} |", "...", width = width));
+        }
+        for name in section {
+            lines.push(format!("| {:<width$} |", name, width = width));
+        }
+    }
+    lines.push(border);
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_horizontal_sections_in_order() {
+        let config = json!({
+            "modules-left": ["hyprland/workspaces", "hyprland/window"],
+            "modules-center": ["clock"],
+            "modules-right": ["network", "battery"]
+        });
+
+        let preview = render_ascii_preview(&config);
+        let line = preview.lines().nth(2).unwrap();
+
+        assert!(preview.starts_with("bar 0 [top, horizontal]"));
+        assert!(line.starts_with("|hyprland/workspaces  hyprland/window"));
+        assert!(line.ends_with("network  battery|"));
+
+        let workspaces = line.find("hyprland/workspaces").unwrap();
+        let clock = line.find("clock").unwrap();
+        let network = line.find("network").unwrap();
+        assert!(workspaces < clock && clock < network);
+    }
+
+    #[test]
+    fn test_horizontal_overflow_falls_back_to_separators() {
+        let many: Vec<String> = (0..12).map(|i| format!("custom/module{}", i)).collect();
+        let config = json!({
+            "modules-left": many,
+            "modules-center": ["clock"],
+            "modules-right": ["tray"]
+        });
+
+        let preview = render_ascii_preview(&config);
+        assert!(preview.contains(" | clock | tray"));
+    }
+
+    #[test]
+    fn test_vertical_bar_stacks_modules() {
+        let config = json!({
+            "position": "left",
+            "modules-left": ["cpu", "memory"],
+            "modules-center": ["clock"],
+            "modules-right": ["tray"]
+        });
+
+        let preview = render_ascii_preview(&config);
+        let lines: Vec<&str> = preview.lines().collect();
+
+        assert_eq!(lines[0], "bar 0 [left, vertical]");
+        assert_eq!(lines[2].trim_matches(|c| c == '|' || c == ' '), "cpu");
+        assert_eq!(lines[3].trim_matches(|c| c == '|' || c == ' '), "memory");
+        assert_eq!(lines[4].trim_matches(|c| c == '|' || c == ' '), "...");
+        assert_eq!(lines[5].trim_matches(|c| c == '|' || c == ' '), "clock");
+        assert_eq!(lines[7].trim_matches(|c| c == '|' || c == ' '), "tray");
+    }
+
+    #[test]
+    fn test_multi_bar_renders_each_bar() {
+        let config = json!([
+            { "name": "main", "modules-left": ["clock"] },
+            { "position": "bottom", "modules-right": ["tray"] }
+        ]);
+
+        let preview = render_ascii_preview(&config);
+        assert!(preview.contains("bar 0 (main) [top, horizontal]"));
+        assert!(preview.contains("bar 1 [bottom, horizontal]"));
+        assert!(preview.find("clock").unwrap() < preview.find("tray").unwrap());
+    }
+}
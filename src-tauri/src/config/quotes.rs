@@ -0,0 +1,152 @@
+// ============================================================================
+// SINGLE-QUOTED STRINGS
+// ============================================================================
+//
+// JSON only allows double-quoted strings, but configs pasted from JavaScript
+// often contain 'single-quoted' ones, which serde rejects with an opaque
+// "expected value" error. These helpers find them before parsing.
+
+use crate::config::parser::{tokenize_jsonc, TokenKind};
+use serde::{Deserialize, Serialize};
+
+/// A single-quoted string literal found outside strings and comments
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SingleQuotedString {
+    /// 1-based line of the opening quote
+    pub line: usize,
+    /// 1-based column (in characters) of the opening quote
+    pub column: usize,
+    /// The literal, quotes included
+    pub text: String,
+    /// Whether [`fix_quotes`] can convert it safely
+    pub fixable: bool,
+}
+
+/// Find single-quoted literals and their byte ranges
+///
+/// Text inside double-quoted strings and comments is skipped, so apostrophes
+/// in valid strings are never reported. A literal is fixable when it sits
+/// where a JSON string can (after `{`, `[`, `,` or `:` and before `,`, `}`,
+/// `]` or `:`) and holds no `"` or backslash that would need re-escaping.
+fn scan(content: &str) -> Vec<(usize, usize, SingleQuotedString)> {
+    let mut found = Vec::new();
+    let mut previous: Option<u8> = None;
+
+    for token in tokenize_jsonc(content) {
+        let (start, end) = (token.span.start, token.span.end);
+        let text = &content[start..end];
+
+        match token.kind {
+            TokenKind::Comment => continue,
+            TokenKind::String => previous = Some(b'"'),
+            TokenKind::SingleQuoted => {
+                let inner = &text[1..text.len() - 1];
+                let next = content[end..].bytes().find(|b| !b.is_ascii_whitespace());
+                let fixable = !inner.contains(['"', '\\'])
+                    && matches!(previous, Some(b'{' | b'[' | b',' | b':'))
+                    && matches!(next, None | Some(b',' | b'}' | b']' | b':'));
+                let line_start = content[..start].rfind('\n').map_or(0, |pos| pos + 1);
+
+                found.push((
+                    start,
+                    end,
+                    SingleQuotedString {
+                        line: token.line,
+                        column: content[line_start..start].chars().count() + 1,
+                        text: text.to_string(),
+                        fixable,
+                    },
+                ));
+                previous = Some(b'"');
+            }
+            TokenKind::Punct(_) | TokenKind::Bare => previous = text.bytes().last(),
+        }
+    }
+
+    found
+}
+
+/// Find likely single-quoted string literals
+pub fn find_single_quoted_strings(content: &str) -> Vec<SingleQuotedString> {
+    scan(content).into_iter().map(|(_, _, literal)| literal).collect()
+}
+
+/// Convert the fixable single-quoted literals to double-quoted strings
+///
+/// Literals that aren't safely convertible are left as they are; everything
+/// else in the content, comments included, is unchanged.
+pub fn fix_quotes(content: &str) -> String {
+    let mut output = String::with_capacity(content.len());
+    let mut copied = 0;
+
+    for (start, end, literal) in scan(content) {
+        if !literal.fixable {
+            continue;
+        }
+        output.push_str(&content[copied..start]);
+        output.push('"');
+        output.push_str(&content[start + 1..end - 1]);
+        output.push('"');
+        copied = end;
+    }
+    output.push_str(&content[copied..]);
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::parser::parse_jsonc;
+
+    const PASTED: &str = r#"{
+    // don't touch this comment
+    "clock": {
+        "format": '{:%H:%M}',
+        "tooltip-format": "It's {:%A}"
+    },
+    'modules-left': ['clock', "tray"],
+    "custom/x": { "exec": 'echo "hi"' }
+}"#;
+
+    #[test]
+    fn test_find_single_quoted_strings() {
+        let found = find_single_quoted_strings(PASTED);
+        let summary: Vec<_> = found
+            .iter()
+            .map(|s| (s.line, s.column, s.text.as_str(), s.fixable))
+            .collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                (4, 19, "'{:%H:%M}'", true),
+                (7, 5, "'modules-left'", true),
+                (7, 22, "'clock'", true),
+                (8, 27, "'echo \"hi\"'", false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fix_quotes() {
+        let fixed = fix_quotes(PASTED);
+
+        assert!(fixed.contains(r#""format": "{:%H:%M}","#));
+        assert!(fixed.contains(r#""modules-left": ["clock", "tray"],"#));
+        assert!(fixed.contains("// don't touch this comment"));
+        assert!(fixed.contains(r#""tooltip-format": "It's {:%A}""#));
+        // Not safely convertible, left for the user
+        assert!(fixed.contains(r#"'echo "hi"'"#));
+
+        let config = parse_jsonc(&fix_quotes("{ 'height': 30, 'modules-left': ['clock'] }")).unwrap();
+        assert_eq!(config["modules-left"][0], "clock");
+    }
+
+    #[test]
+    fn test_valid_json_has_no_single_quotes() {
+        let content = r#"{ "format": "It's {}", "exec": "echo 'quoted'" /* 'c' */ }"#;
+        assert!(find_single_quoted_strings(content).is_empty());
+        assert_eq!(fix_quotes(content), content);
+    }
+}
@@ -0,0 +1,154 @@
+// ============================================================================
+// DEFAULT CONFIG SCAFFOLDING
+// ============================================================================
+//
+// A minimal working setup for first-time users, written only where no
+// configuration exists yet.
+
+use crate::config::ConfigPaths;
+use crate::error::{AppError, Result};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// Starter `config.jsonc`: one bar with a clock, battery and network module
+pub const DEFAULT_CONFIG: &str = r#"// Waybar configuration
+// See https://github.com/Alexays/Waybar/wiki/Configuration
+{
+    "layer": "top",
+    "position": "top",
+    "height": 30,
+    "spacing": 4,
+
+    "modules-left": [],
+    "modules-center": ["clock"],
+    "modules-right": ["network", "battery"],
+
+    "clock": {
+        "format": "{:%H:%M}",
+        "tooltip-format": "{:%A, %d %B %Y}"
+    },
+
+    "battery": {
+        "format": "BAT {capacity}%",
+        "format-charging": "CHR {capacity}%",
+        "states": {
+            "warning": 30,
+            "critical": 15
+        }
+    },
+
+    "network": {
+        "format-wifi": "{essid} ({signalStrength}%)",
+        "format-ethernet": "{ipaddr}",
+        "format-disconnected": "Disconnected"
+    }
+}
+"#;
+
+/// Starter `style.css` matching [`DEFAULT_CONFIG`]
+pub const DEFAULT_STYLE: &str = r#"* {
+    font-family: sans-serif;
+    font-size: 13px;
+}
+
+window#waybar {
+    background-color: rgba(30, 30, 46, 0.9);
+    color: #cdd6f4;
+}
+
+#clock,
+#battery,
+#network {
+    padding: 0 10px;
+}
+
+#battery.warning {
+    color: #f9e2af;
+}
+
+#battery.critical {
+    color: #f38ba8;
+}
+
+#network.disconnected {
+    color: #f38ba8;
+}
+"#;
+
+/// Write a new file, failing if anything already exists at `path`
+fn write_new_file(path: &Path, content: &str) -> Result<()> {
+    let mut file = OpenOptions::new().write(true).create_new(true).open(path)?;
+    file.write_all(content.as_bytes())?;
+    Ok(())
+}
+
+/// Create the config directory with a starter config and stylesheet
+///
+/// Refuses with `AlreadyExists`, writing nothing, when a config file (either
+/// `config.jsonc` or the legacy `config`) or the stylesheet is already there.
+pub fn create_default_config(paths: &ConfigPaths) -> Result<ConfigPaths> {
+    let style = Path::new(&paths.style_file);
+    let existing = ConfigPaths::detect_config_file(&paths.config_dir)
+        .or_else(|| style.exists().then(|| style.to_path_buf()));
+    if let Some(existing) = existing {
+        return Err(AppError::AlreadyExists(format!(
+            "Not overwriting existing Waybar configuration: {}",
+            existing.display()
+        )));
+    }
+
+    paths.ensure_config_dir()?;
+    write_new_file(Path::new(&paths.config_file), DEFAULT_CONFIG)?;
+    write_new_file(Path::new(&paths.style_file), DEFAULT_STYLE)?;
+
+    log::info!("Created default Waybar configuration in {}", paths.config_dir);
+    Ok(paths.clone())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn paths_in(dir: &Path) -> ConfigPaths {
+        let config_dir = dir.join("waybar").to_str().unwrap().to_string();
+        ConfigPaths {
+            config_file: format!("{}/config.jsonc", config_dir),
+            style_file: format!("{}/style.css", config_dir),
+            config_dir,
+        }
+    }
+
+    #[test]
+    fn test_create_default_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = paths_in(temp_dir.path());
+
+        let created = create_default_config(&paths).unwrap();
+        assert_eq!(created.config_file, paths.config_file);
+
+        let config = fs::read_to_string(&paths.config_file).unwrap();
+        let config = crate::config::parser::parse_jsonc(&config).unwrap();
+        assert_eq!(config["modules-right"], serde_json::json!(["network", "battery"]));
+
+        let style = fs::read_to_string(&paths.style_file).unwrap();
+        assert!(crate::config::css::validate_css(&style).is_ok());
+    }
+
+    #[test]
+    fn test_create_default_config_refuses_to_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = paths_in(temp_dir.path());
+        fs::create_dir_all(&paths.config_dir).unwrap();
+        fs::write(format!("{}/config", paths.config_dir), "{}").unwrap();
+
+        assert!(matches!(create_default_config(&paths), Err(AppError::AlreadyExists(_))));
+        assert!(!Path::new(&paths.style_file).exists());
+    }
+}
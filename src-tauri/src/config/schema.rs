@@ -0,0 +1,302 @@
+// ============================================================================
+// CONFIG SCHEMA VALIDATION
+// ============================================================================
+//
+// Structural checks over a parsed config: each known bar option and common
+// module option must have the type (and, for enumerations, one of the
+// values) Waybar expects. Unknown keys are left to the lints. Violations
+// carry a JSON pointer (RFC 6901) to the offending value.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// A value that doesn't have the shape Waybar expects
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON pointer to the value (e.g. `/0/modules-left/2`)
+    pub path: String,
+    /// Human-readable explanation
+    pub message: String,
+}
+
+/// The expected shape of an option
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Shape {
+    Bool,
+    String,
+    /// A non-negative integer
+    Count,
+    /// Any integer
+    Integer,
+    /// A number of pixels or a CSS-like string (`"4 8"`)
+    NumberOrString,
+    /// A string or an array of strings
+    Strings,
+    /// An array of strings
+    StringArray,
+    /// One of the listed strings
+    OneOf(&'static [&'static str]),
+}
+
+/// Bar options and their shapes
+const BAR_OPTIONS: &[(&str, Shape)] = &[
+    ("layer", Shape::OneOf(&["top", "bottom", "overlay"])),
+    ("position", Shape::OneOf(&["top", "bottom", "left", "right"])),
+    ("mode", Shape::OneOf(&["dock", "hide", "invisible", "overlay"])),
+    ("height", Shape::Count),
+    ("width", Shape::Count),
+    ("spacing", Shape::Count),
+    ("margin", Shape::NumberOrString),
+    ("margin-top", Shape::Integer),
+    ("margin-bottom", Shape::Integer),
+    ("margin-left", Shape::Integer),
+    ("margin-right", Shape::Integer),
+    ("name", Shape::String),
+    ("id", Shape::String),
+    ("output", Shape::Strings),
+    ("include", Shape::Strings),
+    ("modules-left", Shape::StringArray),
+    ("modules-center", Shape::StringArray),
+    ("modules-right", Shape::StringArray),
+    ("exclusive", Shape::Bool),
+    ("passthrough", Shape::Bool),
+    ("fixed-center", Shape::Bool),
+    ("gtk-layer-shell", Shape::Bool),
+    ("ipc", Shape::Bool),
+    ("start_hidden", Shape::Bool),
+    ("reload_style_on_change", Shape::Bool),
+];
+
+/// Options shared by most modules and their shapes
+const MODULE_OPTIONS: &[(&str, Shape)] = &[
+    ("format", Shape::String),
+    ("format-alt", Shape::String),
+    ("tooltip", Shape::Bool),
+    ("tooltip-format", Shape::String),
+    ("max-length", Shape::Count),
+    ("min-length", Shape::Count),
+    ("rotate", Shape::Integer),
+    ("align", Shape::NumberOrString),
+    ("justify", Shape::OneOf(&["left", "right", "center", "fill"])),
+    ("on-click", Shape::String),
+    ("on-click-middle", Shape::String),
+    ("on-click-right", Shape::String),
+    ("on-scroll-up", Shape::String),
+    ("on-scroll-down", Shape::String),
+    ("smooth-scrolling-threshold", Shape::NumberOrString),
+    ("exec", Shape::String),
+    ("exec-if", Shape::String),
+    ("return-type", Shape::OneOf(&["", "json"])),
+    ("modules", Shape::StringArray),
+];
+
+// ============================================================================
+// VALIDATION
+// ============================================================================
+
+/// Escape one JSON pointer segment (`~` becomes `~0`, `/` becomes `~1`)
+fn pointer_segment(segment: &str) -> String {
+    segment.replace('~', "~0").replace('/', "~1")
+}
+
+/// Describe a value's JSON type for messages
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "a boolean",
+        Value::Number(_) => "a number",
+        Value::String(_) => "a string",
+        Value::Array(_) => "an array",
+        Value::Object(_) => "an object",
+    }
+}
+
+/// Check one value against its shape, pushing violations found under `path`
+fn check_shape(value: &Value, shape: Shape, path: &str, violations: &mut Vec<SchemaViolation>) {
+    let mut violation = |path: &str, message: String| {
+        violations.push(SchemaViolation { path: path.to_string(), message });
+    };
+    let got = type_name(value);
+
+    match shape {
+        Shape::Bool if !value.is_boolean() => {
+            violation(path, format!("Expected a boolean, got {}", got))
+        }
+        Shape::String if !value.is_string() => {
+            violation(path, format!("Expected a string, got {}", got))
+        }
+        Shape::Count if !value.is_u64() => {
+            violation(path, format!("Expected a non-negative integer, got {}", got))
+        }
+        Shape::Integer if !(value.is_i64() || value.is_u64()) => {
+            violation(path, format!("Expected an integer, got {}", got))
+        }
+        Shape::NumberOrString if !(value.is_number() || value.is_string()) => {
+            violation(path, format!("Expected a number or a string, got {}", got))
+        }
+        Shape::OneOf(allowed) => match value.as_str() {
+            Some(text) if allowed.contains(&text) => {}
+            _ => violation(
+                path,
+                format!(
+                    "Expected one of {}, got {}",
+                    allowed.iter().map(|v| format!("\"{}\"", v)).collect::<Vec<_>>().join(", "),
+                    value
+                ),
+            ),
+        },
+        Shape::StringArray | Shape::Strings => match value {
+            Value::String(_) if shape == Shape::Strings => {}
+            Value::Array(items) => {
+                for (index, item) in items.iter().enumerate() {
+                    if !item.is_string() {
+                        violation(
+                            &format!("{}/{}", path, index),
+                            format!("Expected a string, got {}", type_name(item)),
+                        );
+                    }
+                }
+            }
+            _ if shape == Shape::Strings => {
+                violation(path, format!("Expected a string or an array of strings, got {}", got))
+            }
+            _ => violation(path, format!("Expected an array of strings, got {}", got)),
+        },
+        _ => {}
+    }
+}
+
+/// Check a list of known options in an object
+fn check_options(
+    object: &Map<String, Value>,
+    options: &[(&str, Shape)],
+    path: &str,
+    violations: &mut Vec<SchemaViolation>,
+) {
+    for (key, value) in object {
+        if let Some((_, shape)) = options.iter().find(|(option, _)| option == key) {
+            check_shape(value, *shape, &format!("{}/{}", path, pointer_segment(key)), violations);
+        }
+    }
+}
+
+/// Check one bar and its module definitions
+fn check_bar(bar: &Map<String, Value>, path: &str, violations: &mut Vec<SchemaViolation>) {
+    check_options(bar, BAR_OPTIONS, path, violations);
+
+    for (key, value) in bar {
+        // Bar options are covered above; any other object is a module definition
+        if BAR_OPTIONS.iter().any(|(option, _)| option == key) {
+            continue;
+        }
+        if let Value::Object(module) = value {
+            let module_path = format!("{}/{}", path, pointer_segment(key));
+            check_options(module, MODULE_OPTIONS, &module_path, violations);
+        }
+    }
+}
+
+/// Validate the structure of a parsed config
+///
+/// The root must be a bar object or an array of them. Known bar options and
+/// common module options are checked for their type; violations are listed
+/// in config order.
+pub fn validate_config_schema(config: &Value) -> Vec<SchemaViolation> {
+    let mut violations = Vec::new();
+
+    match config {
+        Value::Object(bar) => check_bar(bar, "", &mut violations),
+        Value::Array(items) => {
+            for (index, item) in items.iter().enumerate() {
+                match item {
+                    Value::Object(bar) => check_bar(bar, &format!("/{}", index), &mut violations),
+                    other => violations.push(SchemaViolation {
+                        path: format!("/{}", index),
+                        message: format!("Expected a bar object, got {}", type_name(other)),
+                    }),
+                }
+            }
+        }
+        other => violations.push(SchemaViolation {
+            path: String::new(),
+            message: format!(
+                "Expected a bar object or an array of bar objects, got {}",
+                type_name(other)
+            ),
+        }),
+    }
+
+    violations
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn paths(violations: &[SchemaViolation]) -> Vec<&str> {
+        violations.iter().map(|v| v.path.as_str()).collect()
+    }
+
+    #[test]
+    fn test_valid_config_has_no_violations() {
+        let config = json!({
+            "layer": "top",
+            "position": "bottom",
+            "height": 30,
+            "margin": "4 8",
+            "margin-top": -2,
+            "output": ["DP-1", "!HDMI-A-1"],
+            "modules-left": ["clock", "custom/weather"],
+            "clock": { "format": "{:%H:%M}", "tooltip": false, "max-length": 20 },
+            "custom/weather": { "exec": "weather.sh", "return-type": "json" }
+        });
+
+        assert!(validate_config_schema(&config).is_empty());
+    }
+
+    #[test]
+    fn test_violations_point_at_the_field() {
+        let config = json!([
+            { "height": "30", "layer": "middle", "modules-left": ["clock", 3] },
+            {
+                "modules-right": "clock",
+                "custom/weather": { "interval": 60, "tooltip": "yes", "max-length": -1 }
+            },
+            "bar"
+        ]);
+
+        let violations = validate_config_schema(&config);
+        assert_eq!(
+            paths(&violations),
+            vec![
+                "/0/height",
+                "/0/layer",
+                "/0/modules-left/1",
+                "/1/modules-right",
+                "/1/custom~1weather/tooltip",
+                "/1/custom~1weather/max-length",
+                "/2",
+            ]
+        );
+        assert_eq!(violations[0].message, "Expected a non-negative integer, got a string");
+        assert_eq!(
+            violations[1].message,
+            "Expected one of \"top\", \"bottom\", \"overlay\", got \"middle\""
+        );
+    }
+
+    #[test]
+    fn test_root_must_be_object_or_array() {
+        let violations = validate_config_schema(&json!("bar"));
+        assert_eq!(paths(&violations), vec![""]);
+    }
+}
@@ -0,0 +1,236 @@
+// ============================================================================
+// NAMED SNAPSHOTS
+// ============================================================================
+//
+// Deliberate, user-named save points of the config and style, kept apart
+// from the automatic `.backup.<timestamp>` files under
+// `<config_dir>/snapshots/<name>/`.
+
+use crate::config::git::TRACKED_FILES;
+use crate::config::writer::create_backup;
+use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// Directory holding snapshots, relative to the config dir
+const SNAPSHOTS_DIR: &str = "snapshots";
+
+/// Maximum snapshot name length
+const MAX_NAME_LENGTH: usize = 64;
+
+/// A named snapshot
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    /// Snapshot name
+    pub name: String,
+    /// Files stored in the snapshot
+    pub files: Vec<String>,
+    /// Creation time as a Unix timestamp
+    pub created: u64,
+}
+
+/// Check a user-chosen name is a plain directory name
+///
+/// Letters, digits, `-`, `_` and `.` only, not starting with a dot, so a
+/// name can never escape its parent directory. `kind` names the thing being
+/// named in the error (e.g. "snapshot").
+pub(crate) fn validate_entry_name(kind: &str, name: &str) -> Result<()> {
+    let valid = !name.is_empty()
+        && name.len() <= MAX_NAME_LENGTH
+        && !name.starts_with('.')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'));
+
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::Validation(format!(
+            "Invalid {} name \"{}\": use up to {} letters, digits, '-', '_' or '.', not starting with '.'",
+            kind, name, MAX_NAME_LENGTH
+        )))
+    }
+}
+
+/// Get the directory of a snapshot, validating its name
+fn snapshot_dir(config_dir: &str, name: &str) -> Result<PathBuf> {
+    validate_entry_name("snapshot", name)?;
+    Ok(Path::new(config_dir).join(SNAPSHOTS_DIR).join(name))
+}
+
+/// Describe the snapshot stored in `dir`
+fn snapshot_info(name: &str, dir: &Path) -> Result<SnapshotInfo> {
+    let files = TRACKED_FILES
+        .iter()
+        .filter(|file| dir.join(file).is_file())
+        .map(|file| file.to_string())
+        .collect();
+
+    let created = fs::metadata(dir)?
+        .modified()?
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    Ok(SnapshotInfo {
+        name: name.to_string(),
+        files,
+        created,
+    })
+}
+
+/// Copy the current config and style files into a new named snapshot
+pub fn create_snapshot(config_dir: &str, name: &str) -> Result<SnapshotInfo> {
+    let dir = snapshot_dir(config_dir, name)?;
+    if dir.exists() {
+        return Err(AppError::AlreadyExists(format!("Snapshot already exists: {}", name)));
+    }
+
+    let sources: Vec<&str> = TRACKED_FILES
+        .iter()
+        .copied()
+        .filter(|file| Path::new(config_dir).join(file).is_file())
+        .collect();
+    if sources.is_empty() {
+        return Err(AppError::NotFound(format!("No config files to snapshot in {}", config_dir)));
+    }
+
+    fs::create_dir_all(&dir)?;
+    for file in sources {
+        fs::copy(Path::new(config_dir).join(file), dir.join(file))?;
+    }
+
+    log::info!("Created snapshot {}", name);
+    snapshot_info(name, &dir)
+}
+
+/// List snapshots sorted by name
+pub fn list_snapshots(config_dir: &str) -> Result<Vec<SnapshotInfo>> {
+    let root = Path::new(config_dir).join(SNAPSHOTS_DIR);
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut snapshots = Vec::new();
+    for entry in fs::read_dir(&root)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        if validate_entry_name("snapshot", &name).is_ok() {
+            snapshots.push(snapshot_info(&name, &entry.path())?);
+        }
+    }
+
+    snapshots.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(snapshots)
+}
+
+/// Copy a snapshot's files back into the config directory
+///
+/// Each current file is backed up before being overwritten. Returns the
+/// restored file names.
+pub fn restore_snapshot(config_dir: &str, name: &str) -> Result<Vec<String>> {
+    let dir = snapshot_dir(config_dir, name)?;
+    if !dir.is_dir() {
+        return Err(AppError::NotFound(format!("Snapshot not found: {}", name)));
+    }
+
+    let info = snapshot_info(name, &dir)?;
+    for file in &info.files {
+        let target = Path::new(config_dir).join(file);
+        if target.exists() {
+            create_backup(&target.to_string_lossy())?;
+        }
+        fs::copy(dir.join(file), &target)?;
+    }
+
+    log::info!("Restored snapshot {}", name);
+    Ok(info.files)
+}
+
+/// Delete a snapshot
+pub fn delete_snapshot(config_dir: &str, name: &str) -> Result<()> {
+    let dir = snapshot_dir(config_dir, name)?;
+    if !dir.is_dir() {
+        return Err(AppError::NotFound(format!("Snapshot not found: {}", name)));
+    }
+
+    fs::remove_dir_all(&dir)?;
+    log::info!("Deleted snapshot {}", name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("config.jsonc"), r#"{ "height": 30 }"#).unwrap();
+        fs::write(temp_dir.path().join("style.css"), "* { margin: 0; }").unwrap();
+        let dir = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, dir)
+    }
+
+    #[test]
+    fn test_create_list_and_restore_snapshot() {
+        let (temp_dir, dir) = setup();
+
+        let created = create_snapshot(&dir, "before-theme").unwrap();
+        assert_eq!(created.files, vec!["config.jsonc", "style.css"]);
+
+        let snapshots = list_snapshots(&dir).unwrap();
+        assert_eq!(snapshots.len(), 1);
+        assert_eq!(snapshots[0].name, "before-theme");
+
+        fs::write(temp_dir.path().join("config.jsonc"), r#"{ "height": 40 }"#).unwrap();
+        let restored = restore_snapshot(&dir, "before-theme").unwrap();
+        assert_eq!(restored, vec!["config.jsonc", "style.css"]);
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("config.jsonc")).unwrap(),
+            r#"{ "height": 30 }"#
+        );
+
+        // The overwritten state was backed up
+        let backups = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("config.jsonc.backup."))
+            .count();
+        assert_eq!(backups, 1);
+    }
+
+    #[test]
+    fn test_duplicate_and_delete_snapshot() {
+        let (_temp_dir, dir) = setup();
+
+        create_snapshot(&dir, "v1").unwrap();
+        assert!(matches!(create_snapshot(&dir, "v1"), Err(AppError::AlreadyExists(_))));
+
+        delete_snapshot(&dir, "v1").unwrap();
+        assert!(list_snapshots(&dir).unwrap().is_empty());
+        assert!(matches!(restore_snapshot(&dir, "v1"), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_snapshot_names_cannot_traverse() {
+        let (_temp_dir, dir) = setup();
+
+        for name in ["../escape", "a/b", "..", ".hidden", "", "with space"] {
+            assert!(
+                matches!(create_snapshot(&dir, name), Err(AppError::Validation(_))),
+                "{:?} should be rejected",
+                name
+            );
+        }
+        assert!(matches!(delete_snapshot(&dir, "../../etc"), Err(AppError::Validation(_))));
+    }
+}
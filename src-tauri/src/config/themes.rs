@@ -0,0 +1,204 @@
+// ============================================================================
+// NAMED THEMES
+// ============================================================================
+//
+// Reusable config and style pairs under `<config_dir>/themes/<name>/`, for
+// switching between looks (e.g. a dark and a light bar). Unlike snapshots,
+// saving a theme again updates it.
+
+use crate::config::snapshots::validate_entry_name;
+use crate::config::writer::create_backup;
+use crate::config::ConfigPaths;
+use crate::error::{AppError, Result};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directory holding themes, relative to the config dir
+const THEMES_DIR: &str = "themes";
+
+/// Stylesheet of a theme; the config is whichever file `detect_config_file`
+/// finds (`config.jsonc` or a legacy `config`)
+const STYLE_FILE: &str = "style.css";
+
+/// Get the directory of a theme, validating its name
+fn theme_dir(config_dir: &str, name: &str) -> Result<PathBuf> {
+    validate_entry_name("theme", name)?;
+    Ok(Path::new(config_dir).join(THEMES_DIR).join(name))
+}
+
+/// Get the file name of a path as a string
+fn file_name(path: &Path) -> String {
+    path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_default()
+}
+
+/// Get the config file and stylesheet present in `dir`
+fn theme_sources(dir: &Path) -> Vec<PathBuf> {
+    let config = ConfigPaths::detect_config_file(&dir.to_string_lossy());
+    let style = Some(dir.join(STYLE_FILE)).filter(|style| style.is_file());
+    config.into_iter().chain(style).collect()
+}
+
+/// Copy the current config and style into a theme, replacing its files
+///
+/// Returns the saved file names.
+pub fn save_theme(config_dir: &str, name: &str) -> Result<Vec<String>> {
+    let dir = theme_dir(config_dir, name)?;
+
+    let sources = theme_sources(Path::new(config_dir));
+    if sources.is_empty() {
+        return Err(AppError::NotFound(format!("No config files to save in {}", config_dir)));
+    }
+    let names: Vec<String> = sources.iter().map(|source| file_name(source)).collect();
+
+    fs::create_dir_all(&dir)?;
+    // A theme mirrors the current files, so drop one that is gone (or was renamed)
+    for existing in theme_sources(&dir) {
+        if !names.contains(&file_name(&existing)) {
+            fs::remove_file(&existing)?;
+        }
+    }
+    for source in &sources {
+        fs::copy(source, dir.join(file_name(source)))?;
+    }
+
+    log::info!("Saved theme {}", name);
+    Ok(names)
+}
+
+/// Copy a theme's files into the config directory
+///
+/// The theme's config replaces whichever config file the directory already
+/// uses, so a legacy `config` isn't shadowed by a new `config.jsonc` (or the
+/// other way around). Each current file is backed up before being
+/// overwritten. Returns the applied file names; reloading Waybar is left to
+/// the caller.
+pub fn apply_theme(config_dir: &str, name: &str) -> Result<Vec<String>> {
+    let dir = theme_dir(config_dir, name)?;
+    if !dir.is_dir() {
+        return Err(AppError::NotFound(format!("Theme not found: {}", name)));
+    }
+
+    let current_config = ConfigPaths::detect_config_file(config_dir);
+    let mut applied = Vec::new();
+    for source in theme_sources(&dir) {
+        let target = match &current_config {
+            Some(config) if file_name(&source) != STYLE_FILE => config.clone(),
+            _ => Path::new(config_dir).join(file_name(&source)),
+        };
+        if target.exists() {
+            create_backup(&target.to_string_lossy())?;
+        }
+        fs::copy(&source, &target)?;
+        applied.push(file_name(&target));
+    }
+
+    log::info!("Applied theme {}", name);
+    Ok(applied)
+}
+
+/// List theme names, sorted
+pub fn list_themes(config_dir: &str) -> Result<Vec<String>> {
+    let root = Path::new(config_dir).join(THEMES_DIR);
+    if !root.is_dir() {
+        return Ok(Vec::new());
+    }
+
+    let mut themes = Vec::new();
+    for entry in fs::read_dir(&root)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_dir() {
+            continue;
+        }
+        if let Some(name) = entry.file_name().to_str() {
+            if validate_entry_name("theme", name).is_ok() {
+                themes.push(name.to_string());
+            }
+        }
+    }
+
+    themes.sort();
+    Ok(themes)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn setup() -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("config.jsonc"), r#"{ "height": 30 }"#).unwrap();
+        fs::write(temp_dir.path().join("style.css"), "* { color: white; }").unwrap();
+        let dir = temp_dir.path().to_str().unwrap().to_string();
+        (temp_dir, dir)
+    }
+
+    #[test]
+    fn test_save_list_and_apply_theme() {
+        let (temp_dir, dir) = setup();
+        let style = temp_dir.path().join("style.css");
+
+        assert_eq!(save_theme(&dir, "dark").unwrap(), vec!["config.jsonc", "style.css"]);
+        fs::write(&style, "* { color: black; }").unwrap();
+        save_theme(&dir, "light").unwrap();
+        assert_eq!(list_themes(&dir).unwrap(), vec!["dark", "light"]);
+
+        apply_theme(&dir, "dark").unwrap();
+        assert_eq!(fs::read_to_string(&style).unwrap(), "* { color: white; }");
+
+        // The light style was backed up before being replaced
+        let backups = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_name().to_string_lossy().starts_with("style.css.backup."))
+            .count();
+        assert_eq!(backups, 1);
+    }
+
+    #[test]
+    fn test_save_theme_updates_existing_theme() {
+        let (temp_dir, dir) = setup();
+
+        save_theme(&dir, "dark").unwrap();
+        fs::write(temp_dir.path().join("style.css"), "* { color: gray; }").unwrap();
+        save_theme(&dir, "dark").unwrap();
+
+        let saved = temp_dir.path().join("themes/dark/style.css");
+        assert_eq!(fs::read_to_string(saved).unwrap(), "* { color: gray; }");
+    }
+
+    #[test]
+    fn test_theme_with_legacy_config_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_str().unwrap().to_string();
+        let config = temp_dir.path().join("config");
+        fs::write(&config, r#"{ "height": 30 }"#).unwrap();
+
+        assert_eq!(save_theme(&dir, "legacy").unwrap(), vec!["config"]);
+        assert!(temp_dir.path().join("themes/legacy/config").is_file());
+
+        fs::write(&config, r#"{ "height": 20 }"#).unwrap();
+        assert_eq!(apply_theme(&dir, "legacy").unwrap(), vec!["config"]);
+        assert_eq!(fs::read_to_string(&config).unwrap(), r#"{ "height": 30 }"#);
+        assert!(!temp_dir.path().join("config.jsonc").exists());
+    }
+
+    #[test]
+    fn test_theme_names_cannot_traverse() {
+        let (_temp_dir, dir) = setup();
+
+        for name in ["../escape", "a/b", "..", ".hidden", ""] {
+            assert!(
+                matches!(save_theme(&dir, name), Err(AppError::Validation(_))),
+                "{:?} should be rejected",
+                name
+            );
+        }
+        assert!(matches!(apply_theme(&dir, "../../etc"), Err(AppError::Validation(_))));
+        assert!(matches!(apply_theme(&dir, "missing"), Err(AppError::NotFound(_))));
+    }
+}
@@ -0,0 +1,226 @@
+// ============================================================================
+// CONFIG FILE WATCHING
+// ============================================================================
+//
+// Notices when the config or style file is changed by another program (an
+// editor, a dotfile manager) so the UI can reload instead of overwriting it.
+// The parent directories are watched rather than the files themselves:
+// editors commonly save by writing a new file and renaming it over the old
+// one, which would silently end a watch on the original inode. The app's own
+// saves are not reported.
+
+use crate::config::writer::is_own_write;
+use crate::config::ConfigPaths;
+use crate::error::{AppError, Result};
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// Event emitted to the frontend when a watched file changes
+pub const CONFIG_CHANGED_EVENT: &str = "config-file-changed";
+
+/// Quiet period before a change is reported
+/// Editors often write a file more than once per save
+pub const DEFAULT_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Watcher started by the `watch_config` command
+static ACTIVE_WATCHER: Mutex<Option<ConfigWatcher>> = Mutex::new(None);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/// Which of the watched files changed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WatchedFile {
+    Config,
+    Style,
+}
+
+/// Payload of the `config-file-changed` event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigFileChange {
+    /// Which file changed
+    pub file: WatchedFile,
+    /// Path of the changed file
+    pub path: String,
+}
+
+/// A running watch on the config and style files
+///
+/// Watching stops when this is dropped.
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+}
+
+// ============================================================================
+// WATCHING
+// ============================================================================
+
+/// Match the paths of a filesystem event against the watched files
+///
+/// Reads (access events) are ignored so loading a file doesn't report it as changed.
+fn changed_files(event: &Event, targets: &[(WatchedFile, PathBuf)]) -> Vec<ConfigFileChange> {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return Vec::new();
+    }
+
+    targets
+        .iter()
+        .filter(|(_, target)| event.paths.iter().any(|path| path == target))
+        .map(|(file, target)| ConfigFileChange {
+            file: *file,
+            path: target.to_string_lossy().to_string(),
+        })
+        .collect()
+}
+
+impl ConfigWatcher {
+    /// Start watching the config and style files in `paths`
+    ///
+    /// `on_change` is called from a background thread once per changed file,
+    /// after no further events have arrived for `debounce`. A file that then
+    /// holds what the app itself last wrote to it is skipped.
+    pub fn start(
+        paths: &ConfigPaths,
+        debounce: Duration,
+        on_change: impl Fn(ConfigFileChange) + Send + 'static,
+    ) -> Result<Self> {
+        let targets = vec![
+            (WatchedFile::Config, PathBuf::from(&paths.config_file)),
+            (WatchedFile::Style, PathBuf::from(&paths.style_file)),
+        ];
+
+        let (tx, rx) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<Event>| {
+            match event {
+                Ok(event) => {
+                    let _ = tx.send(event);
+                }
+                Err(e) => log::warn!("Config watcher error: {}", e),
+            }
+        })
+        .map_err(|e| AppError::Io(format!("Failed to create file watcher: {}", e)))?;
+
+        let mut dirs: Vec<&Path> = targets.iter().filter_map(|(_, path)| path.parent()).collect();
+        dirs.dedup();
+        for dir in dirs {
+            watcher
+                .watch(dir, RecursiveMode::NonRecursive)
+                .map_err(|e| AppError::Io(format!("Failed to watch {}: {}", dir.display(), e)))?;
+        }
+
+        // Ends when the watcher is dropped, which closes the channel
+        thread::spawn(move || {
+            let mut pending: Vec<ConfigFileChange> = Vec::new();
+            loop {
+                let next = if pending.is_empty() {
+                    rx.recv().map_err(|_| RecvTimeoutError::Disconnected)
+                } else {
+                    rx.recv_timeout(debounce)
+                };
+
+                match next {
+                    Ok(event) => {
+                        for change in changed_files(&event, &targets) {
+                            if !pending.contains(&change) {
+                                pending.push(change);
+                            }
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => pending
+                        .drain(..)
+                        .filter(|change| !is_own_write(Path::new(&change.path)))
+                        .for_each(&on_change),
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+            }
+        });
+
+        Ok(Self { _watcher: watcher })
+    }
+}
+
+/// Replace the watcher started by `watch_config`, stopping the previous one
+/// Returns whether a watcher was running
+pub fn set_active_watcher(watcher: Option<ConfigWatcher>) -> Result<bool> {
+    let mut active = ACTIVE_WATCHER
+        .lock()
+        .map_err(|_| AppError::Internal("Config watcher lock poisoned".to_string()))?;
+    let was_running = active.is_some();
+    *active = watcher;
+    Ok(was_running)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_watcher_reports_debounced_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_str().unwrap().to_string();
+        let paths = ConfigPaths {
+            config_dir: dir.clone(),
+            config_file: format!("{}/config.jsonc", dir),
+            style_file: format!("{}/style.css", dir),
+        };
+        fs::write(&paths.config_file, "{}").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let _watcher = ConfigWatcher::start(&paths, Duration::from_millis(100), move |change| {
+            tx.send(change).unwrap();
+        })
+        .unwrap();
+
+        fs::write(temp_dir.path().join("notes.txt"), "unrelated").unwrap();
+        fs::write(&paths.config_file, "{ \"height\": 30 }").unwrap();
+        fs::write(&paths.config_file, "{ \"height\": 32 }").unwrap();
+
+        let change = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            change,
+            ConfigFileChange { file: WatchedFile::Config, path: paths.config_file.clone() }
+        );
+        assert!(rx.recv_timeout(Duration::from_millis(500)).is_err());
+    }
+
+    #[test]
+    fn test_watcher_skips_own_writes() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().to_str().unwrap().to_string();
+        let paths = ConfigPaths {
+            config_dir: dir.clone(),
+            config_file: format!("{}/config.jsonc", dir),
+            style_file: format!("{}/style.css", dir),
+        };
+        fs::write(&paths.style_file, "* {}").unwrap();
+
+        let (tx, rx) = mpsc::channel();
+        let _watcher = ConfigWatcher::start(&paths, Duration::from_millis(100), move |change| {
+            tx.send(change).unwrap();
+        })
+        .unwrap();
+
+        crate::config::writer::write_config_file(&paths.style_file, "* { color: red; }").unwrap();
+        assert!(rx.recv_timeout(Duration::from_millis(500)).is_err());
+
+        fs::write(&paths.style_file, "* { color: blue; }").unwrap();
+        let change = rx.recv_timeout(Duration::from_secs(5)).unwrap();
+        assert_eq!(change.file, WatchedFile::Style);
+    }
+}
@@ -2,13 +2,83 @@
 // CONFIG WRITER
 // ============================================================================
 
+use crate::config::parser::{tokenize_jsonc, TokenKind};
 use crate::error::{AppError, Result};
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+/// Free space required before a write, as a multiple of the file size
+/// (room for the backup, the new content and some slack)
+const DISK_SPACE_FACTOR: u64 = 3;
+
+/// Backups kept per file when no limit is given
+pub const DEFAULT_MAX_BACKUPS: usize = 10;
+
+/// Mode given to files that didn't exist before the write
+const NEW_FILE_MODE: u32 = 0o644;
+
+/// Whether writes check for free disk space first
+static DISK_SPACE_CHECK: AtomicBool = AtomicBool::new(true);
+
+/// Content hash of the last write to each file through [`write_config_file`]
+static LAST_WRITES: Mutex<Vec<(PathBuf, u64)>> = Mutex::new(Vec::new());
+
+/// Enable or disable the free disk space check before writes
+pub fn set_disk_space_check(enabled: bool) {
+    DISK_SPACE_CHECK.store(enabled, Ordering::Relaxed);
+}
+
+/// Get the bytes available to unprivileged users on the filesystem of `path`
+///
+/// Uses the nearest existing ancestor, as the target directory may not have
+/// been created yet.
+pub fn free_disk_space(path: &Path) -> Result<u64> {
+    let existing = path
+        .ancestors()
+        .find(|p| p.exists())
+        .unwrap_or_else(|| Path::new("/"));
+    let stat = nix::sys::statvfs::statvfs(existing).map_err(|e| {
+        AppError::Io(format!("Failed to query disk space for {}: {}", existing.display(), e))
+    })?;
+
+    Ok(stat.blocks_available() as u64 * stat.fragment_size() as u64)
+}
+
+/// Fail if the filesystem of `path` can't hold a backup plus `new_len` bytes
+///
+/// `free_space` is the free-space query, injectable for tests.
+fn ensure_disk_space_with(
+    path: &Path,
+    new_len: u64,
+    free_space: impl Fn(&Path) -> Result<u64>,
+) -> Result<()> {
+    let existing_len = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+    let required = existing_len.max(new_len) * DISK_SPACE_FACTOR;
+    let available = free_space(path)?;
+
+    if available < required {
+        return Err(AppError::StorageFull(format!(
+            "Insufficient disk space to write {}: {} bytes free, {} needed",
+            path.display(),
+            available,
+            required
+        )));
+    }
+
+    Ok(())
+}
+
 /// Create a backup of an existing file before modifying it
 /// Returns the path to the backup file
+///
+/// Backups are named `<name>.backup.<timestamp>`. When a backup with the
+/// current timestamp already exists (two saves within one second), the
+/// timestamp is bumped until the name is free, so no backup is overwritten.
 pub fn create_backup(file_path: &str) -> Result<PathBuf> {
     let path = Path::new(file_path);
 
@@ -18,7 +88,7 @@ pub fn create_backup(file_path: &str) -> Result<PathBuf> {
     }
 
     // Generate backup filename with timestamp
-    let timestamp = SystemTime::now()
+    let mut timestamp = SystemTime::now()
         .duration_since(UNIX_EPOCH)
         .map_err(|e| AppError::Internal(format!("Failed to get timestamp: {}", e)))?
         .as_secs();
@@ -29,10 +99,16 @@ pub fn create_backup(file_path: &str) -> Result<PathBuf> {
         .to_str()
         .ok_or_else(|| AppError::Internal("Invalid UTF-8 in file name".to_string()))?;
 
-    let backup_name = format!("{}.backup.{}", file_name, timestamp);
-    let backup_path = path.parent()
-        .ok_or_else(|| AppError::Internal("Invalid file path".to_string()))?
-        .join(backup_name);
+    let parent = path.parent()
+        .ok_or_else(|| AppError::Internal("Invalid file path".to_string()))?;
+
+    // Two saves within the same second must not overwrite each other's backup,
+    // so bump the timestamp until the name is free
+    let mut backup_path = parent.join(format!("{}.backup.{}", file_name, timestamp));
+    while backup_path.exists() {
+        timestamp += 1;
+        backup_path = parent.join(format!("{}.backup.{}", file_name, timestamp));
+    }
 
     // Copy file to backup
     fs::copy(path, &backup_path)?;
@@ -40,10 +116,68 @@ pub fn create_backup(file_path: &str) -> Result<PathBuf> {
     Ok(backup_path)
 }
 
+/// Delete the oldest `<file_name>.backup.<timestamp>` files in `config_dir`,
+/// keeping the newest `max_keep`
+///
+/// Only backups of `file_name` are considered, so pruning `config.jsonc`
+/// backups never touches those of `style.css`. Backups are ordered by their
+/// numeric timestamp. Returns the removed file names, oldest first.
+pub fn prune_backups(config_dir: &str, file_name: &str, max_keep: usize) -> Result<Vec<String>> {
+    let prefix = format!("{}.backup.", file_name);
+    let mut backups = Vec::new();
+
+    for entry in fs::read_dir(config_dir)? {
+        let entry = entry?;
+        let Some(name) = entry.file_name().to_str().map(str::to_string) else {
+            continue;
+        };
+        let timestamp = name
+            .strip_prefix(&prefix)
+            .filter(|ts| !ts.is_empty() && ts.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|ts| ts.parse::<u64>().ok());
+        if let Some(timestamp) = timestamp {
+            backups.push((timestamp, name));
+        }
+    }
+
+    backups.sort();
+    let excess = backups.len().saturating_sub(max_keep);
+    let mut removed = Vec::new();
+    for (_, name) in backups.into_iter().take(excess) {
+        fs::remove_file(Path::new(config_dir).join(&name))?;
+        removed.push(name);
+    }
+
+    Ok(removed)
+}
+
+/// Prune the backups of a file just written (see [`prune_backups`])
+/// Failures are logged rather than returned, as the write itself succeeded
+pub fn rotate_backups(file_path: &str, max_keep: usize) {
+    let path = Path::new(file_path);
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name().and_then(|n| n.to_str()))
+    else {
+        return;
+    };
+
+    match prune_backups(&dir.to_string_lossy(), file_name, max_keep) {
+        Ok(removed) if !removed.is_empty() => {
+            log::info!("Removed {} old backup(s) of {}", removed.len(), file_path)
+        }
+        Ok(_) => {}
+        Err(e) => log::warn!("Failed to prune backups of {}: {}", file_path, e),
+    }
+}
+
 /// Write content to a file with automatic backup
 pub fn write_config_file(file_path: &str, content: &str) -> Result<()> {
     let path = Path::new(file_path);
 
+    // Refuse up front rather than leave a partial backup or write behind
+    if DISK_SPACE_CHECK.load(Ordering::Relaxed) {
+        ensure_disk_space_with(path, content.len() as u64, free_disk_space)?;
+    }
+
     // Create backup if file exists
     if path.exists() {
         let backup_path = create_backup(file_path)?;
@@ -56,12 +190,95 @@ pub fn write_config_file(file_path: &str, content: &str) -> Result<()> {
     }
 
     // Write new content
-    fs::write(path, content)?;
+    write_atomic(path, content)?;
+    record_write(path, content);
 
     log::info!("Successfully wrote config to: {}", file_path);
     Ok(())
 }
 
+/// Hash file content, for comparing within this process only
+fn content_hash(content: &[u8]) -> u64 {
+    use std::hash::{DefaultHasher, Hash, Hasher};
+
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Remember what was last written to `path`
+fn record_write(path: &Path, content: &str) {
+    let hash = content_hash(content.as_bytes());
+    if let Ok(mut writes) = LAST_WRITES.lock() {
+        match writes.iter_mut().find(|(written, _)| written == path) {
+            Some(entry) => entry.1 = hash,
+            None => writes.push((path.to_path_buf(), hash)),
+        }
+    }
+}
+
+/// Whether `path` holds exactly what [`write_config_file`] last wrote to it
+///
+/// Lets the file watcher tell the app's own saves from changes made by
+/// other programs.
+pub fn is_own_write(path: &Path) -> bool {
+    let Ok(content) = fs::read(path) else {
+        return false;
+    };
+    let hash = content_hash(&content);
+
+    LAST_WRITES
+        .lock()
+        .is_ok_and(|writes| writes.iter().any(|(written, last)| written == path && *last == hash))
+}
+
+/// Replace a file's content all at once
+///
+/// The content goes to a temporary file in the same directory, which is then
+/// renamed over the target, so an interrupted write never leaves a truncated
+/// file. A symlinked target (e.g. a dotfiles checkout) is written through so
+/// the link survives. The temporary file gets the permissions (and, where
+/// allowed, the owner) of the file it replaces, or mode 0644 for a new file.
+/// The temporary file is removed on any error.
+fn write_atomic(path: &Path, content: &str) -> Result<()> {
+    use std::io::Write;
+    use std::os::unix::fs::{fchown, MetadataExt, PermissionsExt};
+
+    let target = if path.is_symlink() { fs::canonicalize(path)? } else { path.to_path_buf() };
+    let original = match fs::metadata(&target) {
+        Ok(metadata) => Some(metadata),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => return Err(e.into()),
+    };
+    let file_name = target
+        .file_name()
+        .and_then(|name| name.to_str())
+        .ok_or_else(|| AppError::Internal("Invalid file path".to_string()))?;
+    let temp_path =
+        target.with_file_name(format!(".{}.{}.tmp", file_name, std::process::id()));
+
+    let written = fs::File::create(&temp_path).and_then(|mut file| {
+        match &original {
+            Some(original) => {
+                file.set_permissions(original.permissions())?;
+                // Only root can give a file away; other users keep their own ownership
+                if let Err(e) = fchown(&file, Some(original.uid()), Some(original.gid())) {
+                    log::debug!("Could not preserve ownership of {}: {}", target.display(), e);
+                }
+            }
+            None => file.set_permissions(fs::Permissions::from_mode(NEW_FILE_MODE))?,
+        }
+        file.write_all(content.as_bytes())?;
+        file.sync_all()
+    });
+    if let Err(e) = written.and_then(|()| fs::rename(&temp_path, &target)) {
+        let _ = fs::remove_file(&temp_path);
+        return Err(e.into());
+    }
+
+    Ok(())
+}
+
 /// Format JSON with proper indentation (2 spaces)
 pub fn format_json(value: &serde_json::Value) -> Result<String> {
     serde_json::to_string_pretty(value)
@@ -81,6 +298,260 @@ pub fn add_config_comments(json_str: &str) -> String {
     format!("{}{}", header, json_str)
 }
 
+/// A key of a bar object and the comments on the lines right above it
+struct KeyComments {
+    /// Index of the bar (see [`crate::config::bars`])
+    bar: usize,
+    key: String,
+    /// Byte offset of the key's opening quote
+    offset: usize,
+    /// Raw text of each leading comment
+    leading: Vec<String>,
+}
+
+/// Find the keys of each bar object with their leading comments
+///
+/// Bars are the root object, or the objects of a root array. A comment leads
+/// a key when it sits between the previous `{` / `,` and the key, starting
+/// on a later line than that token (so `"a": 1, // note` stays with `a`).
+fn bar_keys(content: &str) -> Vec<KeyComments> {
+    let mut keys = Vec::new();
+    let mut bar_depth = 0;
+    let mut depth = 0;
+    let mut bar = None::<usize>;
+    let mut expecting_key = false;
+    let mut token_line = 0;
+    let mut pending = Vec::new();
+
+    for token in tokenize_jsonc(content) {
+        let text = &content[token.span.clone()];
+        match token.kind {
+            TokenKind::Comment if depth == bar_depth && expecting_key && token.line > token_line => {
+                pending.push(text.to_string());
+            }
+            TokenKind::String => {
+                if let (true, Some(bar)) = (depth == bar_depth && expecting_key, bar) {
+                    if let Ok(key) = serde_json::from_str::<String>(text) {
+                        keys.push(KeyComments {
+                            bar,
+                            key,
+                            offset: token.span.start,
+                            leading: std::mem::take(&mut pending),
+                        });
+                    }
+                    expecting_key = false;
+                }
+            }
+            TokenKind::Punct(open @ (b'{' | b'[')) => {
+                if depth == 0 {
+                    bar_depth = if open == b'[' { 2 } else { 1 };
+                }
+                depth += 1;
+                if depth == bar_depth && open == b'{' {
+                    bar = Some(bar.map_or(0, |b| b + 1));
+                    expecting_key = true;
+                    token_line = token.line;
+                    pending.clear();
+                }
+            }
+            TokenKind::Punct(b'}' | b']') => depth -= 1,
+            TokenKind::Punct(b',') if depth == bar_depth => {
+                expecting_key = true;
+                token_line = token.line;
+                pending.clear();
+            }
+            _ => {}
+        }
+    }
+
+    keys
+}
+
+/// Copy the comments above each bar key of `old_content` into `new_content`
+///
+/// A key keeps its leading comments when its value is unchanged. Comments
+/// are inserted on their own lines above the key, at its indentation. If
+/// either document doesn't parse, `new_content` is returned unchanged.
+///
+/// Every other comment is dropped: comments after a value on the same line,
+/// after the last key of a bar, outside the bars, inside a module's object,
+/// and above a key whose value changed. [`diff_config`] lists them as
+/// `dropped_comments`.
+pub fn carry_over_comments(new_content: &str, old_content: &str) -> String {
+    let parse = crate::config::parser::parse_jsonc;
+    let (Ok(old), Ok(new)) = (parse(old_content), parse(new_content)) else {
+        return new_content.to_string();
+    };
+    let (old_bars, new_bars) = (crate::config::bars(&old), crate::config::bars(&new));
+
+    let old_comments: std::collections::HashMap<(usize, String), Vec<String>> = bar_keys(old_content)
+        .into_iter()
+        .filter(|k| !k.leading.is_empty())
+        .map(|k| ((k.bar, k.key), k.leading))
+        .collect();
+
+    let mut output = String::with_capacity(new_content.len());
+    let mut copied = 0;
+
+    for key in bar_keys(new_content) {
+        let Some(leading) = old_comments.get(&(key.bar, key.key.clone())) else {
+            continue;
+        };
+        let old_value = old_bars.get(key.bar).and_then(|bar| bar.get(&key.key));
+        let new_value = new_bars.get(key.bar).and_then(|bar| bar.get(&key.key));
+        if old_value.is_none() || old_value != new_value {
+            continue;
+        }
+
+        let line_start = new_content[..key.offset].rfind('\n').map_or(0, |pos| pos + 1);
+        let indent = &new_content[line_start..key.offset];
+        if indent.trim().is_empty() {
+            output.push_str(&new_content[copied..line_start]);
+            for comment in leading {
+                output.push_str(indent);
+                output.push_str(comment);
+                output.push('\n');
+            }
+            copied = line_start;
+        } else {
+            output.push_str(&new_content[copied..key.offset]);
+            for comment in leading {
+                output.push_str(comment);
+                output.push('\n');
+            }
+            copied = key.offset;
+        }
+    }
+    output.push_str(&new_content[copied..]);
+
+    output
+}
+
+/// Line ending style of a text file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LineEnding {
+    Lf,
+    CrLf,
+}
+
+impl LineEnding {
+    /// The ending used by most lines of `content` (`Lf` on a tie or no lines)
+    pub fn detect(content: &str) -> Self {
+        let crlf = content.matches("\r\n").count();
+        let lf = content.matches('\n').count() - crlf;
+        if crlf > lf {
+            LineEnding::CrLf
+        } else {
+            LineEnding::Lf
+        }
+    }
+
+    /// Rewrite every line ending of `content` in this style
+    pub fn apply(self, content: &str) -> String {
+        let lf = content.replace("\r\n", "\n");
+        match self {
+            LineEnding::Lf => lf,
+            LineEnding::CrLf => lf.replace('\n', "\r\n"),
+        }
+    }
+}
+
+/// Give `content` the dominant line ending of the file's `previous` content
+///
+/// New files (no `previous`) are written as they are.
+pub fn match_line_ending(content: &str, previous: Option<&str>) -> String {
+    match previous.map(LineEnding::detect) {
+        Some(ending) if ending != LineEnding::detect(content) => ending.apply(content),
+        _ => content.to_string(),
+    }
+}
+
+/// The text `save_config` writes for `content`
+///
+/// Adds the comment header and, when the file already has `previous`
+/// content, keeps the comments of its unchanged keys and its line endings.
+pub fn config_output(content: &str, previous: Option<&str>) -> String {
+    let with_comments = add_config_comments(content);
+    match previous {
+        Some(previous) => {
+            match_line_ending(&carry_over_comments(&with_comments, previous), Some(previous))
+        }
+        None => with_comments,
+    }
+}
+
+/// What saving would change in a file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfigDiff {
+    /// Unified diff of the file, empty when nothing changes
+    pub diff: String,
+    /// Number of lines the save adds
+    pub added_lines: usize,
+    /// Number of lines the save removes
+    pub removed_lines: usize,
+    /// Comments of the current file the save drops, see [`carry_over_comments`]
+    pub dropped_comments: Vec<String>,
+}
+
+/// Comments of `old` that `new` no longer contains, in order
+///
+/// Occurrences are counted, so a comment written twice in `old` and kept
+/// once is reported once.
+pub fn dropped_comments(old: &str, new: &str) -> Vec<String> {
+    let comments = |content| {
+        tokenize_jsonc(content)
+            .into_iter()
+            .filter(|token| token.kind == TokenKind::Comment)
+            .map(move |token| &content[token.span])
+    };
+
+    let mut kept: std::collections::HashMap<&str, usize> = std::collections::HashMap::new();
+    for comment in comments(new) {
+        *kept.entry(comment).or_default() += 1;
+    }
+
+    comments(old)
+        .filter(|comment| match kept.get_mut(comment) {
+            Some(count) if *count > 0 => {
+                *count -= 1;
+                false
+            }
+            _ => true,
+        })
+        .map(str::to_string)
+        .collect()
+}
+
+/// Compute a unified diff (3 lines of context) from `old` to `new`
+pub fn diff_config(path: &str, old: &str, new: &str) -> ConfigDiff {
+    let text_diff = TextDiff::from_lines(old, new);
+    let (mut added_lines, mut removed_lines) = (0, 0);
+    for change in text_diff.iter_all_changes() {
+        match change.tag() {
+            ChangeTag::Insert => added_lines += 1,
+            ChangeTag::Delete => removed_lines += 1,
+            ChangeTag::Equal => {}
+        }
+    }
+
+    let diff = if added_lines + removed_lines == 0 {
+        String::new()
+    } else {
+        text_diff
+            .unified_diff()
+            .context_radius(3)
+            .header(&format!("a/{}", path), &format!("b/{}", path))
+            .to_string()
+    };
+
+    ConfigDiff {
+        diff,
+        added_lines,
+        removed_lines,
+        dropped_comments: dropped_comments(old, new),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -170,6 +641,21 @@ mod tests {
         assert!(backup2.exists());
     }
 
+    #[test]
+    fn test_backups_within_one_second_keep_both_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("test.json");
+
+        fs::write(&file_path, "content 1").unwrap();
+        let backup1 = create_backup(file_path.to_str().unwrap()).unwrap();
+        fs::write(&file_path, "content 2").unwrap();
+        let backup2 = create_backup(file_path.to_str().unwrap()).unwrap();
+
+        assert_ne!(backup1, backup2);
+        assert_eq!(fs::read_to_string(&backup1).unwrap(), "content 1");
+        assert_eq!(fs::read_to_string(&backup2).unwrap(), "content 2");
+    }
+
     // ========================================
     // Write Config File Tests
     // ========================================
@@ -190,13 +676,69 @@ mod tests {
         // Check backup exists
         let backup_files: Vec<_> = fs::read_dir(temp_dir.path())
             .unwrap()
-            .filter_map(Result::ok)
+            .filter_map(|e| e.ok())
             .filter(|e| e.file_name().to_str().unwrap().contains("backup"))
             .collect();
 
         assert_eq!(backup_files.len(), 1);
     }
 
+    #[test]
+    fn test_write_config_file_leaves_no_temp_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("config.jsonc");
+        fs::write(&file_path, "{}").unwrap();
+
+        write_config_file(file_path.to_str().unwrap(), r#"{"height": 30}"#).unwrap();
+
+        assert_eq!(fs::read_to_string(&file_path).unwrap(), r#"{"height": 30}"#);
+        let leftovers: Vec<_> = fs::read_dir(temp_dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().ends_with(".tmp"))
+            .collect();
+        assert!(leftovers.is_empty());
+    }
+
+    #[test]
+    fn test_write_config_file_keeps_symlink() {
+        let temp_dir = TempDir::new().unwrap();
+        let real_path = temp_dir.path().join("dotfiles-config.jsonc");
+        let link_path = temp_dir.path().join("config.jsonc");
+        fs::write(&real_path, "{}").unwrap();
+        std::os::unix::fs::symlink(&real_path, &link_path).unwrap();
+
+        write_config_file(link_path.to_str().unwrap(), "{\"a\": 1}").unwrap();
+
+        assert!(link_path.is_symlink());
+        assert_eq!(fs::read_to_string(&real_path).unwrap(), "{\"a\": 1}");
+    }
+
+    #[test]
+    fn test_prune_backups_keeps_newest_of_same_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path();
+        for name in [
+            "config.jsonc",
+            "config.jsonc.backup.999999999",
+            "config.jsonc.backup.1700000000",
+            "config.jsonc.backup.1700000100",
+            "config.jsonc.backup.1700000200",
+            "style.css.backup.1600000000",
+        ] {
+            fs::write(dir.join(name), "x").unwrap();
+        }
+
+        let removed = prune_backups(dir.to_str().unwrap(), "config.jsonc", 2).unwrap();
+
+        // Ordered by timestamp, not name, so the 9-digit one is the oldest
+        assert_eq!(removed, vec!["config.jsonc.backup.999999999", "config.jsonc.backup.1700000000"]);
+        assert!(dir.join("config.jsonc.backup.1700000100").exists());
+        assert!(dir.join("config.jsonc.backup.1700000200").exists());
+        assert!(dir.join("style.css.backup.1600000000").exists());
+        assert!(dir.join("config.jsonc").exists());
+    }
+
     #[test]
     fn test_write_config_file_creates_parent_dirs() {
         let temp_dir = TempDir::new().unwrap();
@@ -219,6 +761,14 @@ mod tests {
         perms.set_mode(0o444); // Read-only
         fs::set_permissions(&dir_path, perms).unwrap();
 
+        // Permissions aren't enforced for privileged users (e.g. root in CI containers)
+        if fs::write(dir_path.join(".probe"), "").is_ok() {
+            let mut perms = fs::metadata(&dir_path).unwrap().permissions();
+            perms.set_mode(0o755);
+            fs::set_permissions(&dir_path, perms).unwrap();
+            return;
+        }
+
         let file_path = dir_path.join("config.json");
         let result = write_config_file(file_path.to_str().unwrap(), "content");
 
@@ -308,6 +858,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::approx_constant)]
     fn test_format_json_preserves_types() {
         let value = serde_json::json!({
             "string": "text",
@@ -331,6 +882,33 @@ mod tests {
         assert!(parsed["object"].is_object());
     }
 
+    #[test]
+    fn test_format_json_keeps_file_key_order() {
+        let content = r#"{
+            // Sections first, then module blocks
+            "modules-left": ["sway/workspaces"],
+            "modules-center": ["clock"],
+            "modules-right": ["battery"],
+            "sway/workspaces": {},
+            "clock": { "format": "{:%H:%M}", "interval": 60 },
+            "battery": {}
+        }"#;
+
+        let config = crate::config::parser::parse_jsonc(content).unwrap();
+        let keys: Vec<&String> = config.as_object().unwrap().keys().collect();
+        assert_eq!(
+            keys,
+            ["modules-left", "modules-center", "modules-right", "sway/workspaces", "clock", "battery"]
+        );
+
+        let formatted = format_json(&config).unwrap();
+        let position = |key: &str| formatted.find(&format!("\"{}\":", key)).unwrap();
+        assert!(position("modules-left") < position("modules-center"));
+        assert!(position("modules-right") < position("sway/workspaces"));
+        assert!(position("format") < position("interval"));
+        assert!(position("clock") < position("battery"));
+    }
+
     // ========================================
     // Comment Addition Tests
     // ========================================
@@ -363,6 +941,47 @@ mod tests {
         assert!(header.contains("//"));
     }
 
+    // ========================================
+    // Comment Preservation Tests
+    // ========================================
+
+    #[test]
+    fn test_noop_save_keeps_key_comments() {
+        let old = r#"// header
+{
+    // Bar placement
+    "layer": "top", // trailing note
+    /* workspace icons */
+    // (one per workspace)
+    "hyprland/workspaces": { "format": "{icon}" },
+    "height": 30
+}"#;
+        let new = add_config_comments(
+            &format_json(&crate::config::parser::parse_jsonc(old).unwrap()).unwrap(),
+        );
+
+        let merged = carry_over_comments(&new, old);
+        assert!(merged.contains("  // Bar placement\n  \"layer\": \"top\""));
+        assert!(merged.contains(
+            "  /* workspace icons */\n  // (one per workspace)\n  \"hyprland/workspaces\""
+        ));
+        assert!(!merged.contains("trailing note"));
+        assert_eq!(
+            crate::config::parser::parse_jsonc(&merged).unwrap(),
+            crate::config::parser::parse_jsonc(old).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_changed_value_drops_its_comment() {
+        let old = "[\n  {\n    // main height\n    \"height\": 30\n  },\n  {\n    // second\n    \"height\": 20\n  }\n]";
+        let new = "[{\"height\": 31}, {\"height\": 20}]";
+
+        let merged = carry_over_comments(new, old);
+        assert_eq!(merged, "[{\"height\": 31}, {// second\n\"height\": 20}]");
+        assert!(crate::config::parser::parse_jsonc(&merged).is_ok());
+    }
+
     // ========================================
     // Error Handling Tests
     // ========================================
@@ -407,17 +1026,141 @@ mod tests {
         // 3. Verify backup exists with original content
         let backups: Vec<_> = fs::read_dir(temp_dir.path())
             .unwrap()
-            .filter_map(Result::ok)
+            .filter_map(|e| e.ok())
             .filter(|e| e.file_name().to_str().unwrap().contains("backup"))
             .collect();
 
         assert_eq!(backups.len(), 1);
         let backup_content = fs::read_to_string(backups[0].path()).unwrap();
-        assert!(backup_content.contains(r#""modules-left": ["clock"]"#));
+        // Pretty-printing splits the array over lines, so compare parsed values
+        let backup_config = crate::config::parser::parse_jsonc(&backup_content).unwrap();
+        assert_eq!(backup_config["modules-left"], serde_json::json!(["clock"]));
         assert!(!backup_content.contains("battery"));
 
         // 4. Verify current file has updated content
         let current_content = fs::read_to_string(&file_path).unwrap();
         assert!(current_content.contains("battery"));
     }
+
+    // ========================================
+    // Disk Space Tests
+    // ========================================
+
+    #[test]
+    fn test_insufficient_disk_space() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("config.jsonc");
+        fs::write(&file_path, "x".repeat(100)).unwrap();
+
+        let result = ensure_disk_space_with(&file_path, 50, |_| Ok(299));
+        match result {
+            Err(AppError::StorageFull(msg)) => assert!(msg.contains("Insufficient disk space")),
+            other => panic!("Expected StorageFull error, got {:?}", other),
+        }
+
+        assert!(ensure_disk_space_with(&file_path, 50, |_| Ok(300)).is_ok());
+    }
+
+    #[test]
+    fn test_disk_space_for_new_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let file_path = temp_dir.path().join("nested/config.jsonc");
+
+        assert!(ensure_disk_space_with(&file_path, 10, |_| Ok(29)).is_err());
+        assert!(ensure_disk_space_with(&file_path, 10, |_| Ok(30)).is_ok());
+        assert!(free_disk_space(&file_path).unwrap() > 0);
+    }
+
+    #[test]
+    fn test_line_ending_detect_and_apply() {
+        assert_eq!(LineEnding::detect("a\r\nb\r\nc\n"), LineEnding::CrLf);
+        assert_eq!(LineEnding::detect("a\r\nb\nc\n"), LineEnding::Lf);
+        assert_eq!(LineEnding::detect("single line"), LineEnding::Lf);
+
+        assert_eq!(LineEnding::CrLf.apply("a\nb\r\n"), "a\r\nb\r\n");
+        assert_eq!(LineEnding::Lf.apply("a\r\nb\n"), "a\nb\n");
+    }
+
+    #[test]
+    fn test_config_output_keeps_crlf() {
+        let previous = "{\r\n  // Bar placement\r\n  \"layer\": \"top\"\r\n}\r\n";
+        let content = "{\n  \"layer\": \"top\",\n  \"height\": 30\n}";
+
+        let output = config_output(content, Some(previous));
+        assert!(output.contains("  // Bar placement\r\n  \"layer\": \"top\""));
+        assert_eq!(output.matches('\n').count(), output.matches("\r\n").count());
+
+        // New files keep the editor's endings
+        assert!(!config_output(content, None).contains('\r'));
+    }
+
+    #[test]
+    fn test_diff_config() {
+        let old = "{\n    \"height\": 30,\n    \"layer\": \"top\"\n}\n";
+        let new = "{\n    \"height\": 32,\n    \"layer\": \"top\"\n}\n";
+
+        let diff = diff_config("config.jsonc", old, new);
+        assert_eq!(diff.added_lines, 1);
+        assert_eq!(diff.removed_lines, 1);
+        assert!(diff.diff.starts_with("--- a/config.jsonc\n+++ b/config.jsonc\n@@ -1,4 +1,4 @@\n"));
+        assert!(diff.diff.contains("\n-    \"height\": 30,\n+    \"height\": 32,\n"));
+
+        let unchanged = diff_config("config.jsonc", old, old);
+        assert_eq!(
+            unchanged,
+            ConfigDiff { diff: String::new(), added_lines: 0, removed_lines: 0, dropped_comments: Vec::new() }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_config_output_matches_saved_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("config.jsonc");
+        let previous = "// Top bar height\n{\n    // Taller for the clock\n    \"height\": 30\n}\n";
+        fs::write(&path, previous).unwrap();
+
+        let content = "{\n    \"height\": 30,\n    \"layer\": \"top\"\n}";
+        let expected = config_output(content, Some(previous));
+        assert!(expected.contains("// Taller for the clock"));
+
+        let path_str = path.to_string_lossy().to_string();
+        crate::commands::save_config(path_str, content.to_string(), None).await.unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_diff_config_lists_dropped_comments() {
+        let previous = concat!(
+            "{\n",
+            "    // Taller for the clock\n",
+            "    \"height\": 30, // pixels\n",
+            "    \"clock\": {\n",
+            "        // 24h\n",
+            "        \"format\": \"{:%H}\"\n",
+            "    }\n",
+            "    // end\n",
+            "}\n",
+        );
+        let content = "{\n    \"height\": 30,\n    \"clock\": { \"format\": \"{:%H}\" }\n}";
+
+        let output = config_output(content, Some(previous));
+        let diff = diff_config("config.jsonc", previous, &output);
+        assert_eq!(diff.dropped_comments, vec!["// pixels", "// 24h", "// end"]);
+    }
+
+    #[test]
+    fn test_write_preserves_permissions() {
+        let temp_dir = TempDir::new().unwrap();
+        let mode = |path: &Path| fs::metadata(path).unwrap().permissions().mode() & 0o777;
+
+        let private = temp_dir.path().join("config.jsonc");
+        fs::write(&private, "{}").unwrap();
+        fs::set_permissions(&private, fs::Permissions::from_mode(0o600)).unwrap();
+        write_config_file(private.to_str().unwrap(), "{ \"height\": 30 }").unwrap();
+        assert_eq!(mode(&private), 0o600);
+
+        let new = temp_dir.path().join("style.css");
+        write_config_file(new.to_str().unwrap(), "* {}").unwrap();
+        assert_eq!(mode(&new), 0o644);
+    }
 }
@@ -2,13 +2,16 @@
 // CUSTOM ERROR TYPES
 // ============================================================================
 
-use serde::Serialize;
+use serde::ser::SerializeStruct;
+use serde::{Serialize, Serializer};
 use thiserror::Error;
 
 /// Application error types
 /// Provides structured error handling for all Rust-side operations
-#[derive(Error, Debug, Serialize)]
-#[serde(tag = "type", content = "message")]
+///
+/// Serializes as `{ "type": "NotFound", "message": "...", "code": "NOT_FOUND" }`;
+/// the frontend should branch on `code` and only display `message`.
+#[derive(Error, Debug)]
 pub enum AppError {
     #[error("IO error: {0}")]
     Io(String),
@@ -31,10 +34,104 @@ pub enum AppError {
     #[error("Already exists: {0}")]
     AlreadyExists(String),
 
+    #[error("No space left: {0}")]
+    StorageFull(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
 
+/// Coarse error categories, see [`AppError::kind`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ErrorKind {
+    /// A file or resource doesn't exist
+    NotFound,
+    /// The OS refused access
+    PermissionDenied,
+    /// Something is in the way of a create
+    AlreadyExists,
+    /// The disk (or quota) is full
+    StorageFull,
+    /// Any other I/O failure
+    Io,
+    /// The content or arguments are invalid (config, parse and validation errors)
+    InvalidInput,
+    /// A bug or an unexpected environment
+    Internal,
+}
+
+impl AppError {
+    /// Stable machine-readable code for the error kind
+    pub fn code(&self) -> &'static str {
+        match self {
+            AppError::Io(_) => "IO_ERROR",
+            AppError::Config(_) => "CONFIG_ERROR",
+            AppError::Parse(_) => "PARSE_ERROR",
+            AppError::Validation(_) => "VALIDATION_ERROR",
+            AppError::NotFound(_) => "NOT_FOUND",
+            AppError::PermissionDenied(_) => "PERMISSION_DENIED",
+            AppError::AlreadyExists(_) => "ALREADY_EXISTS",
+            AppError::StorageFull(_) => "STORAGE_FULL",
+            AppError::Internal(_) => "INTERNAL_ERROR",
+        }
+    }
+
+    /// Coarse category of the error, for deciding how to react to it
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            AppError::NotFound(_) => ErrorKind::NotFound,
+            AppError::PermissionDenied(_) => ErrorKind::PermissionDenied,
+            AppError::AlreadyExists(_) => ErrorKind::AlreadyExists,
+            AppError::StorageFull(_) => ErrorKind::StorageFull,
+            AppError::Io(_) => ErrorKind::Io,
+            AppError::Config(_) | AppError::Parse(_) | AppError::Validation(_) => {
+                ErrorKind::InvalidInput
+            }
+            AppError::Internal(_) => ErrorKind::Internal,
+        }
+    }
+
+    /// Variant name, serialized as `type`
+    fn type_name(&self) -> &'static str {
+        match self {
+            AppError::Io(_) => "Io",
+            AppError::Config(_) => "Config",
+            AppError::Parse(_) => "Parse",
+            AppError::Validation(_) => "Validation",
+            AppError::NotFound(_) => "NotFound",
+            AppError::PermissionDenied(_) => "PermissionDenied",
+            AppError::AlreadyExists(_) => "AlreadyExists",
+            AppError::StorageFull(_) => "StorageFull",
+            AppError::Internal(_) => "Internal",
+        }
+    }
+
+    /// Human-readable message, without the kind prefix
+    pub fn message(&self) -> &str {
+        match self {
+            AppError::Io(message)
+            | AppError::Config(message)
+            | AppError::Parse(message)
+            | AppError::Validation(message)
+            | AppError::NotFound(message)
+            | AppError::PermissionDenied(message)
+            | AppError::AlreadyExists(message)
+            | AppError::StorageFull(message)
+            | AppError::Internal(message) => message,
+        }
+    }
+}
+
+impl Serialize for AppError {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        let mut error = serializer.serialize_struct("AppError", 3)?;
+        error.serialize_field("type", self.type_name())?;
+        error.serialize_field("message", self.message())?;
+        error.serialize_field("code", self.code())?;
+        error.end()
+    }
+}
+
 /// Convert std::io::Error to AppError
 impl From<std::io::Error> for AppError {
     fn from(err: std::io::Error) -> Self {
@@ -42,6 +139,9 @@ impl From<std::io::Error> for AppError {
             std::io::ErrorKind::NotFound => AppError::NotFound(err.to_string()),
             std::io::ErrorKind::PermissionDenied => AppError::PermissionDenied(err.to_string()),
             std::io::ErrorKind::AlreadyExists => AppError::AlreadyExists(err.to_string()),
+            std::io::ErrorKind::StorageFull | std::io::ErrorKind::QuotaExceeded => {
+                AppError::StorageFull(err.to_string())
+            }
             _ => AppError::Io(err.to_string()),
         }
     }
@@ -56,3 +156,36 @@ impl From<serde_json::Error> for AppError {
 
 /// Result type alias for AppError
 pub type Result<T> = std::result::Result<T, AppError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_serializes_with_code() {
+        let error = AppError::NotFound("Config file not found: /tmp/config".to_string());
+        assert_eq!(
+            serde_json::to_value(&error).unwrap(),
+            serde_json::json!({
+                "type": "NotFound",
+                "message": "Config file not found: /tmp/config",
+                "code": "NOT_FOUND"
+            })
+        );
+        assert_eq!(AppError::PermissionDenied(String::new()).code(), "PERMISSION_DENIED");
+    }
+
+    #[test]
+    fn test_io_error_kind_is_kept() {
+        let full = AppError::from(std::io::Error::from_raw_os_error(28)); // ENOSPC
+        assert_eq!(full.kind(), ErrorKind::StorageFull);
+        assert_eq!(full.code(), "STORAGE_FULL");
+
+        let denied = AppError::from(std::io::Error::from_raw_os_error(13)); // EACCES
+        assert_eq!(denied.kind(), ErrorKind::PermissionDenied);
+
+        let other = AppError::from(std::io::Error::from_raw_os_error(5)); // EIO
+        assert_eq!(other.kind(), ErrorKind::Io);
+        assert_eq!(AppError::Parse(String::new()).kind(), ErrorKind::InvalidInput);
+    }
+}
@@ -8,6 +8,7 @@ pub mod config;
 pub mod commands;
 pub mod waybar;
 pub mod system;
+pub mod logging;
 
 // Learn more about Tauri commands at https://tauri.app/develop/calling-rust/
 #[tauri::command]
@@ -15,33 +16,166 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// Wrap the command handler so every invocation is written to the diagnostics log
+///
+/// Tauri doesn't expose the resolver for wrapping, so the outcome is reported
+/// back by the frontend through `log_command_result`.
+fn with_invocation_logging<R: tauri::Runtime>(
+    handler: impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        let command = invoke.message.command().to_string();
+        let args = match invoke.message.payload() {
+            tauri::ipc::InvokeBody::Json(args) => Some(args.clone()),
+            _ => None,
+        };
+        logging::log_dispatch(&command, args.as_ref(), || handler(invoke))
+    }
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Falls back to logging to stderr
+    if let Err(e) = logging::init() {
+        log::warn!("Diagnostics log file disabled: {}", e);
+    }
+
+    let handler = with_invocation_logging(tauri::generate_handler![
+        greet,
+        // Config commands
+        commands::detect_config_paths,
+        commands::detect_config_paths_in,
+        commands::create_default_config,
+        commands::load_config,
+        commands::save_config,
+        commands::preview_save_config,
+        commands::validate_config,
+        commands::validate_config_schema,
+        commands::count_bars,
+        commands::merge_config_files,
+        commands::resolve_includes,
+        commands::find_duplicate_includes,
+        commands::split_config,
+        commands::flatten_config,
+        commands::reload_needed,
+        commands::config_fingerprint,
+        commands::benchmark_parse,
+        commands::check_encoding,
+        commands::get_free_disk_space,
+        commands::set_disk_space_check,
+        commands::load_css,
+        commands::save_css,
+        commands::watch_config,
+        commands::stop_watch_config,
+        commands::flatten_css,
+        commands::normalize_hex_colors,
+        commands::extract_color_definitions,
+        commands::set_color_definition,
+        commands::list_backups,
+        commands::export_config,
+        commands::import_config,
+        commands::restore_backup,
+        commands::migrate_backups,
+        commands::create_snapshot,
+        commands::list_snapshots,
+        commands::restore_snapshot,
+        commands::delete_snapshot,
+        commands::save_theme,
+        commands::apply_theme,
+        commands::list_themes,
+        commands::list_junk_files,
+        commands::clean_junk_files,
+        commands::migrate_to_jsonc,
+        commands::migrate_modules,
+        commands::preview_migration,
+        commands::reindent,
+        commands::find_trailing_whitespace,
+        commands::trim_trailing_whitespace,
+        commands::find_single_quoted_strings,
+        commands::fix_quotes,
+        commands::render_ascii_preview,
+        commands::list_config_modules,
+        commands::modules_available_in_version,
+        commands::set_all_tooltips,
+        commands::export_portable,
+        commands::import_portable,
+        commands::relink_script,
+        // Git commands
+        commands::commit_config,
+        commands::config_git_log,
+        commands::restore_from_commit,
+        // Lint commands
+        commands::check_group_orientation,
+        commands::check_group_drawers,
+        commands::check_layer_settings,
+        commands::check_compositor_features,
+        commands::check_reload_options,
+        commands::check_format_placeholders,
+        commands::check_format_alt,
+        commands::check_tooltip_fields,
+        commands::check_format_icon_types,
+        commands::check_clock_intervals,
+        commands::check_unknown_keys,
+        commands::find_duplicate_click_actions,
+        commands::check_scroll_actions,
+        commands::check_cpu_format_icons,
+        commands::check_workspace_icons,
+        commands::check_network_interfaces,
+        commands::check_mpris_players,
+        commands::check_length_limits,
+        commands::compare_bar_dimensions,
+        commands::compare_bar_layers,
+        commands::find_undefined_colors,
+        commands::find_unused_css_rules,
+        commands::find_font_overrides,
+        // Waybar commands
+        waybar::reload_waybar,
+        waybar::reload_waybar_safe,
+        waybar::toggle_waybar_visibility,
+        waybar::is_waybar_running,
+        waybar::get_waybar_pids,
+        waybar::get_running_waybar_config_path,
+        waybar::get_waybar_stats,
+        waybar::start_waybar,
+        waybar::start_waybar_with,
+        waybar::start_waybar_capturing,
+        waybar::stop_waybar,
+        waybar::restart_waybar,
+        waybar::get_waybar_version,
+        // System commands
+        system::detect_compositor,
+        system::get_compositor_info,
+        system::is_compositor_running,
+        system::get_cpu_info,
+        system::list_network_interfaces,
+        system::list_mpris_players,
+        system::get_session_details,
+        system::get_waybar_environment,
+        system::get_power_profiles,
+        system::get_hyprland_monitors,
+        system::get_hyprland_workspaces,
+        system::get_sway_outputs,
+        system::get_sway_workspaces,
+        system::is_waybar_service_active,
+        system::is_waybar_service_enabled,
+        system::start_waybar_service,
+        system::stop_waybar_service,
+        system::restart_waybar_service,
+        system::reload_waybar_service,
+        system::get_keyboard_layouts,
+        system::capture_system_profile,
+        system::load_system_profile,
+        system::clear_system_profile,
+        // Diagnostics commands
+        logging::set_log_level,
+        logging::get_app_log_path,
+        logging::log_command_result,
+    ]);
+
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_fs::init())
-        .invoke_handler(tauri::generate_handler![
-            greet,
-            // Config commands
-            commands::detect_config_paths,
-            commands::load_config,
-            commands::save_config,
-            commands::load_css,
-            commands::save_css,
-            commands::list_backups,
-            commands::restore_backup,
-            // Waybar commands
-            waybar::reload_waybar,
-            waybar::is_waybar_running,
-            waybar::get_waybar_pids,
-            waybar::start_waybar,
-            waybar::stop_waybar,
-            waybar::restart_waybar,
-            // System commands
-            system::detect_compositor,
-            system::get_compositor_info,
-            system::is_compositor_running,
-        ])
+        .invoke_handler(handler)
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
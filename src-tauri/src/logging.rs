@@ -0,0 +1,273 @@
+// ============================================================================
+// DIAGNOSTICS LOGGING
+// ============================================================================
+//
+// Logging for troubleshooting the app itself. Everything logged through the
+// `log` macros, plus every command invocation and its outcome, is appended to
+// `app.log` in the user's cache dir with the home directory redacted, so the
+// file can be attached to bug reports as-is. Records go through `fern`.
+
+use crate::config::edit::dehome;
+use crate::error::{AppError, Result};
+use log::LevelFilter;
+use serde_json::Value;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::OnceLock;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directory name under the cache dir
+const APP_CACHE_DIR: &str = "waybar-config-gui";
+
+/// Log file name
+const LOG_FILE_NAME: &str = "app.log";
+
+/// Level used until the user picks one
+const DEFAULT_LEVEL: LevelFilter = LevelFilter::Info;
+
+/// Log target for command invocations and their outcome
+const COMMAND_TARGET: &str = "command";
+
+/// Command the frontend calls to report another command's outcome
+const RESULT_COMMAND: &str = "log_command_result";
+
+/// Path of the log file, once logging to it is set up
+static LOG_PATH: OnceLock<PathBuf> = OnceLock::new();
+
+// ============================================================================
+// SETUP
+// ============================================================================
+
+/// Get the diagnostics log path (`$XDG_CACHE_HOME/waybar-config-gui/app.log`,
+/// falling back to `~/.cache`)
+pub fn app_log_path() -> Result<PathBuf> {
+    let cache_dir = match std::env::var("XDG_CACHE_HOME") {
+        Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => {
+            let home = std::env::var("HOME")
+                .map_err(|_| AppError::Config("HOME environment variable not set".to_string()))?;
+            PathBuf::from(home).join(".cache")
+        }
+    };
+
+    Ok(cache_dir.join(APP_CACHE_DIR).join(LOG_FILE_NAME))
+}
+
+/// Build a dispatch formatting records with a timestamp and the home
+/// directory redacted
+///
+/// The dispatch itself passes every level; `log::max_level` does the
+/// filtering, so [`set_log_level`] applies immediately.
+fn dispatch() -> fern::Dispatch {
+    let home = std::env::var("HOME").ok();
+
+    fern::Dispatch::new()
+        .format(move |out, message, record| {
+            let message = message.to_string();
+            let message = match &home {
+                Some(home) => dehome(&message, home).unwrap_or(message),
+                None => message,
+            };
+            let timestamp = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+
+            out.finish(format_args!("{} {:<5} {}: {}", timestamp, record.level(), record.target(), message))
+        })
+        .level(LevelFilter::Trace)
+}
+
+/// Install `dispatch` as the global logger
+fn apply(dispatch: fern::Dispatch) -> Result<()> {
+    dispatch
+        .apply()
+        .map_err(|e| AppError::Internal(format!("Failed to install logger: {}", e)))?;
+    log::set_max_level(DEFAULT_LEVEL);
+    Ok(())
+}
+
+/// Open the log file at `path`, creating its directory
+fn open_log_file(path: &Path) -> Result<fs::File> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    Ok(fern::log_file(path)?)
+}
+
+/// Install the file logger at `path`
+///
+/// If the file can't be opened, records go to stderr instead and the error
+/// is returned. Only the first call installs a logger; later calls are
+/// no-ops.
+fn init_at(path: &Path) -> Result<()> {
+    if LOG_PATH.get().is_some() {
+        return Ok(());
+    }
+
+    match open_log_file(path) {
+        Ok(file) => {
+            apply(dispatch().chain(file))?;
+            let _ = LOG_PATH.set(path.to_path_buf());
+            Ok(())
+        }
+        Err(e) => {
+            let _ = apply(dispatch().chain(std::io::stderr()));
+            Err(e)
+        }
+    }
+}
+
+/// Install the file logger at [`app_log_path`], falling back to stderr
+pub fn init() -> Result<()> {
+    match app_log_path() {
+        Ok(path) => init_at(&path),
+        Err(e) => {
+            let _ = apply(dispatch().chain(std::io::stderr()));
+            Err(e)
+        }
+    }
+}
+
+// ============================================================================
+// COMMAND LOGGING
+// ============================================================================
+
+/// Run a command handler, logging the invocation and its arguments at debug
+/// level
+///
+/// `handler` returns whether it knew the command, like Tauri's invoke
+/// handler; an unknown command is logged as a warning. The handler answers
+/// the frontend itself, so the outcome is logged by [`log_outcome`] once the
+/// frontend reports it.
+pub fn log_dispatch(command: &str, args: Option<&Value>, handler: impl FnOnce() -> bool) -> bool {
+    match args {
+        _ if command == RESULT_COMMAND => {}
+        Some(args) => log::debug!(target: COMMAND_TARGET, "{} {}", command, args),
+        None => log::debug!(target: COMMAND_TARGET, "{}", command),
+    }
+
+    let handled = handler();
+    if !handled {
+        log::warn!(target: COMMAND_TARGET, "Unknown command: {}", command);
+    }
+    handled
+}
+
+/// Log the outcome of a command, given the error it answered with if any
+///
+/// Success is logged at debug level, failure as a warning with the error's
+/// code and message.
+pub fn log_outcome(command: &str, error: Option<&Value>) {
+    let Some(error) = error else {
+        log::debug!(target: COMMAND_TARGET, "{} ok", command);
+        return;
+    };
+
+    match (error["code"].as_str(), error["message"].as_str()) {
+        (Some(code), Some(message)) => {
+            log::warn!(target: COMMAND_TARGET, "{} failed: {} {}", command, code, message)
+        }
+        _ => log::warn!(target: COMMAND_TARGET, "{} failed: {}", command, error),
+    }
+}
+
+// ============================================================================
+// COMMANDS
+// ============================================================================
+
+/// Set the diagnostics log level (`off`, `error`, `warn`, `info`, `debug`, `trace`)
+///
+/// `debug` records every command invocation with its arguments.
+#[tauri::command]
+pub async fn set_log_level(level: String) -> Result<()> {
+    let filter = LevelFilter::from_str(&level)
+        .map_err(|_| AppError::Validation(format!("Unknown log level: {}", level)))?;
+
+    log::set_max_level(filter);
+    log::info!("Log level set to {}", filter);
+    Ok(())
+}
+
+/// Record the outcome of a command invocation
+///
+/// Tauri gives the invoke handler no view of the reply, so the frontend's
+/// `invoke` wrapper reports each result once the call settles.
+#[tauri::command]
+pub async fn log_command_result(command: String, error: Option<Value>) -> Result<()> {
+    log_outcome(&command, error.as_ref());
+    Ok(())
+}
+
+/// Get the path of the diagnostics log file
+#[tauri::command]
+pub async fn get_app_log_path() -> Result<String> {
+    let path = match LOG_PATH.get() {
+        Some(path) => path.clone(),
+        None => app_log_path()?,
+    };
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_command_invocation_is_logged() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("logs").join(LOG_FILE_NAME);
+        init_at(&path).unwrap();
+        set_log_level("debug".to_string()).await.unwrap();
+
+        let home = std::env::var("HOME").unwrap_or_else(|_| "/nonexistent-home".to_string());
+        let config_path = format!("{}/.config/waybar/config.jsonc", home);
+        assert!(log_dispatch("load_config", Some(&json!({ "path": config_path })), || true));
+        let error = AppError::NotFound(format!("Config file not found: {}", config_path));
+        log_command_result("load_config".to_string(), Some(serde_json::to_value(&error).unwrap()))
+            .await
+            .unwrap();
+        assert!(log_dispatch("detect_config_paths", None, || true));
+        log_outcome("detect_config_paths", None);
+        assert!(!log_dispatch("no_such_command", None, || false));
+        log::logger().flush();
+
+        let logged = fs::read_to_string(&path).unwrap();
+        let line = |needle: &str| {
+            logged
+                .lines()
+                .find(|line| line.contains(needle))
+                .unwrap_or_else(|| panic!("{:?} should be logged", needle))
+                .to_string()
+        };
+
+        let invocation = line("command: load_config");
+        assert!(invocation.contains("DEBUG"));
+        assert!(invocation.contains("~/.config/waybar/config.jsonc"));
+        assert!(!invocation.contains(&home));
+
+        let failure = line("command: load_config failed: NOT_FOUND");
+        assert!(failure.contains("WARN"));
+        assert!(failure.contains("Config file not found: ~/.config/waybar/config.jsonc"));
+        assert!(line("command: detect_config_paths ok").contains("DEBUG"));
+        assert!(line("Unknown command: no_such_command").contains("WARN"));
+
+        assert_eq!(get_app_log_path().await.unwrap(), path.to_string_lossy());
+    }
+
+    #[tokio::test]
+    async fn test_set_log_level_rejects_unknown_level() {
+        assert!(matches!(
+            set_log_level("loud".to_string()).await,
+            Err(AppError::Validation(_))
+        ));
+    }
+}
@@ -25,6 +25,14 @@ pub enum Compositor {
     Dwl,
     /// Niri compositor
     Niri,
+    /// Wayfire compositor
+    Wayfire,
+    /// labwc compositor
+    Labwc,
+    /// GNOME Shell (Mutter)
+    Gnome,
+    /// KDE Plasma (KWin)
+    Kde,
     /// Generic Wayland compositor (or unknown)
     Unknown,
 }
@@ -38,6 +46,10 @@ impl Compositor {
             Compositor::River => "river",
             Compositor::Dwl => "dwl",
             Compositor::Niri => "niri",
+            Compositor::Wayfire => "wayfire",
+            Compositor::Labwc => "labwc",
+            Compositor::Gnome => "gnome",
+            Compositor::Kde => "kde",
             Compositor::Unknown => "unknown",
         }
     }
@@ -63,6 +75,10 @@ impl From<&str> for Compositor {
             "river" => Compositor::River,
             "dwl" => Compositor::Dwl,
             "niri" => Compositor::Niri,
+            "wayfire" => Compositor::Wayfire,
+            "labwc" => Compositor::Labwc,
+            "gnome" => Compositor::Gnome,
+            "kde" => Compositor::Kde,
             _ => Compositor::Unknown,
         }
     }
@@ -76,7 +92,7 @@ impl From<&str> for Compositor {
  * Detect the currently running Wayland compositor
  *
  * Detection strategy:
- * 1. Check $XDG_CURRENT_DESKTOP environment variable
+ * 1. Check $XDG_CURRENT_DESKTOP environment variable (a `:`-separated list)
  * 2. Check $WAYLAND_DISPLAY to confirm Wayland is running
  * 3. Check process list for known compositor names (fallback)
  *
@@ -90,6 +106,10 @@ impl From<&str> for Compositor {
  * - River
  * - DWL
  * - Niri
+ * - Wayfire
+ * - labwc
+ * - GNOME
+ * - KDE Plasma
  */
 #[tauri::command]
 pub async fn detect_compositor() -> Result<String> {
@@ -107,9 +127,9 @@ fn detect_compositor_internal() -> Result<Compositor> {
         return Ok(Compositor::Unknown);
     }
 
-    // Strategy 1: Check XDG_CURRENT_DESKTOP
+    // Strategy 1: Check XDG_CURRENT_DESKTOP (e.g. "ubuntu:GNOME")
     if let Ok(desktop) = env::var("XDG_CURRENT_DESKTOP") {
-        let compositor = Compositor::from(desktop.as_str());
+        let compositor = compositor_from_desktop(&desktop);
         if compositor.is_known() {
             return Ok(compositor);
         }
@@ -134,6 +154,17 @@ fn detect_compositor_internal() -> Result<Compositor> {
     Ok(Compositor::Unknown)
 }
 
+/**
+ * Find the first known compositor in an `XDG_CURRENT_DESKTOP` value
+ */
+fn compositor_from_desktop(desktop: &str) -> Compositor {
+    desktop
+        .split(':')
+        .map(|name| Compositor::from(name.trim()))
+        .find(Compositor::is_known)
+        .unwrap_or(Compositor::Unknown)
+}
+
 /**
  * Check if running in a Wayland session
  * Checks for WAYLAND_DISPLAY environment variable
@@ -156,6 +187,10 @@ fn detect_from_processes() -> Result<Compositor> {
         ("river", Compositor::River),
         ("dwl", Compositor::Dwl),
         ("niri", Compositor::Niri),
+        ("wayfire", Compositor::Wayfire),
+        ("labwc", Compositor::Labwc),
+        ("gnome-shell", Compositor::Gnome),
+        ("kwin_wayland", Compositor::Kde),
     ];
 
     for (process_name, compositor) in compositors {
@@ -187,10 +222,12 @@ fn detect_from_processes() -> Result<Compositor> {
 pub async fn get_compositor_info() -> Result<CompositorInfo> {
     let compositor = detect_compositor_internal()?;
     let version = get_compositor_version(&compositor).await.ok();
+    let parsed_version = version.as_deref().and_then(|raw| parse_version(&compositor, raw));
 
     Ok(CompositorInfo {
         name: compositor.to_string(),
         version,
+        parsed_version,
         session_type: if is_wayland_session() {
             "wayland".to_string()
         } else {
@@ -205,10 +242,47 @@ pub async fn get_compositor_info() -> Result<CompositorInfo> {
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CompositorInfo {
     pub name: String,
+    /// First line of `--version` output, as printed
     pub version: Option<String>,
+    /// Version number read from `version`
+    #[serde(default)]
+    pub parsed_version: Option<SemVer>,
     pub session_type: String,
 }
 
+/**
+ * A `major.minor.patch` version number
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, serde::Serialize, serde::Deserialize)]
+pub struct SemVer {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl SemVer {
+    /**
+     * Parse a version token such as `v0.41.2`, `1.9` or `1.10-dev`
+     * A missing patch number reads as 0; anything after the numbers is ignored
+     */
+    pub fn parse(token: &str) -> Option<Self> {
+        let token = token.trim().trim_start_matches('v');
+        let end = token
+            .find(|c: char| !c.is_ascii_digit() && c != '.')
+            .unwrap_or(token.len());
+        let mut parts = token[..end].trim_end_matches('.').split('.');
+
+        let major = parts.next()?.parse().ok()?;
+        let minor = parts.next()?.parse().ok()?;
+        let patch = match parts.next() {
+            Some(patch) => patch.parse().ok()?,
+            None => 0,
+        };
+
+        Some(Self { major, minor, patch })
+    }
+}
+
 /**
  * Get compositor version string
  */
@@ -221,6 +295,10 @@ async fn get_compositor_version(compositor: &Compositor) -> Result<String> {
         Compositor::River => "river",
         Compositor::Dwl => "dwl",
         Compositor::Niri => "niri",
+        Compositor::Wayfire => "wayfire",
+        Compositor::Labwc => "labwc",
+        Compositor::Gnome => "gnome-shell",
+        Compositor::Kde => "kwin_wayland",
         Compositor::Unknown => return Err(AppError::NotFound("Unknown compositor".to_string())),
     };
 
@@ -242,6 +320,39 @@ async fn get_compositor_version(compositor: &Compositor) -> Result<String> {
     }
 }
 
+/**
+ * Read the version number from a compositor's `--version` output
+ *
+ * Known shapes:
+ * - Hyprland: `Hyprland, built from branch main at commit abc1234 (...) tag v0.41.2`
+ *   (newer releases print `Hyprland 0.45.2 built from branch ...`)
+ * - Sway: `sway version 1.9`
+ * - Niri: `niri 0.1.10 (commit abc1234)`
+ *
+ * Other compositors, and unexpected shapes, fall back to the first
+ * word that reads as a version.
+ */
+pub fn parse_version(compositor: &Compositor, raw: &str) -> Option<SemVer> {
+    let after = |marker: &str| {
+        raw.find(marker)
+            .and_then(|pos| raw[pos + marker.len()..].split_whitespace().next())
+            .and_then(SemVer::parse)
+    };
+
+    let known = match compositor {
+        Compositor::Hyprland => after("tag v"),
+        Compositor::Sway => after("sway version "),
+        Compositor::Niri => after("niri "),
+        _ => None,
+    };
+
+    known.or_else(|| {
+        raw.split_whitespace()
+            .map(|word| word.trim_matches(|c: char| c == ',' || c == '(' || c == ')'))
+            .find_map(SemVer::parse)
+    })
+}
+
 /**
  * Check if a specific compositor is currently running
  */
@@ -290,10 +401,44 @@ mod tests {
         assert_eq!(Compositor::from("river"), Compositor::River);
         assert_eq!(Compositor::from("dwl"), Compositor::Dwl);
         assert_eq!(Compositor::from("niri"), Compositor::Niri);
+        assert_eq!(Compositor::from("wayfire"), Compositor::Wayfire);
+        assert_eq!(Compositor::from("labwc"), Compositor::Labwc);
+        assert_eq!(Compositor::from("GNOME"), Compositor::Gnome);
+        assert_eq!(Compositor::from("KDE"), Compositor::Kde);
         assert_eq!(Compositor::from("unknown"), Compositor::Unknown);
         assert_eq!(Compositor::from("something"), Compositor::Unknown);
     }
 
+    #[test]
+    fn test_compositor_from_desktop() {
+        assert_eq!(compositor_from_desktop("ubuntu:GNOME"), Compositor::Gnome);
+        assert_eq!(compositor_from_desktop("KDE"), Compositor::Kde);
+        assert_eq!(compositor_from_desktop("labwc:wlroots"), Compositor::Labwc);
+        assert_eq!(compositor_from_desktop("X-Generic"), Compositor::Unknown);
+    }
+
+    #[test]
+    fn test_parse_version() {
+        let version = |major, minor, patch| Some(SemVer { major, minor, patch });
+
+        assert_eq!(
+            parse_version(
+                &Compositor::Hyprland,
+                "Hyprland, built from branch main at commit 9958d29 (version: bump to v0.41.2). Date: 2024-06-19 tag v0.41.2"
+            ),
+            version(0, 41, 2)
+        );
+        assert_eq!(
+            parse_version(&Compositor::Hyprland, "Hyprland 0.45.2 built from branch  at commit 12f9a0d"),
+            version(0, 45, 2)
+        );
+        assert_eq!(parse_version(&Compositor::Sway, "sway version 1.9"), version(1, 9, 0));
+        assert_eq!(parse_version(&Compositor::Sway, "sway version 1.10-dev-3f2f0a9c"), version(1, 10, 0));
+        assert_eq!(parse_version(&Compositor::Niri, "niri 0.1.10 (commit 2ecc2d2)"), version(0, 1, 10));
+        assert_eq!(parse_version(&Compositor::Labwc, "labwc 0.7.2 (+xwayland +nls)"), version(0, 7, 2));
+        assert_eq!(parse_version(&Compositor::River, "river"), None);
+    }
+
     #[test]
     fn test_is_wayland_session() {
         // Test will pass regardless of environment
@@ -0,0 +1,148 @@
+// ============================================================================
+// CPU INFORMATION
+// ============================================================================
+
+use crate::error::Result;
+use std::fs;
+
+/// Location of the kernel's CPU description
+const CPUINFO_PATH: &str = "/proc/cpuinfo";
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/**
+ * CPU information relevant to the `cpu` module
+ */
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CpuInfo {
+    /// Number of logical cores (one `{usageN}` placeholder each)
+    pub logical_cores: usize,
+    /// CPU model name, if reported
+    pub model: Option<String>,
+}
+
+// ============================================================================
+// DETECTION
+// ============================================================================
+
+/**
+ * Get the logical core count and model name
+ *
+ * Reads /proc/cpuinfo; when it can't be read or lists no processors the
+ * core count falls back to the parallelism reported by the standard library.
+ */
+#[tauri::command]
+pub async fn get_cpu_info() -> Result<CpuInfo> {
+    let mut info = fs::read_to_string(CPUINFO_PATH)
+        .map(|content| parse_cpuinfo(&content))
+        .unwrap_or(CpuInfo { logical_cores: 0, model: None });
+
+    if info.logical_cores == 0 {
+        info.logical_cores = std::thread::available_parallelism()
+            .map(|n| n.get())
+            .unwrap_or(1);
+    }
+
+    Ok(info)
+}
+
+/**
+ * Parse /proc/cpuinfo content
+ *
+ * Counts `processor` entries and takes the first `model name` (x86) or
+ * `Model` / `cpu model` line (ARM, MIPS) as the model.
+ */
+pub fn parse_cpuinfo(content: &str) -> CpuInfo {
+    let mut logical_cores = 0;
+    let mut model = None;
+
+    for line in content.lines() {
+        let Some((key, value)) = line.split_once(':') else {
+            continue;
+        };
+
+        match key.trim() {
+            "processor" => logical_cores += 1,
+            "model name" | "Model" | "cpu model" if model.is_none() => {
+                let value = value.trim();
+                if !value.is_empty() {
+                    model = Some(value.to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    CpuInfo { logical_cores, model }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const X86_CPUINFO: &str = "\
+processor\t: 0
+vendor_id\t: GenuineIntel
+model\t\t: 140
+model name\t: 11th Gen Intel(R) Core(TM) i7-1165G7 @ 2.80GHz
+cpu cores\t: 4
+
+processor\t: 1
+vendor_id\t: GenuineIntel
+model name\t: 11th Gen Intel(R) Core(TM) i7-1165G7 @ 2.80GHz
+
+processor\t: 2
+model name\t: 11th Gen Intel(R) Core(TM) i7-1165G7 @ 2.80GHz
+
+processor\t: 3
+model name\t: 11th Gen Intel(R) Core(TM) i7-1165G7 @ 2.80GHz
+";
+
+    const ARM_CPUINFO: &str = "\
+processor\t: 0
+BogoMIPS\t: 108.00
+CPU part\t: 0xd08
+
+processor\t: 1
+BogoMIPS\t: 108.00
+
+Hardware\t: BCM2835
+Model\t\t: Raspberry Pi 4 Model B Rev 1.4
+";
+
+    #[test]
+    fn test_parse_x86_cpuinfo() {
+        let info = parse_cpuinfo(X86_CPUINFO);
+        assert_eq!(info.logical_cores, 4);
+        assert_eq!(
+            info.model.as_deref(),
+            Some("11th Gen Intel(R) Core(TM) i7-1165G7 @ 2.80GHz")
+        );
+    }
+
+    #[test]
+    fn test_parse_arm_cpuinfo() {
+        let info = parse_cpuinfo(ARM_CPUINFO);
+        assert_eq!(info.logical_cores, 2);
+        assert_eq!(info.model.as_deref(), Some("Raspberry Pi 4 Model B Rev 1.4"));
+    }
+
+    #[test]
+    fn test_parse_empty_cpuinfo() {
+        let info = parse_cpuinfo("");
+        assert_eq!(info.logical_cores, 0);
+        assert!(info.model.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_get_cpu_info() {
+        let info = get_cpu_info().await.unwrap();
+        assert!(info.logical_cores >= 1);
+    }
+}
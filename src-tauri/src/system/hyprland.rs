@@ -0,0 +1,249 @@
+// ============================================================================
+// HYPRLAND QUERIES
+// ============================================================================
+//
+// Talks to Hyprland's request socket directly, the same way hyprctl does:
+// connect, write one command (`j/` asks for JSON), read until Hyprland
+// closes the connection.
+
+use crate::error::{AppError, Result};
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// How long to wait on the socket before giving up on a busy or hung Hyprland
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/**
+ * A Hyprland workspace as reported by `j/workspaces`
+ */
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct Workspace {
+    pub id: i64,
+    pub name: String,
+    #[serde(default)]
+    pub monitor: String,
+    #[serde(default)]
+    pub windows: u32,
+}
+
+/**
+ * A Hyprland monitor as reported by `j/monitors`
+ */
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct Monitor {
+    pub name: String,
+    pub width: u32,
+    pub height: u32,
+    #[serde(default = "default_scale")]
+    pub scale: f64,
+}
+
+fn default_scale() -> f64 {
+    1.0
+}
+
+// ============================================================================
+// IPC
+// ============================================================================
+
+/**
+ * Locate the request socket of the running Hyprland instance
+ * Environment variables are read through `lookup`
+ */
+pub fn hyprland_socket_path(lookup: impl Fn(&str) -> Option<String>) -> Result<PathBuf> {
+    let signature = lookup("HYPRLAND_INSTANCE_SIGNATURE").filter(|s| !s.is_empty()).ok_or_else(|| {
+        AppError::NotFound(
+            "Not running under Hyprland (HYPRLAND_INSTANCE_SIGNATURE is not set)".to_string(),
+        )
+    })?;
+    let runtime_dir = lookup("XDG_RUNTIME_DIR").filter(|s| !s.is_empty()).ok_or_else(|| {
+        AppError::NotFound("XDG_RUNTIME_DIR is not set; cannot locate the Hyprland socket".to_string())
+    })?;
+
+    Ok(PathBuf::from(runtime_dir)
+        .join("hypr")
+        .join(signature)
+        .join(".socket.sock"))
+}
+
+/**
+ * Send one command to the socket and return the full reply
+ *
+ * Fails instead of blocking when Hyprland doesn't answer within two seconds.
+ */
+pub fn hyprland_request(socket: &Path, command: &str) -> Result<String> {
+    hyprland_request_within(socket, command, SOCKET_TIMEOUT)
+}
+
+/**
+ * Send one command to the socket, waiting at most `timeout` on each read
+ * or write
+ */
+fn hyprland_request_within(socket: &Path, command: &str, timeout: Duration) -> Result<String> {
+    let mut stream = UnixStream::connect(socket).map_err(|e| {
+        AppError::NotFound(format!("Cannot connect to Hyprland socket {}: {}", socket.display(), e))
+    })?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    let mut reply = String::new();
+    stream
+        .write_all(command.as_bytes())
+        .and_then(|()| stream.read_to_string(&mut reply))
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => AppError::Internal(
+                format!("Hyprland did not answer {} within {:?}", command, timeout),
+            ),
+            _ => AppError::from(e),
+        })?;
+    Ok(reply)
+}
+
+/**
+ * Send a command to the Hyprland instance of this session
+ */
+fn query(command: &str) -> Result<String> {
+    let socket = hyprland_socket_path(|name| std::env::var(name).ok())?;
+    hyprland_request(&socket, command)
+}
+
+// ============================================================================
+// QUERIES
+// ============================================================================
+
+/**
+ * Get the live Hyprland monitors
+ */
+#[tauri::command]
+pub async fn get_hyprland_monitors() -> Result<Vec<Monitor>> {
+    parse_monitors(&query("j/monitors")?)
+}
+
+/**
+ * Get the live Hyprland workspaces
+ */
+#[tauri::command]
+pub async fn get_hyprland_workspaces() -> Result<Vec<Workspace>> {
+    parse_workspaces(&query("j/workspaces")?)
+}
+
+/**
+ * Parse the `j/monitors` reply
+ */
+pub fn parse_monitors(json: &str) -> Result<Vec<Monitor>> {
+    serde_json::from_str(json)
+        .map_err(|e| AppError::Parse(format!("Invalid Hyprland monitors reply: {}", e)))
+}
+
+/**
+ * Parse the `j/workspaces` reply
+ */
+pub fn parse_workspaces(json: &str) -> Result<Vec<Workspace>> {
+    serde_json::from_str(json)
+        .map_err(|e| AppError::Parse(format!("Invalid Hyprland workspaces reply: {}", e)))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_workspaces() {
+        let json = r#"[
+            {"id": 1, "name": "1", "monitor": "DP-1", "monitorID": 0, "windows": 2,
+             "hasfullscreen": false, "lastwindow": "0x1", "lastwindowtitle": "kitty"},
+            {"id": -98, "name": "special:scratch", "monitor": "DP-1", "windows": 1}
+        ]"#;
+
+        let workspaces = parse_workspaces(json).unwrap();
+        assert_eq!(workspaces.len(), 2);
+        assert_eq!(workspaces[0].name, "1");
+        assert_eq!(workspaces[0].windows, 2);
+        assert_eq!(workspaces[1].id, -98);
+    }
+
+    #[test]
+    fn test_parse_invalid_output() {
+        assert!(matches!(parse_workspaces("ok"), Err(AppError::Parse(_))));
+    }
+
+    #[test]
+    fn test_parse_monitors() {
+        let json = r#"[
+            {"id": 0, "name": "eDP-1", "description": "BOE 0x095F", "width": 2256,
+             "height": 1504, "refreshRate": 59.999, "x": 0, "y": 0, "scale": 1.5,
+             "transform": 0, "focused": true}
+        ]"#;
+
+        assert_eq!(
+            parse_monitors(json).unwrap(),
+            vec![Monitor { name: "eDP-1".to_string(), width: 2256, height: 1504, scale: 1.5 }]
+        );
+    }
+
+    #[test]
+    fn test_hyprland_socket_path() {
+        let path = hyprland_socket_path(|name| match name {
+            "HYPRLAND_INSTANCE_SIGNATURE" => Some("abc_123".to_string()),
+            "XDG_RUNTIME_DIR" => Some("/run/user/1000".to_string()),
+            _ => None,
+        })
+        .unwrap();
+        assert_eq!(path, PathBuf::from("/run/user/1000/hypr/abc_123/.socket.sock"));
+
+        let outside =
+            hyprland_socket_path(|name| (name == "XDG_RUNTIME_DIR").then(|| "/run/user/1000".to_string()));
+        assert!(matches!(outside, Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_hyprland_request_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join(".socket.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut command = [0u8; 64];
+            let len = stream.read(&mut command).unwrap();
+            stream.write_all(br#"[{"id": 1, "name": "1", "monitor": "DP-1"}]"#).unwrap();
+            String::from_utf8_lossy(&command[..len]).to_string()
+        });
+
+        let reply = hyprland_request(&socket, "j/workspaces").unwrap();
+        assert_eq!(server.join().unwrap(), "j/workspaces");
+        assert_eq!(parse_workspaces(&reply).unwrap()[0].monitor, "DP-1");
+
+        drop(temp_dir);
+        assert!(matches!(hyprland_request(&socket, "j/monitors"), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_hyprland_request_times_out() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join(".socket.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+
+        // Accept but never reply, holding the connection open
+        let server = std::thread::spawn(move || listener.accept().unwrap());
+
+        let result = hyprland_request_within(&socket, "j/monitors", Duration::from_millis(100));
+        match result {
+            Err(AppError::Internal(message)) => assert!(message.contains("did not answer j/monitors")),
+            other => panic!("Expected a timeout, got {:?}", other),
+        }
+        drop(server.join().unwrap());
+    }
+}
@@ -0,0 +1,137 @@
+// ============================================================================
+// KEYBOARD LAYOUTS
+// ============================================================================
+
+use crate::error::{AppError, Result};
+use crate::system::detect_compositor;
+use serde_json::Value;
+use std::process::Command;
+
+// ============================================================================
+// QUERIES
+// ============================================================================
+
+/**
+ * Get the keyboard layouts configured in the running compositor
+ *
+ * Hyprland reports xkb layout codes (`us`, `de`), Sway the layout names
+ * (`English (US)`). Layouts are deduplicated across keyboards and keep their
+ * configured order. Other compositors return an empty list.
+ */
+#[tauri::command]
+pub async fn get_keyboard_layouts() -> Result<Vec<String>> {
+    match detect_compositor().await?.as_str() {
+        "hyprland" => parse_hyprland_layouts(&run_query("hyprctl", &["devices", "-j"])?),
+        "sway" => parse_sway_layouts(&run_query("swaymsg", &["-t", "get_inputs", "-r"])?),
+        _ => Ok(Vec::new()),
+    }
+}
+
+/**
+ * Run a compositor IPC query and return its standard output
+ */
+fn run_query(program: &str, args: &[&str]) -> Result<String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| AppError::Internal(format!("Failed to execute {}: {}", program, e)))?;
+
+    if !output.status.success() {
+        return Err(AppError::Internal(format!(
+            "{} {} failed: {}",
+            program,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/**
+ * Add layouts to a list, skipping blanks and ones already present
+ */
+fn push_layouts<'a>(layouts: &mut Vec<String>, names: impl Iterator<Item = &'a str>) {
+    for name in names.map(str::trim).filter(|name| !name.is_empty()) {
+        if !layouts.iter().any(|known| known == name) {
+            layouts.push(name.to_string());
+        }
+    }
+}
+
+/**
+ * Parse the keyboards' comma-separated `layout` from `hyprctl devices -j`
+ */
+pub fn parse_hyprland_layouts(json: &str) -> Result<Vec<String>> {
+    let devices: Value = serde_json::from_str(json)
+        .map_err(|e| AppError::Parse(format!("Invalid hyprctl devices output: {}", e)))?;
+
+    let mut layouts = Vec::new();
+    for keyboard in devices["keyboards"].as_array().into_iter().flatten() {
+        if let Some(layout) = keyboard["layout"].as_str() {
+            push_layouts(&mut layouts, layout.split(','));
+        }
+    }
+    Ok(layouts)
+}
+
+/**
+ * Parse the keyboards' `xkb_layout_names` from `swaymsg -t get_inputs -r`
+ */
+pub fn parse_sway_layouts(json: &str) -> Result<Vec<String>> {
+    let inputs: Value = serde_json::from_str(json)
+        .map_err(|e| AppError::Parse(format!("Invalid swaymsg inputs output: {}", e)))?;
+
+    let mut layouts = Vec::new();
+    for input in inputs.as_array().into_iter().flatten() {
+        if input["type"] != "keyboard" {
+            continue;
+        }
+        let names = input["xkb_layout_names"].as_array().into_iter().flatten();
+        push_layouts(&mut layouts, names.filter_map(Value::as_str));
+    }
+    Ok(layouts)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hyprland_layouts() {
+        let json = r#"{
+            "mice": [{"address": "0x1", "name": "logitech-mouse", "defaultSpeed": 0.0}],
+            "keyboards": [
+                {"address": "0x2", "name": "at-translated-set-2-keyboard", "rules": "",
+                 "model": "", "layout": "us,de", "variant": ",nodeadkeys",
+                 "options": "grp:alt_shift_toggle", "active_keymap": "English (US)",
+                 "main": true},
+                {"address": "0x3", "name": "power-button", "layout": "us", "main": false}
+            ]
+        }"#;
+
+        assert_eq!(parse_hyprland_layouts(json).unwrap(), vec!["us", "de"]);
+        assert!(parse_hyprland_layouts("[").is_err());
+    }
+
+    #[test]
+    fn test_parse_sway_layouts() {
+        let json = r#"[
+            {"identifier": "1:1:AT_Translated_Set_2_keyboard", "type": "keyboard",
+             "xkb_layout_names": ["English (US)", "German (no dead keys)"],
+             "xkb_active_layout_index": 0, "xkb_active_layout_name": "English (US)"},
+            {"identifier": "2:7:SynPS/2_Synaptics_TouchPad", "type": "touchpad"},
+            {"identifier": "0:1:Power_Button", "type": "keyboard",
+             "xkb_layout_names": ["English (US)"]}
+        ]"#;
+
+        assert_eq!(
+            parse_sway_layouts(json).unwrap(),
+            vec!["English (US)", "German (no dead keys)"]
+        );
+    }
+}
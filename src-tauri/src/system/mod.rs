@@ -3,5 +3,25 @@
 // ============================================================================
 
 pub mod compositor;
+pub mod cpu;
+pub mod hyprland;
+pub mod keyboard;
+pub mod mpris;
+pub mod network;
+pub mod power;
+pub mod profile;
+pub mod session;
+pub mod sway;
+pub mod systemd;
 
 pub use compositor::*;
+pub use cpu::*;
+pub use hyprland::*;
+pub use keyboard::*;
+pub use mpris::*;
+pub use network::*;
+pub use power::*;
+pub use profile::*;
+pub use session::*;
+pub use sway::*;
+pub use systemd::*;
@@ -0,0 +1,90 @@
+// ============================================================================
+// MPRIS PLAYERS
+// ============================================================================
+
+use crate::error::{AppError, Result};
+use std::process::Command;
+
+/// D-Bus name prefix every MPRIS media player registers under
+const MPRIS_PREFIX: &str = "org.mpris.MediaPlayer2.";
+
+// ============================================================================
+// DETECTION
+// ============================================================================
+
+/**
+ * List the MPRIS players currently on the session bus, sorted
+ *
+ * Names are as Waybar's `mpris` module sees them: the bus name without the
+ * `org.mpris.MediaPlayer2.` prefix (e.g. `spotify`, `firefox.instance_1_42`).
+ */
+#[tauri::command]
+pub async fn list_mpris_players() -> Result<Vec<String>> {
+    let output = Command::new("busctl")
+        .args([
+            "--user",
+            "--json=short",
+            "call",
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "ListNames",
+        ])
+        .output()
+        .map_err(|e| AppError::Internal(format!("Failed to execute busctl: {}", e)))?;
+
+    if !output.status.success() {
+        return Err(AppError::Internal(format!(
+            "busctl ListNames failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    parse_mpris_players(&String::from_utf8_lossy(&output.stdout))
+}
+
+/**
+ * Extract MPRIS player names from `busctl --json=short call ... ListNames`
+ * output (`{"type":"as","data":[["org.freedesktop.DBus", ...]]}`)
+ */
+pub fn parse_mpris_players(json: &str) -> Result<Vec<String>> {
+    #[derive(serde::Deserialize)]
+    struct Reply {
+        data: (Vec<String>,),
+    }
+
+    let reply: Reply = serde_json::from_str(json)
+        .map_err(|e| AppError::Parse(format!("Invalid busctl ListNames output: {}", e)))?;
+
+    let mut players: Vec<String> = reply
+        .data
+        .0
+        .iter()
+        .filter_map(|name| name.strip_prefix(MPRIS_PREFIX))
+        .map(str::to_string)
+        .collect();
+    players.sort();
+    Ok(players)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_mpris_players() {
+        let json = r#"{"type":"as","data":[["org.freedesktop.DBus",":1.4",
+            "org.mpris.MediaPlayer2.spotify","org.freedesktop.Notifications",
+            "org.mpris.MediaPlayer2.firefox.instance_1_42"]]}"#;
+
+        assert_eq!(
+            parse_mpris_players(json).unwrap(),
+            vec!["firefox.instance_1_42", "spotify"]
+        );
+        assert!(parse_mpris_players("not json").is_err());
+    }
+}
@@ -0,0 +1,61 @@
+// ============================================================================
+// NETWORK INTERFACES
+// ============================================================================
+
+use crate::error::Result;
+use std::fs;
+use std::path::Path;
+
+/// Directory with one entry per network interface
+const SYS_CLASS_NET: &str = "/sys/class/net";
+
+// ============================================================================
+// DETECTION
+// ============================================================================
+
+/**
+ * List the network interfaces of this machine, sorted
+ *
+ * Returns an empty list when /sys/class/net can't be read.
+ */
+#[tauri::command]
+pub async fn list_network_interfaces() -> Result<Vec<String>> {
+    Ok(list_interfaces_in(Path::new(SYS_CLASS_NET)))
+}
+
+/**
+ * List the interface names in a sysfs-style directory
+ */
+fn list_interfaces_in(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut interfaces: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    interfaces.sort();
+    interfaces
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_list_interfaces_in() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in ["wlan0", "lo", "enp3s0"] {
+            fs::create_dir(temp_dir.path().join(name)).unwrap();
+        }
+
+        assert_eq!(list_interfaces_in(temp_dir.path()), vec!["enp3s0", "lo", "wlan0"]);
+        assert!(list_interfaces_in(&temp_dir.path().join("missing")).is_empty());
+    }
+}
@@ -0,0 +1,122 @@
+// ============================================================================
+// POWER PROFILES
+// ============================================================================
+
+use crate::error::Result;
+use std::process::Command;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/**
+ * Power profiles offered by power-profiles-daemon
+ */
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PowerProfiles {
+    /// Available profiles in daemon order (e.g. performance, balanced, power-saver)
+    pub profiles: Vec<String>,
+    /// Currently active profile
+    pub active: Option<String>,
+}
+
+// ============================================================================
+// DETECTION
+// ============================================================================
+
+/**
+ * Get the available and active power profiles
+ *
+ * Queries `powerprofilesctl list`. Returns an empty result when the tool
+ * is missing or the daemon isn't running.
+ */
+#[tauri::command]
+pub async fn get_power_profiles() -> Result<PowerProfiles> {
+    let output = match Command::new("powerprofilesctl").arg("list").output() {
+        Ok(output) if output.status.success() => output,
+        _ => return Ok(PowerProfiles::default()),
+    };
+
+    Ok(parse_powerprofilesctl_list(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/**
+ * Parse `powerprofilesctl list` output
+ *
+ * Profiles are the unindented `name:` lines (indented lines are their
+ * properties); the active one is marked with a leading `*`.
+ */
+pub fn parse_powerprofilesctl_list(output: &str) -> PowerProfiles {
+    let mut profiles = PowerProfiles::default();
+
+    for line in output.lines() {
+        let (active, rest) = match line.strip_prefix('*') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+
+        // Profile lines are indented by at most the width of the "* " marker
+        let indent = rest.len() - rest.trim_start().len();
+        let Some(name) = rest.trim().strip_suffix(':') else {
+            continue;
+        };
+        if indent > 2 || name.is_empty() || name.contains([':', ' ', '\t']) {
+            continue;
+        }
+
+        if active {
+            profiles.active = Some(name.to_string());
+        }
+        profiles.profiles.push(name.to_string());
+    }
+
+    profiles
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIST_OUTPUT: &str = "  performance:
+    CpuDriver:\tintel_pstate
+    Degraded:   no
+
+* balanced:
+    CpuDriver:\tintel_pstate
+    PlatformDriver:\tplatform_profile
+
+  power-saver:
+    CpuDriver:\tintel_pstate
+    PlatformDriver:\tplatform_profile
+";
+
+    #[test]
+    fn test_parse_powerprofilesctl_list() {
+        let profiles = parse_powerprofilesctl_list(LIST_OUTPUT);
+        assert_eq!(profiles.profiles, vec!["performance", "balanced", "power-saver"]);
+        assert_eq!(profiles.active.as_deref(), Some("balanced"));
+    }
+
+    #[test]
+    fn test_parse_without_performance_profile() {
+        let output = "* power-saver:\n    PlatformDriver:\tplaceholder\n\n  balanced:\n    PlatformDriver:\tplaceholder\n";
+        let profiles = parse_powerprofilesctl_list(output);
+        assert_eq!(profiles.profiles, vec!["power-saver", "balanced"]);
+        assert_eq!(profiles.active.as_deref(), Some("power-saver"));
+    }
+
+    #[test]
+    fn test_parse_empty_output() {
+        assert_eq!(parse_powerprofilesctl_list(""), PowerProfiles::default());
+    }
+
+    #[tokio::test]
+    async fn test_get_power_profiles() {
+        // Empty when the daemon isn't available, but never an error
+        assert!(get_power_profiles().await.is_ok());
+    }
+}
@@ -0,0 +1,360 @@
+// ============================================================================
+// SYSTEM PROFILE
+// ============================================================================
+//
+// A snapshot of the hardware and desktop details the lints compare a config
+// against. Captured on the machine the bar runs on, it can be loaded on
+// another machine so those lints use the target's devices instead of live
+// queries.
+
+use crate::error::{AppError, Result};
+use crate::system::{get_compositor_info, get_cpu_info, list_network_interfaces};
+use crate::system::{CompositorInfo, CpuInfo};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+
+/// Directory with one entry per display connector (`card0-DP-1`)
+const SYS_CLASS_DRM: &str = "/sys/class/drm";
+
+/// Directory with one entry per power supply (`BAT0`, `AC`)
+const SYS_CLASS_POWER_SUPPLY: &str = "/sys/class/power_supply";
+
+/// Profile loaded with `load_system_profile`, used instead of live queries
+static ACTIVE_PROFILE: Mutex<Option<SystemProfile>> = Mutex::new(None);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/**
+ * Devices and desktop details of the machine a bar runs on
+ */
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SystemProfile {
+    /// Missing when the compositor couldn't be queried
+    #[serde(default)]
+    pub compositor: Option<CompositorInfo>,
+    /// Missing when the CPU couldn't be queried
+    #[serde(default)]
+    pub cpu: Option<CpuInfo>,
+    /// Connected outputs (`eDP-1`, `DP-2`)
+    #[serde(default)]
+    pub outputs: Vec<String>,
+    /// Network interfaces
+    #[serde(default)]
+    pub interfaces: Vec<String>,
+    /// Batteries as named by the `battery` module's `bat` option
+    #[serde(default)]
+    pub batteries: Vec<String>,
+    /// PulseAudio/PipeWire sink names
+    #[serde(default)]
+    pub audio_sinks: Vec<String>,
+    /// Installed font families
+    #[serde(default)]
+    pub fonts: Vec<String>,
+}
+
+// ============================================================================
+// CAPTURE
+// ============================================================================
+
+/**
+ * Collect the system profile of this machine
+ *
+ * Saves it as JSON to `path` when given. Details that can't be queried
+ * (no compositor, no PulseAudio, no fontconfig) are left out or empty, so
+ * one failing probe doesn't lose the rest of the profile.
+ */
+#[tauri::command]
+pub async fn capture_system_profile(path: Option<String>) -> Result<SystemProfile> {
+    let profile = SystemProfile {
+        compositor: section("compositor", get_compositor_info().await),
+        cpu: section("CPU", get_cpu_info().await),
+        outputs: list_outputs_in(Path::new(SYS_CLASS_DRM)),
+        interfaces: section("network interfaces", list_network_interfaces().await)
+            .unwrap_or_default(),
+        batteries: list_batteries_in(Path::new(SYS_CLASS_POWER_SUPPLY)),
+        audio_sinks: command_output("pactl", &["list", "short", "sinks"])
+            .map(|output| parse_pactl_sinks(&output))
+            .unwrap_or_default(),
+        fonts: command_output("fc-list", &[":", "family"])
+            .map(|output| parse_font_families(&output))
+            .unwrap_or_default(),
+    };
+
+    if let Some(path) = path {
+        if let Some(parent) = Path::new(&path).parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, serde_json::to_string_pretty(&profile)?)?;
+        log::info!("Saved system profile to {}", path);
+    }
+
+    Ok(profile)
+}
+
+/**
+ * Keep the result of a profile probe, logging why a section is missing
+ */
+fn section<T>(name: &str, probe: Result<T>) -> Option<T> {
+    probe
+        .map_err(|e| log::warn!("Leaving {} out of the system profile: {}", name, e))
+        .ok()
+}
+
+/**
+ * Run a command and return its standard output, or None if it failed
+ */
+fn command_output(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/**
+ * List connected outputs from a sysfs DRM directory, sorted
+ *
+ * Connectors are named `card<N>-<output>` and have a `status` file.
+ */
+fn list_outputs_in(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut outputs: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            fs::read_to_string(entry.path().join("status"))
+                .is_ok_and(|status| status.trim() == "connected")
+        })
+        .filter_map(|entry| {
+            let name = entry.file_name().to_str()?.to_string();
+            name.split_once('-').map(|(_, output)| output.to_string())
+        })
+        .collect();
+    outputs.sort();
+    outputs
+}
+
+/**
+ * List batteries from a sysfs power supply directory, sorted
+ */
+fn list_batteries_in(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut batteries: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| {
+            fs::read_to_string(entry.path().join("type")).is_ok_and(|kind| kind.trim() == "Battery")
+        })
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .collect();
+    batteries.sort();
+    batteries
+}
+
+/**
+ * Parse sink names from `pactl list short sinks`
+ * (tab-separated: index, name, driver, sample spec, state)
+ */
+pub fn parse_pactl_sinks(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split('\t').nth(1))
+        .map(str::to_string)
+        .collect()
+}
+
+/**
+ * Parse `fc-list : family` output into sorted, unique family names
+ * (a line lists every name of one font, comma-separated)
+ */
+pub fn parse_font_families(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .filter(|family| !family.is_empty())
+        .map(str::to_string)
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect()
+}
+
+// ============================================================================
+// LOADING
+// ============================================================================
+
+/**
+ * Read a profile saved by `capture_system_profile`
+ */
+pub fn read_profile(path: &Path) -> Result<SystemProfile> {
+    let content = fs::read_to_string(path)?;
+    serde_json::from_str(&content).map_err(|e| {
+        AppError::Parse(format!("Invalid system profile {}: {}", path.display(), e))
+    })
+}
+
+/**
+ * Load a saved profile and use it instead of live queries in the lints
+ * that compare the config with this machine
+ */
+#[tauri::command]
+pub async fn load_system_profile(path: String) -> Result<SystemProfile> {
+    let profile = read_profile(Path::new(&path))?;
+    *ACTIVE_PROFILE
+        .lock()
+        .map_err(|_| AppError::Internal("System profile lock poisoned".to_string()))? =
+        Some(profile.clone());
+    log::info!("Loaded system profile from {}", path);
+    Ok(profile)
+}
+
+/**
+ * Go back to live queries
+ */
+#[tauri::command]
+pub async fn clear_system_profile() -> Result<()> {
+    if let Ok(mut active) = ACTIVE_PROFILE.lock() {
+        *active = None;
+    }
+    Ok(())
+}
+
+/**
+ * Get the loaded profile, if any
+ */
+pub fn active_profile() -> Option<SystemProfile> {
+    ACTIVE_PROFILE.lock().ok().and_then(|active| active.clone())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    const PROFILE_FIXTURE: &str = r#"{
+        "compositor": { "name": "hyprland", "version": "0.41.2", "session_type": "wayland" },
+        "cpu": { "logical_cores": 4, "model": "AMD Ryzen 5 3600" },
+        "outputs": ["DP-1", "HDMI-A-1"],
+        "interfaces": ["enp5s0", "lo"],
+        "batteries": [],
+        "audio_sinks": ["alsa_output.pci-0000_0b_00.4.analog-stereo"],
+        "fonts": ["JetBrainsMono Nerd Font"]
+    }"#;
+
+    #[test]
+    fn test_config_against_loaded_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("desktop.json");
+        fs::write(&path, PROFILE_FIXTURE).unwrap();
+        let profile = read_profile(&path).unwrap();
+
+        let config = json!({
+            "ipc": true,
+            "network": { "interface": "wl*" },
+            "network#wired": { "interface": "enp*" },
+            "cpu": { "format": "{icon0}{icon1}", "format-icons": ["a", "b"] }
+        });
+
+        let network = crate::config::lint::check_network_interfaces(&config, &profile.interfaces);
+        assert_eq!(network.len(), 1);
+        assert_eq!(network[0].target, "network");
+
+        let cores = profile.cpu.unwrap().logical_cores;
+        let cpu = crate::config::lint::check_cpu_format_icons(&config, cores);
+        assert_eq!(cpu.len(), 1);
+
+        let compositor = profile.compositor.unwrap();
+        let features = crate::config::lint::check_compositor_features(&config, &compositor.name);
+        assert_eq!(features.len(), 1);
+        assert_eq!(features[0].key.as_deref(), Some("ipc"));
+    }
+
+    #[test]
+    fn test_read_invalid_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("broken.json");
+        fs::write(&path, r#"{ "outputs": "DP-1" }"#).unwrap();
+
+        assert!(matches!(read_profile(&path), Err(AppError::Parse(_))));
+    }
+
+    #[test]
+    fn test_read_profile_with_missing_sections() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("partial.json");
+        fs::write(&path, r#"{ "outputs": ["DP-1"] }"#).unwrap();
+
+        let profile = read_profile(&path).unwrap();
+        assert!(profile.compositor.is_none());
+        assert!(profile.cpu.is_none());
+        assert_eq!(profile.outputs, vec!["DP-1"]);
+    }
+
+    #[tokio::test]
+    async fn test_commands_use_loaded_profile() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("desktop.json");
+        fs::write(&path, PROFILE_FIXTURE).unwrap();
+        load_system_profile(path.to_string_lossy().to_string()).await.unwrap();
+
+        let content = r#"{
+            "ipc": true,
+            "network": { "interface": "wl*" },
+            "cpu": { "format": "{icon0}{icon1}", "format-icons": ["a", "b"] }
+        }"#;
+        let network = crate::commands::check_network_interfaces(content.to_string()).await;
+        let cpu = crate::commands::check_cpu_format_icons(content.to_string()).await;
+        let features = crate::commands::check_compositor_features(content.to_string()).await;
+        clear_system_profile().await.unwrap();
+
+        // The fixture has no wireless interface, 4 cores and runs Hyprland
+        assert_eq!(network.unwrap().len(), 1);
+        assert!(cpu.unwrap()[0].message.contains("4 cores"));
+        assert_eq!(features.unwrap()[0].key.as_deref(), Some("ipc"));
+    }
+
+    #[test]
+    fn test_list_sysfs_devices() {
+        let temp_dir = TempDir::new().unwrap();
+        let drm = temp_dir.path().join("drm");
+        for (name, status) in [("card1-DP-1", "connected"), ("card1-HDMI-A-1", "disconnected")] {
+            fs::create_dir_all(drm.join(name)).unwrap();
+            fs::write(drm.join(name).join("status"), format!("{}\n", status)).unwrap();
+        }
+        fs::create_dir_all(drm.join("card1")).unwrap();
+        assert_eq!(list_outputs_in(&drm), vec!["DP-1"]);
+
+        let power = temp_dir.path().join("power_supply");
+        for (name, kind) in [("BAT0", "Battery"), ("AC", "Mains")] {
+            fs::create_dir_all(power.join(name)).unwrap();
+            fs::write(power.join(name).join("type"), format!("{}\n", kind)).unwrap();
+        }
+        assert_eq!(list_batteries_in(&power), vec!["BAT0"]);
+    }
+
+    #[test]
+    fn test_parse_sinks_and_fonts() {
+        let sinks = "56\talsa_output.usb-headset.analog-stereo\tPipeWire\ts16le 2ch 48000Hz\tSUSPENDED\n";
+        assert_eq!(parse_pactl_sinks(sinks), vec!["alsa_output.usb-headset.analog-stereo"]);
+
+        let fonts = "DejaVu Sans,DejaVu Sans Light\nNoto Sans\nDejaVu Sans\n";
+        assert_eq!(
+            parse_font_families(fonts),
+            vec!["DejaVu Sans", "DejaVu Sans Light", "Noto Sans"]
+        );
+    }
+}
@@ -0,0 +1,220 @@
+// ============================================================================
+// SESSION DETAILS
+// ============================================================================
+
+use crate::config::edit::dehome;
+use crate::error::Result;
+use std::collections::BTreeMap;
+use std::env;
+
+/// Environment variables affecting compositor detection and path resolution
+const WAYBAR_ENV_VARS: [&str; 8] = [
+    "WAYLAND_DISPLAY",
+    "XDG_CURRENT_DESKTOP",
+    "XDG_CONFIG_HOME",
+    "XDG_RUNTIME_DIR",
+    "WAYBAR_CONFIG_DIR",
+    "HYPRLAND_INSTANCE_SIGNATURE",
+    "SWAYSOCK",
+    "XDG_SESSION_TYPE",
+];
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/**
+ * Details about the graphical session the app runs in
+ *
+ * Explains odd compositor detection results when testing configs inside a
+ * nested or headless compositor.
+ */
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SessionDetails {
+    /// Value of $WAYLAND_DISPLAY, if set
+    pub wayland_display: Option<String>,
+    /// Whether the compositor appears to run inside another session
+    pub is_nested: bool,
+    /// Whether the compositor appears to have no real outputs
+    pub is_headless: bool,
+    /// Seat from $XDG_SEAT, if set
+    pub seat: Option<String>,
+}
+
+// ============================================================================
+// DETECTION
+// ============================================================================
+
+/**
+ * Get details about the current graphical session
+ */
+#[tauri::command]
+pub async fn get_session_details() -> Result<SessionDetails> {
+    Ok(session_details_from(|name| env::var(name).ok()))
+}
+
+/**
+ * Derive session details from environment variables
+ *
+ * Heuristics:
+ * - headless: wlroots `headless` backend, a display name mentioning
+ *   "headless", or a Wayland display without a seat outside a Wayland
+ *   login session
+ * - nested: wlroots `wayland`/`x11` backends, a display name mentioning
+ *   "nested", or a Wayland display inside an X11 login session
+ *
+ * The socket number says nothing: compositors pick `wayland-1` whenever
+ * `wayland-0` is taken or stale.
+ */
+fn session_details_from(lookup: impl Fn(&str) -> Option<String>) -> SessionDetails {
+    let wayland_display = lookup("WAYLAND_DISPLAY").filter(|d| !d.is_empty());
+    let session_type = lookup("XDG_SESSION_TYPE").unwrap_or_default().to_lowercase();
+    let seat = lookup("XDG_SEAT").filter(|s| !s.is_empty());
+    let backends = lookup("WLR_BACKENDS").unwrap_or_default().to_lowercase();
+    let backends: Vec<&str> = backends.split(',').map(str::trim).collect();
+
+    let display = wayland_display.as_deref().unwrap_or_default().to_lowercase();
+    let has_display = wayland_display.is_some();
+
+    let is_headless = backends.contains(&"headless")
+        || display.contains("headless")
+        || (has_display && seat.is_none() && session_type != "wayland");
+
+    let is_nested = backends.contains(&"wayland")
+        || backends.contains(&"x11")
+        || display.contains("nested")
+        || (has_display && session_type == "x11");
+
+    SessionDetails {
+        wayland_display,
+        is_nested,
+        is_headless,
+        seat,
+    }
+}
+
+// ============================================================================
+// ENVIRONMENT REPORT
+// ============================================================================
+
+/**
+ * Get the environment variables relevant to Waybar, for bug reports
+ *
+ * Unset variables map to null. The home directory is replaced by `~`.
+ */
+#[tauri::command]
+pub async fn get_waybar_environment() -> Result<BTreeMap<String, Option<String>>> {
+    Ok(waybar_environment_from(|name| env::var(name).ok()))
+}
+
+/**
+ * Collect the Waybar-relevant variables from a lookup function
+ */
+fn waybar_environment_from(
+    lookup: impl Fn(&str) -> Option<String>,
+) -> BTreeMap<String, Option<String>> {
+    let home = lookup("HOME").unwrap_or_default();
+
+    WAYBAR_ENV_VARS
+        .iter()
+        .map(|name| {
+            let value = lookup(name).map(|value| dehome(&value, &home).unwrap_or(value));
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn details(vars: &[(&str, &str)]) -> SessionDetails {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        session_details_from(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn test_normal_session() {
+        let session = details(&[
+            ("WAYLAND_DISPLAY", "wayland-0"),
+            ("XDG_SESSION_TYPE", "wayland"),
+            ("XDG_SEAT", "seat0"),
+        ]);
+
+        assert_eq!(session.wayland_display.as_deref(), Some("wayland-0"));
+        assert_eq!(session.seat.as_deref(), Some("seat0"));
+        assert!(!session.is_nested);
+        assert!(!session.is_headless);
+    }
+
+    #[test]
+    fn test_nested_display() {
+        let session = details(&[
+            ("WAYLAND_DISPLAY", "wayland-1"),
+            ("XDG_SESSION_TYPE", "wayland"),
+            ("XDG_SEAT", "seat0"),
+        ]);
+        assert!(!session.is_nested);
+        assert!(!session.is_headless);
+
+        let session = details(&[
+            ("WAYLAND_DISPLAY", "wayland-1"),
+            ("XDG_SESSION_TYPE", "wayland"),
+            ("XDG_SEAT", "seat0"),
+            ("WLR_BACKENDS", "wayland"),
+        ]);
+        assert!(session.is_nested);
+
+        let session = details(&[
+            ("WAYLAND_DISPLAY", "wayland-0"),
+            ("XDG_SESSION_TYPE", "x11"),
+            ("XDG_SEAT", "seat0"),
+        ]);
+        assert!(session.is_nested);
+    }
+
+    #[test]
+    fn test_headless_session() {
+        let session = details(&[("WAYLAND_DISPLAY", "wayland-0"), ("WLR_BACKENDS", "headless")]);
+        assert!(session.is_headless);
+        assert!(session.seat.is_none());
+    }
+
+    #[test]
+    fn test_no_wayland_session() {
+        let session = details(&[("XDG_SESSION_TYPE", "tty")]);
+        assert!(session.wayland_display.is_none());
+        assert!(!session.is_nested);
+        assert!(!session.is_headless);
+    }
+
+    #[test]
+    fn test_waybar_environment() {
+        let vars: HashMap<&str, &str> = [
+            ("HOME", "/home/alice"),
+            ("WAYLAND_DISPLAY", "wayland-1"),
+            ("XDG_CONFIG_HOME", "/home/alice/.config"),
+            ("SWAYSOCK", "/run/user/1000/sway-ipc.sock"),
+        ]
+        .into_iter()
+        .collect();
+
+        let environment = waybar_environment_from(|name| vars.get(name).map(|v| v.to_string()));
+
+        assert_eq!(environment.len(), WAYBAR_ENV_VARS.len());
+        assert_eq!(environment["WAYLAND_DISPLAY"].as_deref(), Some("wayland-1"));
+        assert_eq!(environment["XDG_CONFIG_HOME"].as_deref(), Some("~/.config"));
+        assert_eq!(environment["SWAYSOCK"].as_deref(), Some("/run/user/1000/sway-ipc.sock"));
+        assert_eq!(environment["HYPRLAND_INSTANCE_SIGNATURE"], None);
+        assert_eq!(environment["WAYBAR_CONFIG_DIR"], None);
+        assert!(!environment.contains_key("HOME"));
+    }
+}
@@ -0,0 +1,305 @@
+// ============================================================================
+// SWAY QUERIES
+// ============================================================================
+//
+// Speaks the i3/sway IPC protocol on `$SWAYSOCK`. Every message, in both
+// directions, is the magic string `i3-ipc`, a little-endian u32 payload
+// length, a little-endian u32 message type, then the payload.
+
+use crate::error::{AppError, Result};
+use std::io::{Read, Write};
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// Magic string opening every IPC message
+const IPC_MAGIC: &[u8; 6] = b"i3-ipc";
+
+/// Message type of a `GET_WORKSPACES` request
+pub const GET_WORKSPACES: u32 = 1;
+
+/// Message type of a `GET_OUTPUTS` request
+pub const GET_OUTPUTS: u32 = 3;
+
+/// How long to wait on the socket before giving up on a busy or hung Sway
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Largest reply payload accepted; real replies are a few kilobytes
+const MAX_PAYLOAD_LENGTH: u32 = 16 * 1024 * 1024;
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/**
+ * A Sway workspace as reported by `GET_WORKSPACES`
+ */
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SwayWorkspace {
+    pub num: i64,
+    pub name: String,
+    #[serde(default)]
+    pub output: String,
+    #[serde(default)]
+    pub focused: bool,
+}
+
+/**
+ * A Sway output as reported by `GET_OUTPUTS`
+ */
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SwayOutput {
+    pub name: String,
+    #[serde(default)]
+    pub active: bool,
+    #[serde(default)]
+    pub make: String,
+    #[serde(default)]
+    pub model: String,
+}
+
+// ============================================================================
+// IPC
+// ============================================================================
+
+/**
+ * Locate the IPC socket of the running Sway instance
+ * Environment variables are read through `lookup`
+ */
+pub fn sway_socket_path(lookup: impl Fn(&str) -> Option<String>) -> Result<PathBuf> {
+    lookup("SWAYSOCK")
+        .filter(|path| !path.is_empty())
+        .map(PathBuf::from)
+        .ok_or_else(|| AppError::NotFound("Not running under Sway (SWAYSOCK is not set)".to_string()))
+}
+
+/**
+ * Frame an IPC message
+ */
+pub fn encode_ipc_message(message_type: u32, payload: &[u8]) -> Vec<u8> {
+    let mut message = Vec::with_capacity(IPC_MAGIC.len() + 8 + payload.len());
+    message.extend_from_slice(IPC_MAGIC);
+    message.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    message.extend_from_slice(&message_type.to_le_bytes());
+    message.extend_from_slice(payload);
+    message
+}
+
+/**
+ * Turn a failed socket read or write into an error, naming timeouts
+ */
+fn socket_error(e: std::io::Error) -> AppError {
+    match e.kind() {
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => {
+            AppError::Internal("Sway did not answer in time".to_string())
+        }
+        _ => AppError::from(e),
+    }
+}
+
+/**
+ * Read one framed IPC message, returning its type and payload
+ */
+pub fn read_ipc_message(reader: &mut impl Read) -> Result<(u32, String)> {
+    let mut header = [0u8; 14];
+    reader.read_exact(&mut header).map_err(socket_error)?;
+    if &header[..6] != IPC_MAGIC {
+        return Err(AppError::Parse("Sway IPC reply has an invalid header".to_string()));
+    }
+
+    let length = u32::from_le_bytes([header[6], header[7], header[8], header[9]]);
+    let message_type = u32::from_le_bytes([header[10], header[11], header[12], header[13]]);
+
+    if length > MAX_PAYLOAD_LENGTH {
+        return Err(AppError::Parse(format!(
+            "Sway IPC reply claims {} bytes, more than the {} allowed",
+            length, MAX_PAYLOAD_LENGTH
+        )));
+    }
+
+    let mut payload = vec![0u8; length as usize];
+    reader.read_exact(&mut payload).map_err(socket_error)?;
+    let payload = String::from_utf8(payload)
+        .map_err(|e| AppError::Parse(format!("Sway IPC reply is not UTF-8: {}", e)))?;
+
+    Ok((message_type, payload))
+}
+
+/**
+ * Send one request to the socket and return the reply payload
+ */
+pub fn sway_request(socket: &Path, message_type: u32) -> Result<String> {
+    sway_request_within(socket, message_type, SOCKET_TIMEOUT)
+}
+
+/**
+ * Send one request to the socket, waiting at most `timeout` on each read
+ * or write
+ */
+fn sway_request_within(socket: &Path, message_type: u32, timeout: Duration) -> Result<String> {
+    let mut stream = UnixStream::connect(socket).map_err(|e| {
+        AppError::NotFound(format!("Cannot connect to Sway socket {}: {}", socket.display(), e))
+    })?;
+    stream.set_read_timeout(Some(timeout))?;
+    stream.set_write_timeout(Some(timeout))?;
+
+    stream
+        .write_all(&encode_ipc_message(message_type, &[]))
+        .map_err(socket_error)?;
+
+    let (reply_type, payload) = read_ipc_message(&mut stream)?;
+    if reply_type != message_type {
+        return Err(AppError::Parse(format!(
+            "Sway IPC replied with type {} to a type {} request",
+            reply_type, message_type
+        )));
+    }
+    Ok(payload)
+}
+
+/**
+ * Send a request to the Sway instance of this session
+ */
+fn query(message_type: u32) -> Result<String> {
+    let socket = sway_socket_path(|name| std::env::var(name).ok())?;
+    sway_request(&socket, message_type)
+}
+
+// ============================================================================
+// QUERIES
+// ============================================================================
+
+/**
+ * Get the live Sway outputs
+ */
+#[tauri::command]
+pub async fn get_sway_outputs() -> Result<Vec<SwayOutput>> {
+    parse_sway_outputs(&query(GET_OUTPUTS)?)
+}
+
+/**
+ * Get the live Sway workspaces
+ */
+#[tauri::command]
+pub async fn get_sway_workspaces() -> Result<Vec<SwayWorkspace>> {
+    parse_sway_workspaces(&query(GET_WORKSPACES)?)
+}
+
+/**
+ * Parse the `GET_OUTPUTS` reply
+ */
+pub fn parse_sway_outputs(json: &str) -> Result<Vec<SwayOutput>> {
+    serde_json::from_str(json)
+        .map_err(|e| AppError::Parse(format!("Invalid Sway outputs reply: {}", e)))
+}
+
+/**
+ * Parse the `GET_WORKSPACES` reply
+ */
+pub fn parse_sway_workspaces(json: &str) -> Result<Vec<SwayWorkspace>> {
+    serde_json::from_str(json)
+        .map_err(|e| AppError::Parse(format!("Invalid Sway workspaces reply: {}", e)))
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::os::unix::net::UnixListener;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_sway_workspaces() {
+        let json = r#"[
+            {"id": 4, "type": "workspace", "num": 1, "name": "1", "output": "eDP-1",
+             "focused": true, "visible": true, "urgent": false},
+            {"id": 9, "type": "workspace", "num": -1, "name": "web", "output": "HDMI-A-1",
+             "focused": false, "visible": true, "urgent": false}
+        ]"#;
+
+        let workspaces = parse_sway_workspaces(json).unwrap();
+        assert_eq!(workspaces.len(), 2);
+        assert!(workspaces[0].focused);
+        assert_eq!(workspaces[1].name, "web");
+        assert_eq!(workspaces[1].num, -1);
+    }
+
+    #[test]
+    fn test_parse_sway_outputs() {
+        let json = r#"[
+            {"id": 3, "type": "output", "name": "eDP-1", "active": true, "make": "BOE",
+             "model": "0x095F", "serial": "0x00000000", "scale": 1.5},
+            {"id": 7, "type": "output", "name": "HDMI-A-1", "active": false}
+        ]"#;
+
+        let outputs = parse_sway_outputs(json).unwrap();
+        assert_eq!(
+            outputs[0],
+            SwayOutput {
+                name: "eDP-1".to_string(),
+                active: true,
+                make: "BOE".to_string(),
+                model: "0x095F".to_string(),
+            }
+        );
+        assert!(!outputs[1].active);
+        assert_eq!(outputs[1].make, "");
+    }
+
+    #[test]
+    fn test_sway_socket_path_requires_swaysock() {
+        assert!(matches!(sway_socket_path(|_| None), Err(AppError::NotFound(_))));
+        assert_eq!(
+            sway_socket_path(|_| Some("/run/user/1000/sway-ipc.sock".to_string())).unwrap(),
+            PathBuf::from("/run/user/1000/sway-ipc.sock")
+        );
+    }
+
+    #[test]
+    fn test_sway_request_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("sway-ipc.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let (message_type, payload) = read_ipc_message(&mut stream).unwrap();
+            let reply = r#"[{"name": "DP-1", "active": true}]"#;
+            stream.write_all(&encode_ipc_message(message_type, reply.as_bytes())).unwrap();
+            (message_type, payload)
+        });
+
+        let reply = sway_request(&socket, GET_OUTPUTS).unwrap();
+        assert_eq!(server.join().unwrap(), (GET_OUTPUTS, String::new()));
+        assert_eq!(parse_sway_outputs(&reply).unwrap()[0].name, "DP-1");
+    }
+
+    #[test]
+    fn test_sway_request_times_out() {
+        let temp_dir = TempDir::new().unwrap();
+        let socket = temp_dir.path().join("sway-ipc.sock");
+        let listener = UnixListener::bind(&socket).unwrap();
+
+        // Accept the connection but never reply
+        let server = std::thread::spawn(move || listener.accept().unwrap());
+
+        let result = sway_request_within(&socket, GET_OUTPUTS, Duration::from_millis(100));
+        assert!(matches!(result, Err(AppError::Internal(_))));
+        drop(server.join().unwrap());
+    }
+
+    #[test]
+    fn test_read_message_rejects_oversized_length() {
+        let mut reply: &[u8] = b"i3-ipc\xff\xff\xff\xff\x03\0\0\0";
+        assert!(matches!(read_ipc_message(&mut reply), Err(AppError::Parse(_))));
+    }
+
+    #[test]
+    fn test_read_message_rejects_bad_magic() {
+        let mut reply: &[u8] = b"i3-ipd\0\0\0\0\x03\0\0\0";
+        assert!(matches!(read_ipc_message(&mut reply), Err(AppError::Parse(_))));
+    }
+}
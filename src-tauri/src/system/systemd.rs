@@ -0,0 +1,146 @@
+// ============================================================================
+// SYSTEMD USER SERVICE
+// ============================================================================
+//
+// Many setups run Waybar as a `systemctl --user` service. Signalling or
+// spawning Waybar behind systemd's back fights its restart policy, so the
+// process commands go through the service when it runs (or is enabled to).
+// Distributions ship the unit even where the compositor launches Waybar, so
+// the unit merely being installed says nothing.
+
+use crate::error::{AppError, Result};
+use std::process::{Command, Output};
+
+/// Unit name Waybar ships for running it as a user service
+const WAYBAR_SERVICE: &str = "waybar.service";
+
+/// Program controlling systemd
+const SYSTEMCTL: &str = "systemctl";
+
+// ============================================================================
+// SYSTEMCTL
+// ============================================================================
+
+/**
+ * Run a program, failing when it can't be run or exits unsuccessfully
+ *
+ * The error carries the program's stderr.
+ */
+fn run_checked(program: &str, args: &[&str]) -> Result<Output> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|e| AppError::NotFound(format!("Failed to run {}: {}", program, e)))?;
+
+    if output.status.success() {
+        Ok(output)
+    } else {
+        Err(AppError::Internal(format!(
+            "{} {} failed: {}",
+            program,
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+/**
+ * Run `systemctl --user <action> waybar.service`
+ */
+fn waybar_service_action(action: &str) -> Result<Output> {
+    run_checked(SYSTEMCTL, &["--user", action, WAYBAR_SERVICE])
+}
+
+// ============================================================================
+// COMMANDS
+// ============================================================================
+
+/**
+ * Check whether the Waybar user service is enabled
+ *
+ * Returns false when the unit is disabled, not installed, or systemd isn't
+ * available.
+ */
+#[tauri::command]
+pub async fn is_waybar_service_enabled() -> Result<bool> {
+    // `is-enabled` exits non-zero unless the unit is enabled in some form
+    Ok(waybar_service_action("is-enabled").is_ok())
+}
+
+/**
+ * Check whether the Waybar user service is running
+ *
+ * Returns false when the unit is inactive, failed or not installed.
+ */
+#[tauri::command]
+pub async fn is_waybar_service_active() -> Result<bool> {
+    // `is-active` exits non-zero for every state but active
+    Ok(waybar_service_action("is-active").is_ok())
+}
+
+/**
+ * Start the Waybar user service
+ */
+#[tauri::command]
+pub async fn start_waybar_service() -> Result<()> {
+    waybar_service_action("start")?;
+    log::info!("Started {}", WAYBAR_SERVICE);
+    Ok(())
+}
+
+/**
+ * Stop the Waybar user service
+ */
+#[tauri::command]
+pub async fn stop_waybar_service() -> Result<()> {
+    waybar_service_action("stop")?;
+    log::info!("Stopped {}", WAYBAR_SERVICE);
+    Ok(())
+}
+
+/**
+ * Restart the Waybar user service
+ */
+#[tauri::command]
+pub async fn restart_waybar_service() -> Result<()> {
+    waybar_service_action("restart")?;
+    log::info!("Restarted {}", WAYBAR_SERVICE);
+    Ok(())
+}
+
+/**
+ * Reload the Waybar user service
+ *
+ * Uses the unit's `ExecReload`, which sends SIGUSR2 in the unit Waybar ships.
+ */
+#[tauri::command]
+pub async fn reload_waybar_service() -> Result<()> {
+    waybar_service_action("reload")?;
+    log::info!("Reloaded {}", WAYBAR_SERVICE);
+    Ok(())
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_checked() {
+        assert!(run_checked("true", &[]).is_ok());
+
+        let script = "echo 'Unit waybar.service not found.' >&2; exit 1";
+        match run_checked("sh", &["-c", script]) {
+            Err(AppError::Internal(message)) => {
+                assert!(message.ends_with("failed: Unit waybar.service not found."))
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        let missing = run_checked("/nonexistent/systemctl", &["--user", "cat"]);
+        assert!(matches!(missing, Err(AppError::NotFound(_))));
+    }
+}
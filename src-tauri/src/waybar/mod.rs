@@ -3,5 +3,7 @@
 // ============================================================================
 
 pub mod process;
+pub mod stats;
 
 pub use process::*;
+pub use stats::*;
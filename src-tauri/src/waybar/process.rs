@@ -2,18 +2,192 @@
 // WAYBAR PROCESS MANAGEMENT
 // ============================================================================
 
+use crate::config::ConfigPaths;
 use crate::error::{AppError, Result};
-use std::process::Command;
+use crate::system::systemd;
+use nix::errno::Errno;
+use nix::sys::signal::{kill, Signal};
+use nix::unistd::Pid;
+use std::fs;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
 
 // ============================================================================
 // CONSTANTS
 // ============================================================================
 
-/// Signal to reload Waybar configuration (SIGUSR2)
-const RELOAD_SIGNAL: &str = "SIGUSR2";
+/// Signal to reload Waybar configuration
+const RELOAD_SIGNAL: Signal = Signal::SIGUSR2;
+
+/// Signal to toggle Waybar's visibility
+const TOGGLE_SIGNAL: Signal = Signal::SIGUSR1;
 
 /// Process name for Waybar
-const WAYBAR_PROCESS: &str = "waybar";
+pub(crate) const WAYBAR_PROCESS: &str = "waybar";
+
+/// Where running processes are listed
+pub(crate) const PROC_DIR: &str = "/proc";
+
+/// Event emitted for each line Waybar writes to stderr
+pub const WAYBAR_LOG_EVENT: &str = "waybar-log";
+
+/// How long a freshly started Waybar must survive to count as started
+const STARTUP_WINDOW: Duration = Duration::from_secs(2);
+
+/// How long to wait for Waybar to exit after SIGTERM, and again after SIGKILL
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(3);
+
+/// How often to check whether Waybar has exited
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+// ============================================================================
+// PROCESS DISCOVERY
+// ============================================================================
+
+/**
+ * Find the PIDs of processes named `name` in a procfs directory
+ *
+ * Matches `/proc/<pid>/comm` exactly, so `waybar-config-gui` or a shell
+ * running `waybar.sh` don't count. Processes that exit while the directory
+ * is being scanned are skipped.
+ */
+pub fn find_pids_in(proc_dir: &Path, name: &str) -> Result<Vec<u32>> {
+    let entries = fs::read_dir(proc_dir).map_err(|e| {
+        AppError::Internal(format!("Failed to list processes in {}: {}", proc_dir.display(), e))
+    })?;
+
+    let mut pids: Vec<u32> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| entry.file_name().to_str()?.parse::<u32>().ok())
+        .filter(|pid| {
+            fs::read_to_string(proc_dir.join(pid.to_string()).join("comm"))
+                .is_ok_and(|comm| comm.trim_end_matches('\n') == name)
+        })
+        .collect();
+    pids.sort_unstable();
+
+    Ok(pids)
+}
+
+/**
+ * Send a signal to every running Waybar process
+ *
+ * A process that exits before the signal arrives is not an error.
+ */
+fn signal_waybar(signal: Signal) -> Result<()> {
+    for pid in find_pids_in(Path::new(PROC_DIR), WAYBAR_PROCESS)? {
+        match kill(Pid::from_raw(pid as i32), signal) {
+            Ok(()) | Err(Errno::ESRCH) => {}
+            Err(Errno::EPERM) => {
+                return Err(AppError::PermissionDenied(format!(
+                    "Not allowed to send {} to Waybar (PID {})",
+                    signal, pid
+                )))
+            }
+            Err(e) => {
+                return Err(AppError::Internal(format!(
+                    "Failed to send {} to Waybar (PID {}): {}",
+                    signal, pid, e
+                )))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/**
+ * Find the value of a command-line option in Waybar's arguments
+ *
+ * Accepts the forms Waybar itself does: `-c path`, `-cpath`, `--config path`
+ * and `--config=path`. The last occurrence wins.
+ */
+pub fn waybar_option<'a>(args: &[&'a str], short: &str, long: &str) -> Option<&'a str> {
+    let mut value = None;
+    let mut iter = args.iter().skip(1);
+
+    while let Some(arg) = iter.next() {
+        if *arg == short || *arg == long {
+            value = iter.next().copied().or(value);
+        } else if let Some(inline) = arg.strip_prefix(long).and_then(|rest| rest.strip_prefix('=')) {
+            value = Some(inline);
+        } else if let Some(inline) = arg.strip_prefix(short).filter(|_| !arg.starts_with("--")) {
+            value = Some(inline);
+        }
+    }
+
+    value
+}
+
+/**
+ * Get the config and style paths a running Waybar was started with
+ *
+ * Reads the first Waybar process's `cmdline` in a procfs directory. Paths
+ * without a `-c`/`--config` or `-s`/`--style` flag fall back to `defaults`;
+ * relative paths are resolved against the process's working directory.
+ *
+ * Returns:
+ * - Err(NotFound) if Waybar is not running
+ */
+pub fn running_config_paths_in(proc_dir: &Path, defaults: &ConfigPaths) -> Result<ConfigPaths> {
+    let pid = find_pids_in(proc_dir, WAYBAR_PROCESS)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| AppError::NotFound("Waybar is not running".to_string()))?;
+    let process_dir = proc_dir.join(pid.to_string());
+
+    let cmdline = fs::read(process_dir.join("cmdline"))?;
+    let cmdline = String::from_utf8_lossy(&cmdline);
+    let args: Vec<&str> = cmdline.split('\0').filter(|arg| !arg.is_empty()).collect();
+    let cwd = fs::read_link(process_dir.join("cwd")).ok();
+
+    let resolve = |path: &str| -> String {
+        match &cwd {
+            Some(cwd) if Path::new(path).is_relative() => {
+                cwd.join(path).to_string_lossy().to_string()
+            }
+            _ => path.to_string(),
+        }
+    };
+
+    let mut paths = defaults.clone();
+    if let Some(config) = waybar_option(&args, "-c", "--config") {
+        let config = PathBuf::from(resolve(config));
+        if let Some(dir) = config.parent() {
+            paths.config_dir = dir.to_string_lossy().to_string();
+        }
+        paths.config_file = config.to_string_lossy().to_string();
+    }
+    if let Some(style) = waybar_option(&args, "-s", "--style") {
+        paths.style_file = resolve(style);
+    }
+
+    Ok(paths)
+}
+
+/**
+ * Get the config and style paths of the running Waybar
+ *
+ * Unlike `detect_config_paths`, this follows a `-c`/`-s` given on Waybar's
+ * command line, so the files edited are the ones actually loaded.
+ *
+ * Returns:
+ * - Err(NotFound) if Waybar is not running
+ */
+#[tauri::command]
+pub async fn get_running_waybar_config_path() -> Result<ConfigPaths> {
+    let mut defaults = ConfigPaths::default()?;
+    if let Some(config) = ConfigPaths::detect_config_file(&defaults.config_dir) {
+        defaults.config_file = config.to_string_lossy().to_string();
+    }
+
+    running_config_paths_in(Path::new(PROC_DIR), &defaults)
+}
 
 // ============================================================================
 // PROCESS OPERATIONS
@@ -24,69 +198,80 @@ const WAYBAR_PROCESS: &str = "waybar";
  *
  * This is the recommended way to reload Waybar without restarting.
  * Waybar will reload both config and style files when it receives SIGUSR2.
- *
- * Uses `pkill -SIGUSR2 waybar` to send the signal.
+ * A running `waybar.service` is reloaded through systemd instead.
  *
  * Returns:
  * - Ok(()) if signal sent successfully (or if Waybar is not running)
- * - Err if pkill command fails
+ * - Err if the signal could not be sent
  */
 #[tauri::command]
 pub async fn reload_waybar() -> Result<()> {
-    // Check if Waybar is running first
-    if !is_waybar_running().await? {
-        // Not an error - Waybar just isn't running
-        return Ok(());
+    if systemd::is_waybar_service_active().await? {
+        return systemd::reload_waybar_service().await;
     }
+    signal_waybar(RELOAD_SIGNAL)
+}
 
-    // Send SIGUSR2 signal to Waybar
-    let output = Command::new("pkill")
-        .arg(format!("-{}", RELOAD_SIGNAL))
-        .arg(WAYBAR_PROCESS)
-        .output()
-        .map_err(|e| {
-            AppError::Internal(format!("Failed to execute pkill command: {}", e))
-        })?;
-
-    // pkill returns 0 if signal was sent successfully
-    if output.status.success() {
-        Ok(())
-    } else {
-        // Get error message from stderr if available
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        if !stderr.is_empty() {
-            Err(AppError::Internal(format!(
-                "Failed to reload Waybar: {}",
-                stderr.trim()
-            )))
+/**
+ * Check that a config file parses before Waybar is asked to load it
+ */
+pub fn check_config_loadable(config_path: &Path) -> Result<()> {
+    let content = fs::read_to_string(config_path).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            AppError::NotFound(format!("Config file not found: {}", config_path.display()))
         } else {
-            // pkill returns 1 if no processes matched, but we already checked if running
-            Ok(())
+            AppError::from(e)
         }
-    }
+    })?;
+
+    crate::config::parser::parse_jsonc(&content).map_err(|e| {
+        AppError::Validation(format!("Not reloading Waybar, the config is invalid: {}", e))
+    })?;
+    Ok(())
 }
 
 /**
- * Check if Waybar process is currently running
+ * Reload Waybar only if its config parses
+ *
+ * A config Waybar can't parse makes the bar disappear on reload, so the
+ * file is checked first and Waybar is left untouched when it's invalid.
+ *
+ * Returns:
+ * - Ok(()) if signal sent successfully (or if Waybar is not running)
+ * - Err(Validation) if the config doesn't parse
+ * - Err if the signal could not be sent
+ */
+#[tauri::command]
+pub async fn reload_waybar_safe(config_path: String) -> Result<()> {
+    check_config_loadable(Path::new(&config_path))?;
+    reload_waybar().await
+}
+
+/**
+ * Send SIGUSR1 signal to Waybar processes to hide or show the bar
  *
- * Uses `pgrep waybar` to check for running Waybar instances.
+ * Every Waybar process is signalled, so all bars toggle together.
+ *
+ * Returns:
+ * - Ok(()) if signal sent successfully (or if Waybar is not running)
+ * - Err if the signal could not be sent
+ */
+#[tauri::command]
+pub async fn toggle_waybar_visibility() -> Result<()> {
+    signal_waybar(TOGGLE_SIGNAL)
+}
+
+/**
+ * Check if Waybar process is currently running
  *
  * Returns:
  * - Ok(true) if Waybar is running
  * - Ok(false) if Waybar is not running
- * - Err if pgrep command fails
+ * - Err if the process list can't be read
  */
 #[tauri::command]
 pub async fn is_waybar_running() -> Result<bool> {
-    let output = Command::new("pgrep")
-        .arg(WAYBAR_PROCESS)
-        .output()
-        .map_err(|e| {
-            AppError::Internal(format!("Failed to execute pgrep command: {}", e))
-        })?;
-
-    // pgrep returns 0 if processes found, 1 if none found
-    Ok(output.status.success())
+    Ok(!get_waybar_pids().await?.is_empty())
 }
 
 /**
@@ -95,51 +280,79 @@ pub async fn is_waybar_running() -> Result<bool> {
  * Returns:
  * - Ok(Vec<u32>) with process IDs if Waybar is running
  * - Ok(empty Vec) if Waybar is not running
- * - Err if command fails
+ * - Err if the process list can't be read
  */
 #[tauri::command]
 pub async fn get_waybar_pids() -> Result<Vec<u32>> {
-    let output = Command::new("pgrep")
-        .arg(WAYBAR_PROCESS)
-        .output()
-        .map_err(|e| {
-            AppError::Internal(format!("Failed to execute pgrep command: {}", e))
-        })?;
+    find_pids_in(Path::new(PROC_DIR), WAYBAR_PROCESS)
+}
 
-    if !output.status.success() {
-        // No processes found
-        return Ok(Vec::new());
+/**
+ * Start Waybar process
+ *
+ * Launches Waybar with its default config and style paths, or starts
+ * `waybar.service` when that unit is enabled.
+ * Does nothing if Waybar is already running.
+ *
+ * Returns:
+ * - Ok(()) if Waybar started successfully or already running
+ * - Err if command fails
+ */
+#[tauri::command]
+pub async fn start_waybar() -> Result<()> {
+    if is_waybar_running().await? {
+        return Ok(());
     }
+    if systemd::is_waybar_service_enabled().await? {
+        return systemd::start_waybar_service().await;
+    }
+    start_waybar_with(None, None).await
+}
 
-    // Parse PIDs from output
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let pids: Vec<u32> = stdout
-        .lines()
-        .filter_map(|line| line.trim().parse::<u32>().ok())
-        .collect();
+/**
+ * Build the arguments selecting a config (`-c`) and style (`-s`) file
+ *
+ * Each given path must exist.
+ */
+pub fn waybar_args(config_path: Option<&str>, style_path: Option<&str>) -> Result<Vec<String>> {
+    let mut args = Vec::new();
 
-    Ok(pids)
+    for (flag, path, what) in [("-c", config_path, "Config"), ("-s", style_path, "Style")] {
+        if let Some(path) = path {
+            if !Path::new(path).is_file() {
+                return Err(AppError::NotFound(format!("{} file not found: {}", what, path)));
+            }
+            args.push(flag.to_string());
+            args.push(path.to_string());
+        }
+    }
+
+    Ok(args)
 }
 
 /**
- * Start Waybar process
+ * Start Waybar with a specific config and/or style file
  *
- * Launches Waybar as a background process.
- * Does nothing if Waybar is already running.
+ * Launches Waybar as a background process, passing `-c <config>` and
+ * `-s <style>` for the paths given. Does nothing if Waybar is already running.
  *
  * Returns:
  * - Ok(()) if Waybar started successfully or already running
+ * - Err(NotFound) if a given path doesn't exist
  * - Err if command fails
  */
 #[tauri::command]
-pub async fn start_waybar() -> Result<()> {
+pub async fn start_waybar_with(config_path: Option<String>, style_path: Option<String>) -> Result<()> {
+    let args = waybar_args(config_path.as_deref(), style_path.as_deref())?;
+
     // Check if already running
     if is_waybar_running().await? {
         return Ok(());
     }
 
     // Start Waybar in background
-    Command::new("waybar")
+    Command::new(WAYBAR_PROCESS)
+        .args(&args)
         .spawn()
         .map_err(|e| {
             AppError::Internal(format!("Failed to start Waybar: {}", e))
@@ -148,68 +361,202 @@ pub async fn start_waybar() -> Result<()> {
     Ok(())
 }
 
+/**
+ * Watch a freshly spawned Waybar through its startup window
+ *
+ * Every stderr line is passed to `on_line` from a background thread, for as
+ * long as the process runs (the pipe is drained to the end so Waybar never
+ * blocks or dies writing to it). If the process exits within `window`, the
+ * error carries everything it wrote to stderr. Lines are only kept during
+ * the window, so a long-running Waybar doesn't grow the buffer.
+ */
+pub fn watch_startup(
+    mut child: Child,
+    window: Duration,
+    on_line: impl Fn(String) + Send + 'static,
+) -> Result<()> {
+    let captured = Arc::new(Mutex::new(Vec::new()));
+    let capturing = Arc::new(AtomicBool::new(true));
+    let reader = child.stderr.take().map(|stderr| {
+        let captured = Arc::clone(&captured);
+        let capturing = Arc::clone(&capturing);
+        thread::spawn(move || {
+            for line in BufReader::new(stderr).lines().map_while(|line| line.ok()) {
+                if capturing.load(Ordering::Relaxed) {
+                    if let Ok(mut captured) = captured.lock() {
+                        captured.push(line.clone());
+                    }
+                }
+                on_line(line);
+            }
+        })
+    });
+
+    let started = Instant::now();
+    while started.elapsed() < window {
+        if let Some(status) = child.try_wait()? {
+            // The pipe closes with the process, so the reader finishes promptly
+            if let Some(reader) = reader {
+                let _ = reader.join();
+            }
+            let stderr = captured.lock().map(|lines| lines.join("\n")).unwrap_or_default();
+            return Err(AppError::Internal(format!(
+                "Waybar exited during startup ({}):\n{}",
+                status, stderr
+            )));
+        }
+        thread::sleep(Duration::from_millis(50));
+    }
+
+    // Startup succeeded; keep forwarding lines but stop collecting them
+    capturing.store(false, Ordering::Relaxed);
+    if let Ok(mut captured) = captured.lock() {
+        captured.clear();
+        captured.shrink_to_fit();
+    }
+
+    // Reap the process whenever it exits
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/**
+ * Start Waybar and report its stderr
+ *
+ * Emits a `waybar-log` event for each line Waybar writes to stderr. If
+ * Waybar exits within two seconds (usually a config or style error), the
+ * captured stderr is returned as the error.
+ * Does nothing if Waybar is already running.
+ */
+#[tauri::command]
+pub async fn start_waybar_capturing(app: tauri::AppHandle) -> Result<()> {
+    use tauri::Emitter;
+
+    if is_waybar_running().await? {
+        return Ok(());
+    }
+
+    let child = Command::new(WAYBAR_PROCESS)
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| AppError::Internal(format!("Failed to start Waybar: {}", e)))?;
+
+    watch_startup(child, STARTUP_WINDOW, move |line| {
+        if let Err(e) = app.emit(WAYBAR_LOG_EVENT, line) {
+            log::warn!("Failed to emit Waybar log event: {}", e);
+        }
+    })
+}
+
 /**
  * Stop Waybar process
  *
- * Sends SIGTERM to Waybar process to gracefully shut it down.
- * Uses `pkill waybar` (default signal is SIGTERM).
+ * Sends SIGTERM to Waybar process to gracefully shut it down. A running
+ * `waybar.service` is stopped through systemd so it isn't restarted.
  *
  * Returns:
  * - Ok(()) if Waybar stopped successfully or not running
- * - Err if command fails
+ * - Err if the signal could not be sent
  */
 #[tauri::command]
 pub async fn stop_waybar() -> Result<()> {
-    // Check if running first
-    if !is_waybar_running().await? {
-        return Ok(());
+    if systemd::is_waybar_service_active().await? {
+        return systemd::stop_waybar_service().await;
     }
+    signal_waybar(Signal::SIGTERM)
+}
 
-    // Send SIGTERM to Waybar
-    let output = Command::new("pkill")
-        .arg(WAYBAR_PROCESS)
-        .output()
-        .map_err(|e| {
-            AppError::Internal(format!("Failed to execute pkill command: {}", e))
-        })?;
-
-    if output.status.success() {
-        Ok(())
-    } else {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        if !stderr.is_empty() {
-            Err(AppError::Internal(format!(
-                "Failed to stop Waybar: {}",
-                stderr.trim()
-            )))
-        } else {
-            Ok(())
+/**
+ * Poll `is_running` every `interval` until it reports false or `timeout` passes
+ *
+ * Returns whether the process stopped in time.
+ */
+pub fn wait_for_exit(
+    mut is_running: impl FnMut() -> Result<bool>,
+    timeout: Duration,
+    interval: Duration,
+) -> Result<bool> {
+    let started = Instant::now();
+    loop {
+        if !is_running()? {
+            return Ok(true);
         }
+        if started.elapsed() >= timeout {
+            return Ok(false);
+        }
+        thread::sleep(interval);
     }
 }
 
 /**
  * Restart Waybar process
  *
- * Stops and then starts Waybar.
+ * Stops Waybar, waits for it to exit, then starts it again.
  * Useful when configuration changes require a full restart.
+ * A Waybar that ignores SIGTERM for 3 seconds is sent SIGKILL.
+ * When `waybar.service` is running, systemd restarts it instead.
  *
  * Returns:
  * - Ok(()) if restart successful
- * - Err if stop or start fails
+ * - Err if Waybar won't exit, or stop or start fails
  */
 #[tauri::command]
 pub async fn restart_waybar() -> Result<()> {
+    if systemd::is_waybar_service_active().await? {
+        return systemd::restart_waybar_service().await;
+    }
+
+    let waybar_running = || Ok(!find_pids_in(Path::new(PROC_DIR), WAYBAR_PROCESS)?.is_empty());
+
     stop_waybar().await?;
 
-    // Give Waybar time to fully shut down
-    std::thread::sleep(std::time::Duration::from_millis(500));
+    if !wait_for_exit(waybar_running, SHUTDOWN_TIMEOUT, SHUTDOWN_POLL_INTERVAL)? {
+        log::warn!("Waybar did not exit after SIGTERM; sending SIGKILL");
+        signal_waybar(Signal::SIGKILL)?;
+
+        if !wait_for_exit(waybar_running, SHUTDOWN_TIMEOUT, SHUTDOWN_POLL_INTERVAL)? {
+            return Err(AppError::Internal(format!(
+                "Waybar is still running {} seconds after SIGKILL; not starting another instance",
+                SHUTDOWN_TIMEOUT.as_secs()
+            )));
+        }
+    }
 
     start_waybar().await?;
 
     Ok(())
 }
 
+/**
+ * Get the installed Waybar version (e.g. "0.10.4")
+ *
+ * Runs `waybar --version`, which prints `Waybar v0.10.4`.
+ *
+ * Returns:
+ * - Err(NotFound) if Waybar is not installed or prints no version
+ */
+#[tauri::command]
+pub async fn get_waybar_version() -> Result<String> {
+    let output = Command::new(WAYBAR_PROCESS)
+        .arg("--version")
+        .output()
+        .map_err(|e| AppError::NotFound(format!("Failed to run waybar --version: {}", e)))?;
+
+    parse_waybar_version(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| AppError::NotFound("Could not determine the Waybar version".to_string()))
+}
+
+/**
+ * Extract the version number from `waybar --version` output
+ */
+pub fn parse_waybar_version(output: &str) -> Option<String> {
+    output
+        .split_whitespace()
+        .map(|word| word.trim_start_matches('v'))
+        .find(|word| word.starts_with(|c: char| c.is_ascii_digit()) && word.contains('.'))
+        .map(str::to_string)
+}
+
 // ============================================================================
 // TESTS
 // ============================================================================
@@ -226,6 +573,7 @@ mod tests {
     }
 
     #[tokio::test]
+    #[allow(unused_comparisons, clippy::absurd_extreme_comparisons)]
     async fn test_get_waybar_pids() {
         let result = get_waybar_pids().await;
         assert!(result.is_ok());
@@ -242,4 +590,156 @@ mod tests {
         let result = reload_waybar().await;
         assert!(result.is_ok());
     }
+
+    #[test]
+    fn test_find_pids_in_matches_exact_name() {
+        let proc_dir = tempfile::TempDir::new().unwrap();
+        let processes = [("812", "waybar\n"), ("77", "waybar\n"), ("900", "waybar-config-g\n"), ("5", "sh\n")];
+        for (pid, comm) in processes {
+            fs::create_dir(proc_dir.path().join(pid)).unwrap();
+            fs::write(proc_dir.path().join(pid).join("comm"), comm).unwrap();
+        }
+        fs::create_dir(proc_dir.path().join("self")).unwrap();
+        fs::create_dir(proc_dir.path().join("1234")).unwrap();
+
+        assert_eq!(find_pids_in(proc_dir.path(), "waybar").unwrap(), vec![77, 812]);
+        assert!(find_pids_in(&proc_dir.path().join("missing"), "waybar").is_err());
+    }
+
+    #[test]
+    fn test_waybar_option_forms() {
+        let args = ["waybar", "-c", "a.jsonc", "--style=b.css", "-lwarning"];
+        assert_eq!(waybar_option(&args, "-c", "--config"), Some("a.jsonc"));
+        assert_eq!(waybar_option(&args, "-s", "--style"), Some("b.css"));
+        assert_eq!(waybar_option(&args, "-l", "--log-level"), Some("warning"));
+
+        let args = ["waybar", "--config", "first", "-csecond"];
+        assert_eq!(waybar_option(&args, "-c", "--config"), Some("second"));
+        assert_eq!(waybar_option(&["waybar", "-c"], "-c", "--config"), None);
+        assert_eq!(waybar_option(&["waybar"], "-s", "--style"), None);
+    }
+
+    #[test]
+    fn test_running_config_paths_in() {
+        let proc_dir = tempfile::TempDir::new().unwrap();
+        let process = proc_dir.path().join("42");
+        fs::create_dir(&process).unwrap();
+        fs::write(process.join("comm"), "waybar\n").unwrap();
+        std::os::unix::fs::symlink("/home/user", process.join("cwd")).unwrap();
+        let defaults = ConfigPaths::in_dir("/home/user/.config/waybar");
+
+        let cmdline = "waybar\0-c\0/etc/xdg/waybar/config\0-s\0bar.css\0";
+        fs::write(process.join("cmdline"), cmdline).unwrap();
+        let paths = running_config_paths_in(proc_dir.path(), &defaults).unwrap();
+        assert_eq!(paths.config_dir, "/etc/xdg/waybar");
+        assert_eq!(paths.config_file, "/etc/xdg/waybar/config");
+        assert_eq!(paths.style_file, "/home/user/bar.css");
+
+        fs::write(process.join("cmdline"), "/usr/bin/waybar\0").unwrap();
+        let paths = running_config_paths_in(proc_dir.path(), &defaults).unwrap();
+        assert_eq!(paths.config_file, defaults.config_file);
+        assert_eq!(paths.style_file, defaults.style_file);
+
+        fs::write(process.join("comm"), "sh\n").unwrap();
+        let result = running_config_paths_in(proc_dir.path(), &defaults);
+        assert!(matches!(result, Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_waybar_args() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = temp_dir.path().join("config.jsonc");
+        fs::write(&config, "{}").unwrap();
+        let config = config.to_str().unwrap();
+
+        assert!(waybar_args(None, None).unwrap().is_empty());
+        assert_eq!(waybar_args(Some(config), None).unwrap(), vec!["-c", config]);
+
+        let missing = temp_dir.path().join("style.css");
+        assert!(matches!(
+            waybar_args(Some(config), missing.to_str()),
+            Err(AppError::NotFound(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_toggle_waybar_visibility() {
+        // Test should not fail even if Waybar is not running
+        let result = toggle_waybar_visibility().await;
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_check_config_loadable() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config = temp_dir.path().join("config.jsonc");
+
+        fs::write(&config, "// bar\n{ \"height\": 30 }").unwrap();
+        assert!(check_config_loadable(&config).is_ok());
+
+        fs::write(&config, "{ \"height\": 30 \"layer\": \"top\" }").unwrap();
+        assert!(matches!(check_config_loadable(&config), Err(AppError::Validation(_))));
+
+        let missing = temp_dir.path().join("config");
+        assert!(matches!(check_config_loadable(&missing), Err(AppError::NotFound(_))));
+    }
+
+    #[test]
+    fn test_wait_for_exit() {
+        let mut checks = 0;
+        let stopped = wait_for_exit(
+            || {
+                checks += 1;
+                Ok(checks < 3)
+            },
+            Duration::from_secs(1),
+            Duration::from_millis(1),
+        );
+        assert!(stopped.unwrap());
+        assert_eq!(checks, 3);
+
+        let stopped = wait_for_exit(|| Ok(true), Duration::from_millis(20), Duration::from_millis(5));
+        assert!(!stopped.unwrap());
+    }
+
+    #[test]
+    fn test_watch_startup_reports_early_exit() {
+        let child = Command::new("sh")
+            .args(["-c", "echo '[error] Config file not found' >&2; exit 1"])
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let result = watch_startup(child, Duration::from_secs(2), move |line| tx.send(line).unwrap());
+
+        match result {
+            Err(AppError::Internal(message)) => assert!(message.contains("[error] Config file not found")),
+            other => panic!("Expected an early exit error, got {:?}", other),
+        }
+        assert_eq!(rx.recv().unwrap(), "[error] Config file not found");
+    }
+
+    #[test]
+    fn test_watch_startup_accepts_running_process() {
+        let child = Command::new("sh")
+            .args(["-c", "echo '[info] Bar configured' >&2; sleep 0.6; echo '[info] Reloading' >&2; sleep 1"])
+            .stderr(Stdio::piped())
+            .spawn()
+            .unwrap();
+
+        let (tx, rx) = std::sync::mpsc::channel();
+        let result = watch_startup(child, Duration::from_millis(300), move |line| tx.send(line).unwrap());
+        assert!(result.is_ok());
+        assert_eq!(rx.recv_timeout(Duration::from_secs(2)).unwrap(), "[info] Bar configured");
+        // Lines written after the window are still forwarded
+        assert_eq!(rx.recv_timeout(Duration::from_secs(2)).unwrap(), "[info] Reloading");
+    }
+
+    #[test]
+    fn test_parse_waybar_version() {
+        assert_eq!(parse_waybar_version("Waybar v0.10.4\n").as_deref(), Some("0.10.4"));
+        assert_eq!(parse_waybar_version("Waybar v0.9.24-42-gabcdef").as_deref(), Some("0.9.24-42-gabcdef"));
+        assert_eq!(parse_waybar_version("unknown option"), None);
+    }
 }
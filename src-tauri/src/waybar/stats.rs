@@ -0,0 +1,209 @@
+// ============================================================================
+// WAYBAR RESOURCE USAGE
+// ============================================================================
+//
+// Memory and CPU usage of the running Waybar processes, read from procfs.
+// Useful for spotting custom modules that leak.
+
+use super::process::{find_pids_in, PROC_DIR, WAYBAR_PROCESS};
+use crate::error::{AppError, Result};
+use nix::unistd::{sysconf, SysconfVar};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How long CPU time is sampled for
+const SAMPLE_INTERVAL: Duration = Duration::from_millis(250);
+
+// ============================================================================
+// TYPES
+// ============================================================================
+
+/**
+ * Resource usage of one Waybar process
+ */
+#[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct WaybarStat {
+    pub pid: u32,
+    /// Resident memory in KiB
+    pub rss_kb: u64,
+    /// CPU usage over the sampling interval, where 100 is one full core
+    pub cpu_percent: f64,
+}
+
+/**
+ * Units needed to turn procfs counters into KiB and seconds
+ */
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProcUnits {
+    /// Size of a memory page in KiB
+    pub page_kb: u64,
+    /// Clock ticks per second (`USER_HZ`)
+    pub ticks_per_second: u64,
+}
+
+impl ProcUnits {
+    /**
+     * Read the units of this system
+     */
+    pub fn current() -> Result<Self> {
+        let read = |var: SysconfVar| -> Result<u64> {
+            match sysconf(var) {
+                Ok(Some(value)) if value > 0 => Ok(value as u64),
+                Ok(_) => Err(AppError::Internal(format!("{:?} is not available", var))),
+                Err(e) => Err(AppError::Internal(format!("Failed to read {:?}: {}", var, e))),
+            }
+        };
+
+        Ok(Self {
+            page_kb: read(SysconfVar::PAGE_SIZE)? / 1024,
+            ticks_per_second: read(SysconfVar::CLK_TCK)?,
+        })
+    }
+}
+
+// ============================================================================
+// PARSING
+// ============================================================================
+
+/**
+ * Get the resident set size, in pages, from `/proc/<pid>/statm`
+ */
+pub fn parse_statm_rss_pages(statm: &str) -> Option<u64> {
+    statm.split_whitespace().nth(1)?.parse().ok()
+}
+
+/**
+ * Get the CPU time used (user + system), in clock ticks, from `/proc/<pid>/stat`
+ *
+ * The process name in parentheses may itself contain spaces or parentheses,
+ * so fields are counted from the last `)`.
+ */
+pub fn parse_stat_cpu_ticks(stat: &str) -> Option<u64> {
+    let (_, rest) = stat.rsplit_once(')')?;
+    // `rest` starts at field 3 (state); utime and stime are fields 14 and 15
+    let mut fields = rest.split_whitespace().skip(11);
+    let utime: u64 = fields.next()?.parse().ok()?;
+    let stime: u64 = fields.next()?.parse().ok()?;
+    Some(utime + stime)
+}
+
+// ============================================================================
+// SAMPLING
+// ============================================================================
+
+/**
+ * Read the CPU ticks of each Waybar process
+ *
+ * Processes that exit meanwhile are left out.
+ */
+fn sample_cpu_ticks(proc_dir: &Path) -> Result<HashMap<u32, u64>> {
+    Ok(find_pids_in(proc_dir, WAYBAR_PROCESS)?
+        .into_iter()
+        .filter_map(|pid| {
+            let stat = fs::read_to_string(proc_dir.join(pid.to_string()).join("stat")).ok()?;
+            Some((pid, parse_stat_cpu_ticks(&stat)?))
+        })
+        .collect())
+}
+
+/**
+ * Measure every Waybar process in a procfs directory
+ *
+ * CPU usage is the CPU time used between two samples `interval` apart.
+ * Processes that start or exit during the interval are left out.
+ */
+pub fn waybar_stats_in(
+    proc_dir: &Path,
+    interval: Duration,
+    units: ProcUnits,
+) -> Result<Vec<WaybarStat>> {
+    let before = sample_cpu_ticks(proc_dir)?;
+    if before.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let started = Instant::now();
+    thread::sleep(interval);
+    let after = sample_cpu_ticks(proc_dir)?;
+    let elapsed = started.elapsed().as_secs_f64();
+
+    let mut stats: Vec<WaybarStat> = after
+        .into_iter()
+        .filter_map(|(pid, ticks)| {
+            let used = ticks.saturating_sub(*before.get(&pid)?);
+            let statm = fs::read_to_string(proc_dir.join(pid.to_string()).join("statm")).ok()?;
+            let cpu_seconds = used as f64 / units.ticks_per_second as f64;
+
+            Some(WaybarStat {
+                pid,
+                rss_kb: parse_statm_rss_pages(&statm)? * units.page_kb,
+                cpu_percent: if elapsed > 0.0 { cpu_seconds / elapsed * 100.0 } else { 0.0 },
+            })
+        })
+        .collect();
+    stats.sort_by_key(|stat| stat.pid);
+
+    Ok(stats)
+}
+
+/**
+ * Get memory and CPU usage of the running Waybar processes
+ *
+ * Takes about a quarter of a second to sample CPU usage.
+ *
+ * Returns:
+ * - Ok(empty) if Waybar is not running
+ */
+#[tauri::command]
+pub async fn get_waybar_stats() -> Result<Vec<WaybarStat>> {
+    waybar_stats_in(Path::new(PROC_DIR), SAMPLE_INTERVAL, ProcUnits::current()?)
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const STAT: &str = "4242 (waybar) S 1 4242 4242 0 -1 4194560 21034 0 0 0 \
+                        130 45 0 0 20 0 12 0 8412 1021243392 9250 18446744073709551615";
+
+    #[test]
+    fn test_parse_stat_cpu_ticks() {
+        assert_eq!(parse_stat_cpu_ticks(STAT), Some(175));
+
+        let odd_name = STAT.replace("(waybar)", "(way bar) (x)");
+        assert_eq!(parse_stat_cpu_ticks(&odd_name), Some(175));
+        assert_eq!(parse_stat_cpu_ticks("4242 (waybar) S 1"), None);
+    }
+
+    #[test]
+    fn test_parse_statm_rss_pages() {
+        assert_eq!(parse_statm_rss_pages("249327 9250 3046 1 0 40317 0\n"), Some(9250));
+        assert_eq!(parse_statm_rss_pages(""), None);
+    }
+
+    #[test]
+    fn test_waybar_stats_in() {
+        let proc_dir = TempDir::new().unwrap();
+        let units = ProcUnits { page_kb: 4, ticks_per_second: 100 };
+
+        let empty = waybar_stats_in(proc_dir.path(), Duration::ZERO, units).unwrap();
+        assert!(empty.is_empty());
+
+        let process = proc_dir.path().join("4242");
+        fs::create_dir(&process).unwrap();
+        fs::write(process.join("comm"), "waybar\n").unwrap();
+        fs::write(process.join("stat"), STAT).unwrap();
+        fs::write(process.join("statm"), "249327 9250 3046 1 0 40317 0\n").unwrap();
+
+        let stats = waybar_stats_in(proc_dir.path(), Duration::from_millis(10), units).unwrap();
+        assert_eq!(stats, vec![WaybarStat { pid: 4242, rss_kb: 37000, cpu_percent: 0.0 }]);
+    }
+}
@@ -3,7 +3,30 @@
 // Type-safe wrappers for all Tauri backend commands
 // ============================================================================
 
-import { invoke } from '@tauri-apps/api/core'
+import { invoke as tauriInvoke, type InvokeArgs } from '@tauri-apps/api/core'
+
+/**
+ * Invoke a backend command, reporting its outcome to the diagnostics log
+ * The report is best-effort and never affects the caller
+ */
+async function invoke<T>(command: string, args?: InvokeArgs): Promise<T> {
+  const report = (error: unknown) =>
+    Promise.resolve(
+      tauriInvoke('log_command_result', {
+        command,
+        error: error instanceof Error ? error.message : error,
+      })
+    ).catch(() => {})
+
+  try {
+    const result = await tauriInvoke<T>(command, args)
+    void report(null)
+    return result
+  } catch (error) {
+    void report(error)
+    throw error
+  }
+}
 
 // ============================================================================
 // TYPE DEFINITIONS
@@ -24,14 +47,39 @@ export interface ConfigPaths {
 export interface WaybarConfigFile {
   content: string
   path: string
+  /** Non-fatal problems found while loading (e.g. duplicate keys) */
+  warnings?: string[]
+  /** Whether the config pulls in other files with `include` */
+  has_includes?: boolean
 }
 
 /**
  * Error result from Tauri commands
  */
 export interface TauriError {
-  type: 'Io' | 'Config' | 'Parse' | 'Validation' | 'NotFound' | 'Internal'
+  type:
+    | 'Io'
+    | 'Config'
+    | 'Parse'
+    | 'Validation'
+    | 'NotFound'
+    | 'PermissionDenied'
+    | 'AlreadyExists'
+    | 'StorageFull'
+    | 'Internal'
+  /** Human-readable description, for display only */
   message: string
+  /** Stable machine-readable code to branch on */
+  code:
+    | 'IO_ERROR'
+    | 'CONFIG_ERROR'
+    | 'PARSE_ERROR'
+    | 'VALIDATION_ERROR'
+    | 'NOT_FOUND'
+    | 'PERMISSION_DENIED'
+    | 'ALREADY_EXISTS'
+    | 'STORAGE_FULL'
+    | 'INTERNAL_ERROR'
 }
 
 // ============================================================================
@@ -53,6 +101,22 @@ export async function detectConfigPaths(): Promise<ConfigPaths> {
   }
 }
 
+/**
+ * Detect Waybar configuration paths in a given directory
+ * Uses the standard location when no directory is given
+ *
+ * @param dir - Config directory (e.g. one profile of several)
+ * @returns Configuration paths or throws error if not found
+ * @throws TauriError if the config directory is not found
+ */
+export async function detectConfigPathsIn(dir?: string): Promise<ConfigPaths> {
+  try {
+    return await invoke<ConfigPaths>('detect_config_paths_in', { dir })
+  } catch (error) {
+    throw new Error(`Failed to detect config paths: ${error}`)
+  }
+}
+
 // ============================================================================
 // CONFIG FILE COMMANDS
 // ============================================================================
@@ -80,11 +144,16 @@ export async function loadConfig(path: string): Promise<WaybarConfigFile> {
  *
  * @param path - Path to save config file
  * @param content - JSON configuration content
+ * @param maxBackups - Backups of the file to keep (default 10)
  * @throws TauriError if validation fails or write fails
  */
-export async function saveConfig(path: string, content: string): Promise<void> {
+export async function saveConfig(
+  path: string,
+  content: string,
+  maxBackups?: number
+): Promise<void> {
   try {
-    await invoke<void>('save_config', { path, content })
+    await invoke<void>('save_config', { path, content, maxBackups })
   } catch (error) {
     throw new Error(`Failed to save config: ${error}`)
   }
@@ -115,11 +184,12 @@ export async function loadCSS(path: string): Promise<string> {
  *
  * @param path - Path to save CSS file
  * @param content - CSS content
+ * @param maxBackups - Backups of the file to keep (default 10)
  * @throws TauriError if content empty or write fails
  */
-export async function saveCSS(path: string, content: string): Promise<void> {
+export async function saveCSS(path: string, content: string, maxBackups?: number): Promise<void> {
   try {
-    await invoke<void>('save_css', { path, content })
+    await invoke<void>('save_css', { path, content, maxBackups })
   } catch (error) {
     throw new Error(`Failed to save CSS: ${error}`)
   }
@@ -129,17 +199,32 @@ export async function saveCSS(path: string, content: string): Promise<void> {
 // BACKUP COMMANDS
 // ============================================================================
 
+/**
+ * A `<name>.backup.<timestamp>` file
+ */
+export interface BackupInfo {
+  filename: string
+  /** Name of the file it backs up (e.g. config.jsonc) */
+  original_file: string
+  /** Unix timestamp in seconds */
+  timestamp: number
+  /** RFC 3339 UTC date */
+  date: string
+  size_bytes: number
+}
+
 /**
  * List all backup files in config directory
  * Returns backups sorted by timestamp (newest first)
  *
  * @param configDir - Path to config directory
- * @returns Array of backup filenames
+ * @param fileFilter - Only list backups of this file (e.g. "style.css")
+ * @returns Backups with their original file, date and size
  * @throws TauriError if directory cannot be read
  */
-export async function listBackups(configDir: string): Promise<string[]> {
+export async function listBackups(configDir: string, fileFilter?: string): Promise<BackupInfo[]> {
   try {
-    return await invoke<string[]>('list_backups', { configDir })
+    return await invoke<BackupInfo[]>('list_backups', { configDir, fileFilter })
   } catch (error) {
     throw new Error(`Failed to list backups: ${error}`)
   }
@@ -239,6 +324,21 @@ export async function getWaybarPids(): Promise<number[]> {
   }
 }
 
+/**
+ * Get the config and style paths the running Waybar was started with
+ * Follows `-c`/`-s` flags, falling back to the default paths without them
+ *
+ * @returns Paths of the files Waybar actually loaded
+ * @throws TauriError if Waybar is not running
+ */
+export async function getRunningWaybarConfigPath(): Promise<ConfigPaths> {
+  try {
+    return await invoke<ConfigPaths>('get_running_waybar_config_path')
+  } catch (error) {
+    throw new Error(`Failed to get running Waybar config path: ${error}`)
+  }
+}
+
 /**
  * Start Waybar process
  * Does nothing if already running
@@ -253,6 +353,22 @@ export async function startWaybar(): Promise<void> {
   }
 }
 
+/**
+ * Start Waybar with a specific config and/or style file
+ * Does nothing if already running
+ *
+ * @param configPath - Config file passed as `-c` (default path when omitted)
+ * @param stylePath - Stylesheet passed as `-s` (default path when omitted)
+ * @throws TauriError if a path doesn't exist or start fails
+ */
+export async function startWaybarWith(configPath?: string, stylePath?: string): Promise<void> {
+  try {
+    await invoke<void>('start_waybar_with', { configPath, stylePath })
+  } catch (error) {
+    throw new Error(`Failed to start Waybar: ${error}`)
+  }
+}
+
 /**
  * Stop Waybar process
  * Does nothing if not running
@@ -269,7 +385,7 @@ export async function stopWaybar(): Promise<void> {
 
 /**
  * Restart Waybar process
- * Stops and starts Waybar with a brief delay
+ * Stops Waybar, waits for it to exit, then starts it again
  *
  * @throws TauriError if restart fails
  */
@@ -291,9 +407,20 @@ export async function restartWaybar(): Promise<void> {
 export interface CompositorInfo {
   name: string
   version?: string
+  /** Version number read from `version` */
+  parsed_version?: SemVer
   session_type: string
 }
 
+/**
+ * A `major.minor.patch` version number
+ */
+export interface SemVer {
+  major: number
+  minor: number
+  patch: number
+}
+
 /**
  * Detect currently running Wayland compositor
  *
@@ -4,7 +4,12 @@
 // ============================================================================
 
 import { vi } from 'vitest'
-import type { ConfigPaths, WaybarConfigFile, CompositorInfo } from '../../lib/tauri/commands'
+import type {
+  BackupInfo,
+  ConfigPaths,
+  WaybarConfigFile,
+  CompositorInfo,
+} from '../../lib/tauri/commands'
 
 // ============================================================================
 // MOCK DATA
@@ -68,13 +73,32 @@ window#waybar {
 export const mockCompositorInfo: CompositorInfo = {
   name: 'hyprland',
   version: '0.35.0',
+  parsed_version: { major: 0, minor: 35, patch: 0 },
   session_type: 'wayland',
 }
 
-export const mockBackups = [
-  'config.backup.2024-01-15-14-30-00',
-  'config.backup.2024-01-14-10-15-00',
-  'config.backup.2024-01-13-16-45-00',
+export const mockBackups: BackupInfo[] = [
+  {
+    filename: 'config.backup.1705329000',
+    original_file: 'config',
+    timestamp: 1705329000,
+    date: '2024-01-15T14:30:00Z',
+    size_bytes: 512,
+  },
+  {
+    filename: 'config.backup.1705227300',
+    original_file: 'config',
+    timestamp: 1705227300,
+    date: '2024-01-14T10:15:00Z',
+    size_bytes: 498,
+  },
+  {
+    filename: 'config.backup.1705164300',
+    original_file: 'config',
+    timestamp: 1705164300,
+    date: '2024-01-13T16:45:00Z',
+    size_bytes: 476,
+  },
 ]
 
 // ============================================================================
@@ -122,9 +146,15 @@ export const mockInvoke = vi.fn((command: string, args?: Record<string, unknown>
     case 'get_waybar_pids':
       return Promise.resolve([12345])
 
+    case 'get_running_waybar_config_path':
+      return Promise.resolve(mockConfigPaths)
+
     case 'start_waybar':
       return Promise.resolve()
 
+    case 'start_waybar_with':
+      return Promise.resolve()
+
     case 'stop_waybar':
       return Promise.resolve()
 
@@ -144,6 +174,10 @@ export const mockInvoke = vi.fn((command: string, args?: Record<string, unknown>
       }
       return Promise.resolve(false)
 
+    // Diagnostics
+    case 'log_command_result':
+      return Promise.resolve()
+
     default:
       return Promise.reject(new Error(`Unknown command: ${command}`))
   }